@@ -1,13 +1,16 @@
 //! Interactive baseline review - walks the user through each finding.
 
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use console::style;
 use dialoguer::{Input, Select, theme::ColorfulTheme};
 use vet_core::prelude::*;
 
-use crate::files::{collect_files, read_text_file};
+use super::{relocate_moved, scan_files};
+use crate::files::collect_files;
+use crate::git;
 use crate::scanning::{build_scanner, load_patterns};
 use crate::ui::{colors, indicators};
 use crate::{BaselineArgs, CONFIG_FILENAME};
@@ -18,8 +21,8 @@ pub fn run(args: &BaselineArgs) -> Result<()> {
     let config_path = args.config.as_deref().unwrap_or_else(|| Path::new(CONFIG_FILENAME));
     let config = Config::load(config_path)?;
 
-    let new_findings = scan_and_filter_findings(args, &config)?;
     let mut baseline = load_or_create_baseline(&args.output)?;
+    let (new_findings, updated) = scan_and_filter_findings(args, &config, &mut baseline)?;
 
     if new_findings.is_empty() {
         println!(
@@ -28,7 +31,7 @@ pub fn run(args: &BaselineArgs) -> Result<()> {
         );
 
         // Save baseline even if empty, so stats command works
-        if !args.output.exists() {
+        if !args.output.exists() || updated {
             baseline.save(&args.output)?;
             println!(
                 "{} baseline saved to {}",
@@ -43,6 +46,8 @@ pub fn run(args: &BaselineArgs) -> Result<()> {
 
     if added_count > 0 {
         save_baseline_with_summary(&mut baseline, &args.output, added_count)?;
+    } else if updated {
+        baseline.save(&args.output)?;
     }
 
     if skipped_count > 0 {
@@ -52,7 +57,18 @@ pub fn run(args: &BaselineArgs) -> Result<()> {
     Ok(())
 }
 
-fn scan_and_filter_findings(args: &BaselineArgs, config: &Config) -> Result<Vec<Finding>> {
+/// Scans for findings not yet in the baseline, carrying entries for moved
+/// files to their new path and updating the last-seen line of the rest.
+/// Returns the new findings and whether any baseline entry changed.
+///
+/// Unlike `vet scan`, this always follows moved files regardless of
+/// `baseline_follow_renames`: it is the command that rewrites the baseline,
+/// and without it every moved secret would come up for review again.
+fn scan_and_filter_findings(
+    args: &BaselineArgs,
+    config: &Config,
+    baseline: &mut Baseline,
+) -> Result<(Vec<Finding>, bool)> {
     let registry = load_patterns(config)?;
     let severity = args.severity.or(config.severity);
     let scanner = build_scanner(registry, severity);
@@ -67,7 +83,7 @@ fn scan_and_filter_findings(args: &BaselineArgs, config: &Config) -> Result<Vec<
 
     if files.is_empty() {
         println!("{} no files to scan", colors::warning().apply_to(indicators::WARNING));
-        return Ok(Vec::new());
+        return Ok((Vec::new(), false));
     }
 
     println!("{} scanning for secrets...", colors::info().apply_to(indicators::INFO));
@@ -75,16 +91,27 @@ fn scan_and_filter_findings(args: &BaselineArgs, config: &Config) -> Result<Vec<
 
     if all_findings.is_empty() {
         println!("{} no secrets found", colors::success().apply_to(indicators::SUCCESS));
-        return Ok(Vec::new());
+        return Ok((Vec::new(), false));
     }
 
-    let baseline = if args.output.exists() {
-        Baseline::load(&args.output)?
-    } else {
-        Baseline::new()
-    };
+    let moved = relocate_moved(baseline, &all_findings);
+    for relocation in &moved {
+        println!(
+            "{} moved {} from {} to {}",
+            colors::info().apply_to(indicators::INFO),
+            relocation.pattern_id,
+            relocation.from,
+            relocation.to
+        );
+    }
+
+    let lines_updated = all_findings
+        .iter()
+        .filter(|f| baseline.record_line(&f.baseline_fingerprint(), f.line()))
+        .count();
+    let updated = !moved.is_empty() || lines_updated > 0;
 
-    let baseline_matcher = IgnoreMatcher::new(Some(&baseline), &config.ignores);
+    let baseline_matcher = IgnoreMatcher::new(Some(baseline), &config.ignores);
     let minimum_confidence = args.minimum_confidence.unwrap_or(config.minimum_confidence);
 
     let new_findings: Vec<_> = all_findings
@@ -93,7 +120,7 @@ fn scan_and_filter_findings(args: &BaselineArgs, config: &Config) -> Result<Vec<
         .filter(|f| f.confidence >= minimum_confidence)
         .collect();
 
-    Ok(new_findings)
+    Ok((new_findings, updated))
 }
 
 fn load_or_create_baseline(output_path: &Path) -> Result<Baseline> {
@@ -126,6 +153,7 @@ fn review_findings_interactively(
 
     let mut added_count = 0;
     let mut skipped_count = 0;
+    let reviewed_by = args.reviewed_by.clone().or_else(git::user_email);
 
     for (idx, finding) in new_findings.iter().enumerate() {
         display_finding_details(idx, new_findings.len(), finding);
@@ -139,13 +167,24 @@ fn review_findings_interactively(
         match action {
             Action::Accept => {
                 let reason = get_reason_for_action(args, "Why are you accepting this finding?")?;
-                add_finding_to_baseline(baseline, finding, BaselineStatus::Accepted, reason);
+                let review = Review {
+                    reason,
+                    reviewed_by: reviewed_by.clone(),
+                    ticket: get_ticket(args)?,
+                    expires_at: get_expiry(args)?,
+                };
+                add_finding_to_baseline(baseline, finding, BaselineStatus::Accepted, review);
                 added_count += 1;
                 println!("{}\n", colors::success().apply_to("✓ Added to baseline"));
             }
             Action::Ignore => {
-                let reason = prompt_for_reason("Why are you ignoring this finding?")?;
-                add_finding_to_baseline(baseline, finding, BaselineStatus::Ignored, reason);
+                let review = Review {
+                    reason: prompt_for_reason("Why are you ignoring this finding?")?,
+                    reviewed_by: reviewed_by.clone(),
+                    ticket: get_ticket(args)?,
+                    expires_at: get_expiry(args)?,
+                };
+                add_finding_to_baseline(baseline, finding, BaselineStatus::Ignored, review);
                 added_count += 1;
                 println!("{}\n", colors::success().apply_to("✓ Ignored in baseline"));
             }
@@ -192,16 +231,68 @@ fn get_reason_for_action(args: &BaselineArgs, prompt: &str) -> Result<String> {
     }
 }
 
-fn add_finding_to_baseline(baseline: &mut Baseline, finding: &Finding, status: BaselineStatus, reason: String) {
-    let baseline_finding = BaselineFinding::new(
+/// Uses `--ticket`, or asks for an optional ticket when reviewing interactively.
+fn get_ticket(args: &BaselineArgs) -> Result<Option<String>> {
+    if args.ticket.is_some() || args.accept_all {
+        return Ok(args.ticket.clone());
+    }
+
+    let ticket: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Rotation ticket (optional)")
+        .allow_empty(true)
+        .interact_text()?;
+
+    Ok(Some(ticket.trim().to_string()).filter(|t| !t.is_empty()))
+}
+
+/// Uses `--expires`, or asks for an optional expiry when reviewing interactively.
+fn get_expiry(args: &BaselineArgs) -> Result<Option<DateTime<Utc>>> {
+    if args.expires.is_some() || args.accept_all {
+        return Ok(args.expires);
+    }
+
+    let expiry: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Expires (YYYY-MM-DD or 90d, optional)")
+        .allow_empty(true)
+        .validate_with(|input: &String| -> Result<(), String> {
+            if input.trim().is_empty() {
+                Ok(())
+            } else {
+                crate::parse_expiry(input.trim()).map(|_| ())
+            }
+        })
+        .interact_text()?;
+
+    let expiry = expiry.trim();
+    if expiry.is_empty() {
+        Ok(None)
+    } else {
+        crate::parse_expiry(expiry).map(Some).map_err(anyhow::Error::msg)
+    }
+}
+
+/// What the reviewer recorded about a finding.
+struct Review {
+    reason: String,
+    reviewed_by: Option<String>,
+    ticket: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+fn add_finding_to_baseline(baseline: &mut Baseline, finding: &Finding, status: BaselineStatus, review: Review) {
+    let mut baseline_finding = BaselineFinding::new(
         finding.baseline_fingerprint(),
         finding.pattern_id.to_string(),
         finding.severity,
         finding.path.to_string_lossy().to_string(),
         finding.secret.hash_hex().to_string(),
         status,
-        reason,
+        review.reason,
     );
+    baseline_finding.reviewed_by = review.reviewed_by;
+    baseline_finding.ticket = review.ticket;
+    baseline_finding.expires_at = review.expires_at;
+    baseline_finding.line = Some(finding.line());
 
     baseline.add_finding(baseline_finding);
 }
@@ -222,21 +313,6 @@ fn save_baseline_with_summary(baseline: &mut Baseline, output_path: &Path, added
     Ok(())
 }
 
-fn scan_files(scanner: &Scanner, files: &[PathBuf], max_file_size: Option<u64>) -> Vec<Finding> {
-    let mut all_findings = Vec::new();
-
-    for path in files {
-        let Some(content) = read_text_file(path, max_file_size) else {
-            continue;
-        };
-
-        let findings = scanner.scan_content(&content, path);
-        all_findings.extend(findings);
-    }
-
-    all_findings
-}
-
 enum Action {
     Accept,
     Ignore,
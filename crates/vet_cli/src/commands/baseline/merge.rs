@@ -0,0 +1,55 @@
+//! Baseline merge - resolves `.vet-baseline.json` conflicts as a git merge driver.
+//!
+//! Registered through `.gitattributes` and `git config merge.vet-baseline.driver`
+//! (see `vet hook install --merge-driver`), so two branches that each accept
+//! findings merge cleanly instead of conflicting over the JSON array.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use vet_core::prelude::*;
+
+use crate::BaselineMergeArgs;
+use crate::ui::{colors, indicators, pluralise_word};
+
+/// Merges the three versions of a baseline by fingerprint and writes the
+/// result over `ours`, or to `--output`.
+pub fn run(args: &BaselineMergeArgs) -> Result<()> {
+    let base = load_version(&args.base)?;
+    let ours = load_version(&args.ours)?;
+    let theirs = load_version(&args.theirs)?;
+
+    let (mut merged, conflicts) = Baseline::merge(&base, &ours, &theirs);
+
+    let output = args.output.as_deref().unwrap_or(&args.ours);
+    merged.save(output)?;
+
+    let count = merged.len();
+    eprintln!(
+        "{} merged baseline: {count} {}{}",
+        colors::success().apply_to(indicators::SUCCESS),
+        pluralise_word(count, "finding", "findings"),
+        if conflicts > 0 {
+            format!(
+                ", {conflicts} {} resolved by most recent review",
+                pluralise_word(conflicts, "conflict", "conflicts")
+            )
+        } else {
+            String::new()
+        }
+    );
+
+    Ok(())
+}
+
+/// Loads one version of the baseline. Git passes an empty file for a side
+/// where the baseline does not exist, such as the ancestor of a baseline
+/// added on both branches.
+fn load_version(path: &Path) -> Result<Baseline> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    if content.trim().is_empty() {
+        return Ok(Baseline::new());
+    }
+
+    Baseline::load(path).with_context(|| format!("failed to load baseline from {}", path.display()))
+}
@@ -1,20 +1,81 @@
 //! Baseline command - manages acknowledged-secret baselines.
 
+mod import;
 mod interactive;
+mod merge;
+mod prune;
 mod stats;
 
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
 use anyhow::Result;
+use vet_core::prelude::*;
 
+use crate::files::read_text_file;
+use crate::git;
 use crate::{BaselineArgs, BaselineSubcommand};
 
+const DEFAULT_BASELINE_PATH: &str = ".vet-baseline.json";
+
 /// Executes the `vet baseline` command, dispatching to a subcommand or
 /// running the interactive baseline review.
 pub fn run(args: &BaselineArgs) -> Result<()> {
     if let Some(subcommand) = &args.command {
         match subcommand {
             BaselineSubcommand::Stats(stats_args) => stats::run(stats_args),
+            BaselineSubcommand::Prune(prune_args) => prune::run(prune_args),
+            BaselineSubcommand::Merge(merge_args) => merge::run(merge_args),
+            BaselineSubcommand::Import(import_args) => import::run(import_args),
         }
     } else {
         interactive::run(args)
     }
 }
+
+/// Returns the baseline given on the command line, else `baseline_path`
+/// from `.vet.toml`, else `.vet-baseline.json`.
+fn resolve_baseline_path<'a>(explicit: Option<&'a Path>, config: &'a Config) -> &'a Path {
+    explicit
+        .or(config.baseline_path.as_deref().map(Path::new))
+        .unwrap_or_else(|| Path::new(DEFAULT_BASELINE_PATH))
+}
+
+fn scan_files(scanner: &Scanner, files: &[PathBuf], max_file_size: Option<u64>) -> Vec<Finding> {
+    let mut all_findings = Vec::new();
+
+    for path in files {
+        let Some(content) = read_text_file(path, max_file_size) else {
+            continue;
+        };
+
+        let findings = scanner.scan_content(&content, path);
+        all_findings.extend(findings);
+    }
+
+    all_findings
+}
+
+/// Carries baseline entries for moved files over to where their secret is
+/// now found, using git renames since the oldest stale review to tell which
+/// entry moved where.
+pub fn relocate_moved(baseline: &mut Baseline, findings: &[Finding]) -> Vec<Relocation> {
+    let present: HashSet<Fingerprint> = findings.iter().map(Finding::baseline_fingerprint).collect();
+    let Some(since) = baseline.stale_findings(&present).map(|f| f.reviewed_at).min() else {
+        return Vec::new();
+    };
+
+    let repo = git::Repo::open_cwd();
+    let root = repo
+        .as_ref()
+        .and_then(git::Repo::work_dir)
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+    let renames = repo
+        .map(|repo| {
+            repo.thread_local()
+                .renames_since(since, &baseline.stale_paths(&present))
+        })
+        .unwrap_or_default();
+
+    baseline.relocate(findings, &renames, &root)
+}
@@ -0,0 +1,182 @@
+//! Baseline prune - removes entries whose secret no longer occurs.
+//!
+//! Fixed secrets leave their fingerprints behind, and a stale entry would
+//! silently suppress the same secret if it were ever committed again.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use vet_core::prelude::*;
+
+use super::{resolve_baseline_path, scan_files};
+use crate::files::{collect_files, is_in_scope};
+use crate::scanning::{build_scanner, load_patterns};
+use crate::ui::{colors, indicators, pluralise_word};
+use crate::{BaselinePruneArgs, CONFIG_FILENAME};
+
+#[derive(Serialize)]
+struct JsonPruneReport<'a> {
+    baseline: String,
+    dry_run: bool,
+    files_scanned: usize,
+    kept: usize,
+    removed: Vec<JsonStaleEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonStaleEntry<'a> {
+    fingerprint: &'a Fingerprint,
+    pattern_id: &'a str,
+    file: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u32>,
+    status: BaselineStatus,
+    reason: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ticket: Option<&'a str>,
+}
+
+/// Rescans the given paths and removes baseline entries whose fingerprint
+/// no longer occurs, or only lists them with `--dry-run`.
+pub fn run(args: &BaselinePruneArgs) -> Result<()> {
+    let config_path = args.config.as_deref().unwrap_or_else(|| Path::new(CONFIG_FILENAME));
+    let config = Config::load(config_path)?;
+
+    let baseline_path = resolve_baseline_path(args.baseline.as_deref(), &config);
+
+    let mut baseline = Baseline::load(baseline_path)
+        .with_context(|| format!("failed to load baseline from {}", baseline_path.display()))?;
+
+    let excludes: Vec<String> = config
+        .exclude_paths
+        .iter()
+        .chain(args.exclude.iter())
+        .cloned()
+        .collect();
+    let files = collect_files(&args.paths, &excludes, true);
+
+    // With nothing scanned every entry would look stale.
+    if files.is_empty() {
+        anyhow::bail!("no files to scan; refusing to prune every baseline entry");
+    }
+
+    // Scan at every severity so entries recorded under any filter are found.
+    let scanner = build_scanner(load_patterns(&config)?, None);
+    let mut seen: HashSet<Fingerprint> = scan_files(&scanner, &files, config.max_file_size)
+        .iter()
+        .map(Finding::baseline_fingerprint)
+        .collect();
+
+    // Entries for files outside the scanned paths were never looked for.
+    seen.extend(
+        baseline
+            .findings
+            .iter()
+            .filter(|f| !is_in_scope(&args.paths, &excludes, Path::new(&f.file)))
+            .map(|f| f.fingerprint.clone()),
+    );
+
+    let removed = if args.dry_run {
+        baseline.stale_findings(&seen).cloned().collect()
+    } else {
+        baseline.prune(&seen)
+    };
+
+    if !args.dry_run && !removed.is_empty() {
+        baseline.save(baseline_path)?;
+    }
+
+    if args.json {
+        print_json_report(args, baseline_path, files.len(), &baseline, &removed)
+    } else {
+        print_text_report(args, baseline_path, &removed);
+        Ok(())
+    }
+}
+
+fn print_text_report(args: &BaselinePruneArgs, baseline_path: &Path, removed: &[BaselineFinding]) {
+    if removed.is_empty() {
+        println!(
+            "{} no stale entries in {}",
+            colors::success().apply_to(indicators::SUCCESS),
+            baseline_path.display()
+        );
+        return;
+    }
+
+    let count = removed.len();
+    println!(
+        "{} {} stale baseline {}",
+        colors::accent().apply_to("●"),
+        count,
+        pluralise_word(count, "entry", "entries")
+    );
+
+    for finding in removed {
+        let location = finding
+            .line
+            .map_or_else(|| finding.file.clone(), |line| format!("{}:{line}", finding.file));
+        println!(
+            "  {} {} {}",
+            colors::muted().apply_to("-"),
+            style(&finding.pattern_id).cyan(),
+            colors::secondary().apply_to(location)
+        );
+    }
+    println!();
+
+    if args.dry_run {
+        println!(
+            "{} dry run, {} not modified",
+            colors::info().apply_to(indicators::INFO),
+            baseline_path.display()
+        );
+    } else {
+        println!(
+            "{} removed {} {} from {}",
+            colors::success().apply_to(indicators::SUCCESS),
+            count,
+            pluralise_word(count, "entry", "entries"),
+            baseline_path.display()
+        );
+    }
+}
+
+fn print_json_report(
+    args: &BaselinePruneArgs,
+    baseline_path: &Path,
+    files_scanned: usize,
+    baseline: &Baseline,
+    removed: &[BaselineFinding],
+) -> Result<()> {
+    let kept = if args.dry_run {
+        baseline.len() - removed.len()
+    } else {
+        baseline.len()
+    };
+
+    let report = JsonPruneReport {
+        baseline: baseline_path.display().to_string(),
+        dry_run: args.dry_run,
+        files_scanned,
+        kept,
+        removed: removed
+            .iter()
+            .map(|f| JsonStaleEntry {
+                fingerprint: &f.fingerprint,
+                pattern_id: &f.pattern_id,
+                file: &f.file,
+                line: f.line,
+                status: f.status,
+                reason: &f.reason,
+                ticket: f.ticket.as_deref(),
+            })
+            .collect(),
+    };
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
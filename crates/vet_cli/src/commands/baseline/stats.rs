@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use anyhow::{Context, Result};
-use chrono::Local;
+use chrono::{DateTime, Duration, Local, Utc};
 use console::style;
 use vet_core::prelude::*;
 
@@ -14,7 +14,8 @@ const TOP_PATTERNS_DISPLAY_LIMIT: usize = 10;
 const PROGRESS_BAR_WIDTH: usize = 20;
 
 /// Loads a baseline file and prints summary statistics (counts by status,
-/// severity, and pattern) in text or JSON format.
+/// severity, and pattern, and entries that have expired or expire soon) in
+/// text or JSON format.
 pub fn run(args: &BaselineStatsArgs) -> Result<()> {
     let baseline_path = args
         .baseline
@@ -24,16 +25,48 @@ pub fn run(args: &BaselineStatsArgs) -> Result<()> {
     let baseline = Baseline::load(baseline_path)
         .with_context(|| format!("failed to load baseline from {}", baseline_path.display()))?;
 
+    let expiry = ExpiryReport::build(&baseline, Utc::now(), args.expiring_within);
+
     if args.json {
-        print_json_stats(&baseline)?;
+        print_json_stats(&baseline, &expiry)?;
     } else {
-        print_text_stats(&baseline, baseline_path);
+        print_text_stats(&baseline, baseline_path, &expiry);
     }
 
     Ok(())
 }
 
-fn print_text_stats(baseline: &Baseline, baseline_path: &Path) {
+/// Entries with an expiry date that has passed or falls within the window.
+struct ExpiryReport<'a> {
+    window_days: u32,
+    expired: Vec<&'a BaselineFinding>,
+    expiring: Vec<&'a BaselineFinding>,
+}
+
+impl<'a> ExpiryReport<'a> {
+    fn build(baseline: &'a Baseline, now: DateTime<Utc>, window_days: u32) -> Self {
+        let horizon = now + Duration::days(i64::from(window_days));
+        let mut report = Self {
+            window_days,
+            expired: Vec::new(),
+            expiring: Vec::new(),
+        };
+
+        for finding in &baseline.findings {
+            match finding.expires_at {
+                Some(_) if finding.is_expired_at(now) => report.expired.push(finding),
+                Some(expires_at) if expires_at <= horizon => report.expiring.push(finding),
+                _ => {}
+            }
+        }
+
+        report.expired.sort_by_key(|f| f.expires_at);
+        report.expiring.sort_by_key(|f| f.expires_at);
+        report
+    }
+}
+
+fn print_text_stats(baseline: &Baseline, baseline_path: &Path, expiry: &ExpiryReport) {
     let total = baseline.len();
     let (accepted_count, ignored_count) = count_by_status(baseline);
 
@@ -120,10 +153,37 @@ fn print_text_stats(baseline: &Baseline, baseline_path: &Path) {
             newest.file
         );
     }
+
+    print_expiry_list("Expired", &expiry.expired);
+    print_expiry_list(
+        &format!("Expiring within {} days", expiry.window_days),
+        &expiry.expiring,
+    );
+}
+
+fn print_expiry_list(title: &str, findings: &[&BaselineFinding]) {
+    if findings.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{}:", style(title).bold());
+    for finding in findings {
+        let expires = finding
+            .expires_at
+            .map(|at| at.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let ticket = finding
+            .ticket
+            .as_deref()
+            .map(|ticket| format!(" [{ticket}]"))
+            .unwrap_or_default();
+        println!("  {expires}  {} in {}{ticket}", finding.pattern_id, finding.file);
+    }
 }
 
 fn print_status_bar(label: &str, count: usize, total: usize) {
-    let percentage = if total > 0 { (count * 100) / total } else { 0 };
+    let percentage = (count * 100).checked_div(total).unwrap_or(0);
     let bar_length = PROGRESS_BAR_WIDTH;
     let filled = (count * bar_length) / total.max(1);
 
@@ -134,7 +194,7 @@ fn print_status_bar(label: &str, count: usize, total: usize) {
 }
 
 fn print_severity_bar(label: &str, count: usize, total: usize, severity: Severity) {
-    let percentage = if total > 0 { (count * 100) / total } else { 0 };
+    let percentage = (count * 100).checked_div(total).unwrap_or(0);
     let bar_length = PROGRESS_BAR_WIDTH;
     let filled = (count * bar_length) / total.max(1);
 
@@ -171,7 +231,7 @@ fn find_oldest_and_newest(baseline: &Baseline) -> Option<(&BaselineFinding, &Bas
     Some((oldest, newest))
 }
 
-fn print_json_stats(baseline: &Baseline) -> Result<()> {
+fn print_json_stats(baseline: &Baseline, expiry: &ExpiryReport) -> Result<()> {
     let (accepted_count, ignored_count) = count_by_status(baseline);
     let severity_counts = count_by_severity(baseline);
     let pattern_counts = count_by_pattern(baseline);
@@ -190,12 +250,26 @@ fn print_json_stats(baseline: &Baseline) -> Result<()> {
         "created_at": baseline.created_at.to_rfc3339(),
         "updated_at": baseline.updated_at.to_rfc3339(),
         "vet_version": baseline.vet_version,
+        "expiring_within_days": expiry.window_days,
+        "expired": expiry.expired.iter().map(|f| expiry_json(f)).collect::<Vec<_>>(),
+        "expiring": expiry.expiring.iter().map(|f| expiry_json(f)).collect::<Vec<_>>(),
     });
 
     println!("{}", serde_json::to_string_pretty(&stats)?);
     Ok(())
 }
 
+fn expiry_json(finding: &BaselineFinding) -> serde_json::Value {
+    serde_json::json!({
+        "fingerprint": finding.fingerprint,
+        "pattern_id": finding.pattern_id,
+        "file": finding.file,
+        "expires_at": finding.expires_at.map(|at| at.to_rfc3339()),
+        "reviewed_by": finding.reviewed_by,
+        "ticket": finding.ticket,
+    })
+}
+
 fn count_by_status(baseline: &Baseline) -> (usize, usize) {
     let mut accepted = 0;
     let mut ignored = 0;
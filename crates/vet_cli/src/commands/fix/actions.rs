@@ -1,10 +1,13 @@
 //! Fix actions and their application logic.
 
+use std::ops::Range;
 use std::path::Path;
 
-use vet_core::Finding;
+use vet_core::ast::{self, SourceLanguage};
 use vet_core::comment_syntax::{self, IGNORE_MARKER};
+pub use vet_core::env_file::derive_env_var_name;
 use vet_core::text::{find_line_end, find_line_start};
+use vet_core::{Finding, FixPolicy};
 
 const REDACTED_MARKER: &str = "<REDACTED>";
 
@@ -15,6 +18,9 @@ pub enum FixAction {
     Redact,
     /// Replace with an environment variable placeholder.
     Placeholder(String),
+    /// Replace the string literal with the language's lookup of this
+    /// environment variable.
+    ExtractToEnv(String),
     /// Delete the entire line containing the secret.
     DeleteLine,
     /// Append a `vet:ignore` comment to the line.
@@ -28,10 +34,40 @@ impl FixAction {
         match self {
             Self::Redact => "Redact",
             Self::Placeholder(_) => "Placeholder",
+            Self::ExtractToEnv(_) => "Extract to env",
             Self::DeleteLine => "Delete line",
             Self::Ignore => "Ignore",
         }
     }
+
+    /// Returns the action name as used by `--action` and the `[fix]` table.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Redact => "redact",
+            Self::Placeholder(_) => "placeholder",
+            Self::ExtractToEnv(_) => "extract-env",
+            Self::DeleteLine => "delete-line",
+            Self::Ignore => "ignore",
+        }
+    }
+
+    /// Builds the action a configured policy asks for, or `None` when the
+    /// finding should be left alone. `Ignore` needs a comment syntax and
+    /// `ExtractEnv` a supported language, so they resolve to `None` for
+    /// other file types.
+    #[must_use]
+    pub fn from_policy(policy: FixPolicy, finding: &Finding) -> Option<Self> {
+        match policy {
+            FixPolicy::Redact => Some(Self::Redact),
+            FixPolicy::Placeholder => Some(Self::Placeholder(derive_env_var_name(&finding.pattern_id))),
+            FixPolicy::ExtractEnv => ast::detect_language(&finding.path)
+                .map(|_| Self::ExtractToEnv(derive_env_var_name(&finding.pattern_id))),
+            FixPolicy::DeleteLine => Some(Self::DeleteLine),
+            FixPolicy::Ignore => comment_syntax::for_path(&finding.path).map(|_| Self::Ignore),
+            FixPolicy::Skip => None,
+        }
+    }
 }
 
 /// Outcome of applying a fix action to a document.
@@ -72,6 +108,15 @@ pub fn apply_action(content: &mut String, finding: &Finding, action: &FixAction,
             let replacement = format!("${{{env_var}}}");
             replace_span(content, adjusted_start, adjusted_end, &replacement)
         }
+        FixAction::ExtractToEnv(env_var) => {
+            let Some((language, literal)) = env_literal(content, finding, adjusted_start, adjusted_end) else {
+                return ApplyResult {
+                    success: false,
+                    bytes_changed: 0,
+                };
+            };
+            replace_span(content, literal.start, literal.end, &language.env_lookup(env_var))
+        }
         FixAction::DeleteLine => delete_line(content, adjusted_start),
         FixAction::Ignore => append_ignore_comment(content, adjusted_start, &finding.path),
     };
@@ -82,15 +127,39 @@ pub fn apply_action(content: &mut String, finding: &Finding, action: &FixAction,
     }
 }
 
-/// Derives an environment variable name from a pattern ID (e.g. `"payments/stripe-secret"` becomes `"STRIPE_SECRET"`).
+/// Returns the secret's current text, after earlier edits shifted it by `offset`.
+#[must_use]
+pub fn secret_text<'a>(content: &'a str, finding: &Finding, offset: isize) -> Option<&'a str> {
+    let start = adjust_offset(finding.span.byte_start, offset)?;
+    let end = adjust_offset(finding.span.byte_end, offset)?;
+    content.get(start..end)
+}
+
+/// Returns the language lookup that would replace the secret's string
+/// literal, or `None` when extracting to an environment variable is not
+/// possible for this finding.
+#[must_use]
+pub fn env_lookup_preview(content: &str, finding: &Finding, offset: isize, env_var: &str) -> Option<String> {
+    let start = adjust_offset(finding.span.byte_start, offset)?;
+    let end = adjust_offset(finding.span.byte_end, offset)?;
+    let (language, _) = env_literal(content, finding, start, end)?;
+    Some(language.env_lookup(env_var))
+}
+
+/// Returns the module the finding's language lookup needs when the file does
+/// not import it yet, in which case extracting is not offered.
 #[must_use]
-pub fn derive_env_var_name(pattern_id: &str) -> String {
-    pattern_id
-        .split('/')
-        .next_back()
-        .unwrap_or(pattern_id)
-        .replace('-', "_")
-        .to_uppercase()
+pub fn missing_env_import(content: &str, finding: &Finding) -> Option<&'static str> {
+    ast::detect_language(&finding.path)?.missing_env_import(content)
+}
+
+fn env_literal(content: &str, finding: &Finding, start: usize, end: usize) -> Option<(SourceLanguage, Range<usize>)> {
+    let language = ast::detect_language(&finding.path)?;
+    if language.missing_env_import(content).is_some() {
+        return None;
+    }
+    let literal = ast::find_string_literal(content, language, start, end)?;
+    Some((language, literal))
 }
 
 /// Before-and-after text for previewing a fix action.
@@ -129,6 +198,18 @@ pub fn generate_preview(content: &str, finding: &Finding, action: &FixAction, of
             let (prefix, suffix) = split_around_secret(original_line, adjusted_start, adjusted_end, line_start);
             format!("{prefix}${{{env_var}}}{suffix}")
         }
+        FixAction::ExtractToEnv(env_var) => {
+            let (language, literal) = env_literal(content, finding, adjusted_start, adjusted_end)?;
+            if literal.start < line_start || literal.end > line_end {
+                return None;
+            }
+            let lookup = language.env_lookup(env_var);
+            format!(
+                "{}{lookup}{}",
+                &content[line_start..literal.start],
+                &content[literal.end..line_end]
+            )
+        }
         FixAction::DeleteLine => String::new(),
         FixAction::Ignore => {
             let syntax = comment_syntax::for_path(&finding.path)?;
@@ -211,21 +292,6 @@ fn append_ignore_comment(content: &mut String, byte_pos: usize, path: &Path) ->
 mod tests {
     use super::*;
 
-    #[test]
-    fn derive_env_var_name_extracts_after_slash() {
-        assert_eq!(derive_env_var_name("payments/stripe-secret"), "STRIPE_SECRET");
-    }
-
-    #[test]
-    fn derive_env_var_name_converts_hyphens() {
-        assert_eq!(derive_env_var_name("ai/openai-api-key"), "OPENAI_API_KEY");
-    }
-
-    #[test]
-    fn derive_env_var_name_handles_no_slash() {
-        assert_eq!(derive_env_var_name("simple-pattern"), "SIMPLE_PATTERN");
-    }
-
     #[test]
     fn replace_span_shorter_replacement() {
         let mut content = String::from("key = sk_live_abc123xyz");
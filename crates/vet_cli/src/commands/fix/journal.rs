@@ -0,0 +1,380 @@
+//! Undo journal for fix sessions.
+//!
+//! Every `vet fix` run that modifies files records the original text of the
+//! changed region of each file, so `vet fix --undo` can put it back. Journals
+//! live in `.git/vet/fix/`, or in a per-directory temp folder outside a git
+//! repository. They contain the original secrets, so they are written with
+//! owner-only permissions and never inside the working tree.
+//!
+//! A session only ever touches files under its root: the repository's working
+//! tree, or the current directory outside a repository.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use vet_core::fs_util::{atomic_write, atomic_write_private};
+
+use crate::git::Repo;
+
+const JOURNAL_VERSION: u32 = 1;
+const JOURNAL_EXTENSION: &str = "json";
+
+/// Original text of every region a fix session changed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Journal {
+    version: u32,
+    session: String,
+    created_at: DateTime<Utc>,
+    files: Vec<JournalEntry>,
+    /// Files the session changed that undo leaves in place, such as `.env`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    kept: Vec<PathBuf>,
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+/// What `undo` restored and what it left alone.
+#[derive(Debug)]
+pub struct Undone {
+    /// The session that was undone.
+    pub session: String,
+    /// Files put back to their state before the session.
+    pub restored: Vec<PathBuf>,
+    /// Files the session changed that were left as they are.
+    pub kept: Vec<PathBuf>,
+}
+
+/// Where journals are kept and the root their sessions may touch.
+struct Location {
+    dir: PathBuf,
+    root: PathBuf,
+}
+
+/// The single region of one file a session replaced.
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    path: PathBuf,
+    offset: usize,
+    original: String,
+    replacement: String,
+    sha256_after: String,
+}
+
+impl Journal {
+    /// Starts an empty journal for a new session rooted at the current
+    /// repository or directory.
+    pub fn new() -> anyhow::Result<Self> {
+        let created_at = Utc::now();
+        Ok(Self {
+            version: JOURNAL_VERSION,
+            session: created_at.format("%Y%m%dT%H%M%S%3fZ").to_string(),
+            created_at,
+            files: Vec::new(),
+            kept: Vec::new(),
+            root: location()?.root,
+        })
+    }
+
+    /// Returns the session identifier accepted by `vet fix --undo`.
+    #[must_use]
+    pub fn session(&self) -> &str {
+        &self.session
+    }
+
+    /// Returns `true` if no file has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns the canonical path of `path`, failing if it lies outside the
+    /// session root, where undo would not restore it.
+    pub fn check_path(&self, path: &Path) -> anyhow::Result<PathBuf> {
+        let path = fs::canonicalize(path).with_context(|| format!("failed to resolve {}", path.display()))?;
+        if !path.starts_with(&self.root) {
+            bail!(
+                "refusing to fix {}: outside {}, so the change could not be undone",
+                path.display(),
+                self.root.display()
+            );
+        }
+        Ok(path)
+    }
+
+    /// Records the change from `before` to `after` for `path`, keeping only
+    /// the region between their common prefix and suffix.
+    ///
+    /// Refuses files outside the session root, as [`Journal::check_path`] does.
+    pub fn record(&mut self, path: &Path, before: &str, after: &str) -> anyhow::Result<()> {
+        let path = self.check_path(path)?;
+        let (offset, original, replacement) = changed_region(before, after);
+
+        self.files.push(JournalEntry {
+            path,
+            offset,
+            original: original.to_string(),
+            replacement: replacement.to_string(),
+            sha256_after: sha256_hex(after),
+        });
+        Ok(())
+    }
+
+    /// Notes a file the session changed without recording its content, so
+    /// undo can say it was left in place.
+    pub fn keep(&mut self, path: PathBuf) {
+        if !self.kept.contains(&path) {
+            self.kept.push(path);
+        }
+    }
+
+    /// Writes the journal to the journal directory, returning its path.
+    pub fn save(&self) -> anyhow::Result<PathBuf> {
+        let dir = location()?.dir;
+        create_private_dir(&dir)?;
+
+        let path = dir.join(format!("{}.{JOURNAL_EXTENSION}", self.session));
+        let json = serde_json::to_string_pretty(self)?;
+        atomic_write_private(&path, &json).with_context(|| format!("failed to write {}", path.display()))?;
+
+        Ok(path)
+    }
+}
+
+/// Restores the files changed by `session`, or by the most recent session
+/// when `None`, and deletes its journal.
+///
+/// Refuses without touching anything if any of the files has been modified
+/// since the fix was applied, or if the journal names a file outside the
+/// session root or a region the file does not hold.
+pub fn undo(session: Option<&str>) -> anyhow::Result<Undone> {
+    let Location { dir, root } = location()?;
+    create_private_dir(&dir)?;
+    let path = match session {
+        Some(session) => {
+            if session.contains(['/', '\\']) || session.starts_with('.') {
+                bail!("invalid fix session '{session}'");
+            }
+            dir.join(format!("{session}.{JOURNAL_EXTENSION}"))
+        }
+        None => latest_journal(&dir)?.context("no fix sessions to undo")?,
+    };
+
+    let content = fs::read_to_string(&path).with_context(|| match session {
+        Some(session) => format!("no journal for fix session '{session}'"),
+        None => format!("failed to read {}", path.display()),
+    })?;
+    let journal: Journal =
+        serde_json::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))?;
+
+    if journal.version != JOURNAL_VERSION {
+        bail!("unsupported fix journal version {}", journal.version);
+    }
+
+    for entry in &journal.files {
+        if !entry.path.starts_with(&root) {
+            bail!(
+                "refusing to undo fix session '{}': {} is outside {}",
+                journal.session,
+                entry.path.display(),
+                root.display()
+            );
+        }
+    }
+
+    let mut current = Vec::with_capacity(journal.files.len());
+    let mut changed = Vec::new();
+    for entry in &journal.files {
+        match fs::read_to_string(&entry.path) {
+            Ok(text) if sha256_hex(&text) == entry.sha256_after => current.push(text),
+            _ => changed.push(entry.path.display().to_string()),
+        }
+    }
+
+    if !changed.is_empty() {
+        bail!(
+            "refusing to undo fix session '{}': modified since the fix was applied:\n  {}",
+            journal.session,
+            changed.join("\n  ")
+        );
+    }
+
+    for (entry, text) in journal.files.iter().zip(&current) {
+        let region = entry
+            .offset
+            .checked_add(entry.replacement.len())
+            .and_then(|end| text.get(entry.offset..end));
+        if region != Some(entry.replacement.as_str()) {
+            bail!(
+                "refusing to undo fix session '{}': journal does not match {}",
+                journal.session,
+                entry.path.display()
+            );
+        }
+    }
+
+    let mut restored = Vec::with_capacity(journal.files.len());
+    for (entry, mut text) in journal.files.into_iter().zip(current) {
+        text.replace_range(entry.offset..entry.offset + entry.replacement.len(), &entry.original);
+        atomic_write(&entry.path, &text).with_context(|| format!("failed to write {}", entry.path.display()))?;
+        restored.push(entry.path);
+    }
+
+    fs::remove_file(&path).with_context(|| format!("failed to remove {}", path.display()))?;
+
+    Ok(Undone {
+        session: journal.session,
+        restored,
+        kept: journal.kept,
+    })
+}
+
+/// Returns `.git/vet/fix` and the working tree inside a repository, or a temp
+/// directory keyed by the working directory and that directory outside one.
+fn location() -> anyhow::Result<Location> {
+    let cwd = std::env::current_dir().context("failed to read current directory")?;
+    let cwd = fs::canonicalize(&cwd).unwrap_or(cwd);
+
+    if let Some(repo) = Repo::discover(&cwd) {
+        let root = repo.work_dir().map_or_else(
+            || cwd.clone(),
+            |dir| fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf()),
+        );
+        return Ok(Location {
+            dir: repo.git_dir().join("vet").join("fix"),
+            root,
+        });
+    }
+
+    let key = sha256_hex(&cwd.to_string_lossy());
+    Ok(Location {
+        dir: std::env::temp_dir().join("vet-fix").join(&key[..16]),
+        root: cwd,
+    })
+}
+
+/// Creates the journal directory readable by its owner only, refusing one
+/// that someone else owns, since the temp directory is shared.
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> anyhow::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)
+        .with_context(|| format!("failed to create {}", dir.display()))?;
+
+    let metadata = fs::symlink_metadata(dir).with_context(|| format!("failed to read {}", dir.display()))?;
+    // SAFETY: `geteuid` has no preconditions and cannot fail.
+    #[expect(unsafe_code, reason = "geteuid is a plain system call with no safe wrapper in std")]
+    let uid = unsafe { libc::geteuid() };
+    if !metadata.is_dir() || metadata.uid() != uid {
+        bail!(
+            "refusing to use {}: not a directory owned by the current user",
+            dir.display()
+        );
+    }
+
+    if metadata.mode() & 0o077 != 0 {
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("failed to restrict {}", dir.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))
+}
+
+/// Session IDs are timestamps, so the lexically greatest journal is the newest.
+fn latest_journal(dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(None);
+    };
+
+    let mut journals = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == JOURNAL_EXTENSION) {
+            journals.push(path);
+        }
+    }
+
+    Ok(journals.into_iter().max())
+}
+
+/// Splits `before` and `after` into their shared prefix and suffix, returning
+/// the offset of the differing region and its text in each.
+fn changed_region<'a>(before: &'a str, after: &'a str) -> (usize, &'a str, &'a str) {
+    let mut prefix = before.bytes().zip(after.bytes()).take_while(|(a, b)| a == b).count();
+    while !before.is_char_boundary(prefix) || !after.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+
+    let max_suffix = before.len().min(after.len()) - prefix;
+    let mut suffix = before
+        .bytes()
+        .rev()
+        .zip(after.bytes().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !before.is_char_boundary(before.len() - suffix) || !after.is_char_boundary(after.len() - suffix) {
+        suffix -= 1;
+    }
+
+    (
+        prefix,
+        &before[prefix..before.len() - suffix],
+        &after[prefix..after.len() - suffix],
+    )
+}
+
+fn sha256_hex(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_region_isolates_replacement() {
+        let (offset, original, replacement) = changed_region("key = abc123;\n", "key = <REDACTED>;\n");
+        assert_eq!(offset, 6);
+        assert_eq!(original, "abc123");
+        assert_eq!(replacement, "<REDACTED>");
+    }
+
+    #[test]
+    fn changed_region_handles_deleted_line() {
+        let (offset, original, replacement) = changed_region("a\nsecret\nb\n", "a\nb\n");
+        assert_eq!(offset, 2);
+        assert_eq!(original, "secret\n");
+        assert_eq!(replacement, "");
+    }
+
+    #[test]
+    fn changed_region_respects_char_boundaries() {
+        let (offset, original, replacement) = changed_region("x = é", "x = è");
+        assert_eq!(offset, 4);
+        assert_eq!(original, "é");
+        assert_eq!(replacement, "è");
+    }
+
+    #[test]
+    fn changed_region_restores_original_text() {
+        let before = "one\ntwo = secret\nthree\n";
+        let after = "one\ntwo = <REDACTED> // vet:ignore\nthree\n";
+        let (offset, original, replacement) = changed_region(before, after);
+
+        let mut restored = after.to_string();
+        restored.replace_range(offset..offset + replacement.len(), original);
+        assert_eq!(restored, before);
+    }
+}
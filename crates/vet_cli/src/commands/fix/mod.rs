@@ -1,56 +1,113 @@
-//! Fix command - interactively fix detected secrets.
+//! Fix command - fix detected secrets interactively, or in bulk with `--yes`.
 
 mod actions;
+mod journal;
 mod prompts;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write as _;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context as _;
+use serde::Serialize;
+use vet_core::env_file::{self, DOTENV_FILENAME, GITIGNORE_FILENAME};
 use vet_core::fs_util::atomic_write;
 use vet_core::prelude::*;
 
 use self::actions::{FixAction, apply_action};
-use crate::CONFIG_FILENAME;
+use self::journal::Journal;
 use crate::files::{collect_files, read_text_file};
 use crate::scanning::{build_scanner, load_patterns};
 use crate::ui::{colors, indicators, pluralise_word, print_command_header};
+use crate::{CONFIG_FILENAME, FixArgs};
 
-#[derive(Default)]
+#[derive(Default, Serialize)]
 struct FixStats {
     redacted: usize,
     placeholders: usize,
+    extracted: usize,
     deleted: usize,
     ignored: usize,
     skipped: usize,
     files_modified: usize,
 }
 
-/// Executes the `vet fix` command.
-pub fn run(
-    paths: &[PathBuf],
-    config_path: Option<&Path>,
-    severity: Option<Severity>,
-    exclude: &[String],
-    skip_gitignore: bool,
+/// A single finding and what was done about it, reported by `--json`.
+#[derive(Serialize)]
+struct FixChange {
+    file: String,
+    line: u32,
+    pattern_id: String,
+    action: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    env_var: Option<String>,
+    /// Why the requested action could not be applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FixReport {
     dry_run: bool,
-    max_file_size: Option<u64>,
-) -> anyhow::Result<()> {
-    print_command_header("fix");
+    /// Session to pass to `vet fix --undo`, when any file was modified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session: Option<String>,
+    summary: FixStats,
+    changes: Vec<FixChange>,
+    #[serde(skip)]
+    journal: Journal,
+    /// Directory whose `.env` extracted secrets are moved into.
+    #[serde(skip)]
+    project_root: PathBuf,
+}
+
+/// How each finding's action is chosen.
+enum Mode<'a> {
+    /// Prompt the user for every finding.
+    Interactive,
+    /// Apply the `[fix]` policy for the pattern, falling back to `--action`.
+    Automatic {
+        config: &'a Config,
+        default: Option<FixPolicy>,
+    },
+}
+
+impl Mode<'_> {
+    fn is_interactive(&self) -> bool {
+        matches!(self, Self::Interactive)
+    }
+}
 
-    let config_path = config_path.unwrap_or(Path::new(CONFIG_FILENAME));
+/// Executes the `vet fix` command.
+pub fn run(args: &FixArgs) -> anyhow::Result<()> {
+    if let Some(session) = &args.undo {
+        return run_undo(session.as_deref());
+    }
+
+    let quiet = args.json;
+    if !quiet {
+        print_command_header("fix");
+    }
+
+    let config_path = args.config.as_deref().unwrap_or(Path::new(CONFIG_FILENAME));
     let config = Config::load(config_path).context("loading config")?;
 
     let registry = load_patterns(&config)?;
-    let severity = severity.or(config.severity);
+    let severity = args.severity.or(config.severity);
     let scanner = build_scanner(registry, severity);
-    let max_file_size = max_file_size.or(config.max_file_size);
+    let max_file_size = args.max_file_size.or(config.max_file_size);
 
-    let excludes: Vec<String> = config.exclude_paths.iter().chain(exclude.iter()).cloned().collect();
+    let excludes: Vec<String> = config
+        .exclude_paths
+        .iter()
+        .chain(args.exclude.iter())
+        .cloned()
+        .collect();
 
-    let files = collect_files(paths, &excludes, !skip_gitignore);
+    let files = collect_files(&args.paths, &excludes, !args.skip_gitignore);
 
-    if files.is_empty() {
+    if files.is_empty() && !quiet {
         println!("{} no files to scan", colors::warning().apply_to(indicators::WARNING));
         return Ok(());
     }
@@ -58,34 +115,108 @@ pub fn run(
     let findings_by_file = scan_files(&scanner, &files, max_file_size);
 
     let total_findings: usize = findings_by_file.values().map(Vec::len).sum();
-    if total_findings == 0 {
-        println!("{} no secrets found", colors::success().apply_to(indicators::SUCCESS));
-        return Ok(());
-    }
 
-    println!(
-        "{} {} {} in {} {}",
-        colors::info().apply_to(indicators::INFO),
-        total_findings,
-        pluralise_word(total_findings, "secret", "secrets"),
-        findings_by_file.len(),
-        pluralise_word(findings_by_file.len(), "file", "files"),
-    );
+    if !quiet {
+        if total_findings == 0 {
+            println!("{} no secrets found", colors::success().apply_to(indicators::SUCCESS));
+            return Ok(());
+        }
 
-    if dry_run {
         println!(
-            "  {}",
-            colors::muted().apply_to("(dry run - no files will be modified)")
+            "{} {} {} in {} {}",
+            colors::info().apply_to(indicators::INFO),
+            total_findings,
+            pluralise_word(total_findings, "secret", "secrets"),
+            findings_by_file.len(),
+            pluralise_word(findings_by_file.len(), "file", "files"),
         );
+
+        if args.dry_run {
+            println!(
+                "  {}",
+                colors::muted().apply_to("(dry run - no files will be modified)")
+            );
+        }
     }
 
-    let stats = process_files(findings_by_file, total_findings, dry_run)?;
+    let mode = if args.yes {
+        Mode::Automatic {
+            config: &config,
+            default: args.action,
+        }
+    } else {
+        Mode::Interactive
+    };
 
-    print_summary(&stats, dry_run);
+    let report = process_files(
+        findings_by_file,
+        total_findings,
+        &mode,
+        project_root(config_path),
+        args.dry_run,
+        quiet,
+    )?;
+
+    if quiet {
+        let mut stdout = std::io::stdout().lock();
+        serde_json::to_writer_pretty(&mut stdout, &report)?;
+        writeln!(stdout)?;
+    } else {
+        print_summary(&report.summary, args.dry_run);
+        if let Some(session) = &report.session {
+            println!(
+                "  {}",
+                colors::muted().apply_to(format!("undo with `vet fix --undo {session}`"))
+            );
+            println!();
+        }
+    }
 
     Ok(())
 }
 
+fn run_undo(session: Option<&str>) -> anyhow::Result<()> {
+    print_command_header("fix");
+
+    let undone = journal::undo(session)?;
+    let restored = &undone.restored;
+
+    for path in restored {
+        println!(
+            "  {} {}",
+            colors::muted().apply_to("↺"),
+            colors::secondary().apply_to(path.display().to_string()),
+        );
+    }
+    println!();
+    println!(
+        "{} restored {} {} from session {}",
+        colors::success().apply_to(indicators::SUCCESS),
+        restored.len(),
+        pluralise_word(restored.len(), "file", "files"),
+        undone.session,
+    );
+    for path in &undone.kept {
+        println!(
+            "{} left {} in place; remove the extracted values by hand if no longer needed",
+            colors::warning().apply_to(indicators::WARNING),
+            path.display(),
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Returns the directory holding the config file, which `.env` and
+/// `.gitignore` live next to.
+fn project_root(config_path: &Path) -> PathBuf {
+    match config_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
 fn scan_files(scanner: &Scanner, files: &[PathBuf], max_file_size: Option<u64>) -> HashMap<PathBuf, Vec<Finding>> {
     let mut findings_by_file: HashMap<PathBuf, Vec<Finding>> = HashMap::new();
 
@@ -106,29 +237,63 @@ fn scan_files(scanner: &Scanner, files: &[PathBuf], max_file_size: Option<u64>)
 fn process_files(
     findings_by_file: HashMap<PathBuf, Vec<Finding>>,
     total_findings: usize,
+    mode: &Mode<'_>,
+    project_root: PathBuf,
     dry_run: bool,
-) -> anyhow::Result<FixStats> {
-    let mut stats = FixStats::default();
+    quiet: bool,
+) -> anyhow::Result<FixReport> {
+    let mut report = FixReport {
+        dry_run,
+        session: None,
+        summary: FixStats::default(),
+        changes: Vec::new(),
+        journal: Journal::new()?,
+        project_root,
+    };
     let mut finding_index = 0;
 
     let mut sorted_files: Vec<_> = findings_by_file.into_iter().collect();
     sorted_files.sort_by(|(a, _), (b, _)| a.cmp(b));
 
+    // Refuse before touching anything, rather than partway through the run.
+    if !dry_run {
+        for (path, _) in &sorted_files {
+            report.journal.check_path(path)?;
+        }
+    }
+
     for (path, findings) in sorted_files {
-        let modified = process_single_file(&path, findings, &mut stats, &mut finding_index, total_findings, dry_run)?;
+        let modified = process_single_file(
+            &path,
+            findings,
+            mode,
+            &mut report,
+            &mut finding_index,
+            total_findings,
+            dry_run,
+        )?;
 
         if modified && !dry_run {
-            stats.files_modified += 1;
+            report.summary.files_modified += 1;
         }
     }
 
-    Ok(stats)
+    if !quiet && !mode.is_interactive() {
+        print_changes(&report.changes, dry_run);
+    }
+
+    if !report.journal.is_empty() {
+        report.session = Some(report.journal.session().to_string());
+    }
+
+    Ok(report)
 }
 
 fn process_single_file(
     path: &Path,
     findings: Vec<Finding>,
-    stats: &mut FixStats,
+    mode: &Mode<'_>,
+    report: &mut FixReport,
     finding_index: &mut usize,
     total_findings: usize,
     dry_run: bool,
@@ -136,9 +301,11 @@ fn process_single_file(
     let Some(mut content) = read_text_file(path, None) else {
         return Ok(false);
     };
+    let original = content.clone();
 
     let mut offset: isize = 0;
     let mut file_modified = false;
+    let mut deleted_lines = HashSet::new();
 
     let mut sorted_findings = findings;
     sorted_findings.sort_by_key(|f| f.span.byte_start);
@@ -146,43 +313,147 @@ fn process_single_file(
     for finding in &sorted_findings {
         *finding_index += 1;
 
-        let action = prompts::prompt_for_action(
-            path,
-            finding,
-            &sorted_findings,
-            *finding_index,
-            total_findings,
-            &content,
-            offset,
-        )?;
+        // A previous finding removed this line, so there is nothing left to fix.
+        if deleted_lines.contains(&finding.span.line) {
+            report.summary.skipped += 1;
+            report.changes.push(change_for(path, finding, None));
+            continue;
+        }
+
+        let action = match mode {
+            Mode::Interactive => prompts::prompt_for_action(
+                path,
+                finding,
+                &sorted_findings,
+                *finding_index,
+                total_findings,
+                &content,
+                offset,
+            )?,
+            Mode::Automatic { config, default } => config
+                .fix_policy(&finding.pattern_id)
+                .or(*default)
+                .and_then(|policy| FixAction::from_policy(policy, finding)),
+        };
 
         let Some(action) = action else {
-            stats.skipped += 1;
+            report.summary.skipped += 1;
+            report.changes.push(change_for(path, finding, None));
             continue;
         };
 
+        // The lookup would fail at runtime without its import, and guessing
+        // where the import belongs is riskier than leaving the secret for now.
+        if let FixAction::ExtractToEnv(_) = action
+            && let Some(module) = actions::missing_env_import(&content, finding)
+        {
+            report.summary.skipped += 1;
+            report.changes.push(FixChange {
+                note: Some(format!("{module} is not imported; add the import and re-run")),
+                ..change_for(path, finding, None)
+            });
+            continue;
+        }
+
+        // The real value moves into `.env`, under a name that doesn't clash
+        // with what is already there.
+        let (action, dotenv_entry) = match action {
+            FixAction::ExtractToEnv(base) => {
+                let Some(value) = actions::secret_text(&content, finding, offset).map(str::to_string) else {
+                    report.summary.skipped += 1;
+                    report.changes.push(change_for(path, finding, None));
+                    continue;
+                };
+                let name = env_file::resolve_name(&report.project_root, &base, &value)
+                    .with_context(|| format!("failed to read {DOTENV_FILENAME}"))?;
+                (FixAction::ExtractToEnv(name.clone()), Some((name, value)))
+            }
+            other => (other, None),
+        };
+
         let result = apply_action(&mut content, finding, &action, offset);
 
-        if result.success {
-            offset += result.bytes_changed;
-            file_modified = true;
+        if !result.success {
+            report.summary.skipped += 1;
+            report.changes.push(change_for(path, finding, None));
+            continue;
+        }
 
-            match action {
-                FixAction::Redact => stats.redacted += 1,
-                FixAction::Placeholder(_) => stats.placeholders += 1,
-                FixAction::DeleteLine => stats.deleted += 1,
-                FixAction::Ignore => stats.ignored += 1,
+        if let Some((name, value)) = dotenv_entry
+            && !dry_run
+        {
+            env_file::store(&report.project_root, &name, &value)
+                .with_context(|| format!("failed to update {DOTENV_FILENAME}"))?;
+            // Undo restores source files only; the new `.env` entry stays.
+            for file in [DOTENV_FILENAME, GITIGNORE_FILENAME] {
+                let path = report.project_root.join(file);
+                report.journal.keep(fs::canonicalize(&path).unwrap_or(path));
             }
         }
+
+        offset += result.bytes_changed;
+        file_modified = true;
+
+        let stats = &mut report.summary;
+        match action {
+            FixAction::Redact => stats.redacted += 1,
+            FixAction::Placeholder(_) => stats.placeholders += 1,
+            FixAction::ExtractToEnv(_) => stats.extracted += 1,
+            FixAction::DeleteLine => {
+                stats.deleted += 1;
+                deleted_lines.insert(finding.span.line);
+            }
+            FixAction::Ignore => stats.ignored += 1,
+        }
+        report.changes.push(change_for(path, finding, Some(&action)));
     }
 
     if file_modified && !dry_run {
+        // Journal first, so a failed write never leaves a change without an undo record.
+        report.journal.record(path, &original, &content)?;
+        report.journal.save()?;
         atomic_write(path, &content).with_context(|| format!("failed to write {}", path.display()))?;
     }
 
     Ok(file_modified)
 }
 
+fn change_for(path: &Path, finding: &Finding, action: Option<&FixAction>) -> FixChange {
+    FixChange {
+        file: path.strip_prefix(".").unwrap_or(path).display().to_string(),
+        line: finding.span.line,
+        pattern_id: finding.pattern_id.to_string(),
+        action: action.map_or(FixPolicy::Skip.as_str(), FixAction::name),
+        env_var: match action {
+            Some(FixAction::Placeholder(env_var) | FixAction::ExtractToEnv(env_var)) => Some(env_var.clone()),
+            _ => None,
+        },
+        note: None,
+    }
+}
+
+fn print_changes(changes: &[FixChange], dry_run: bool) {
+    println!();
+    for change in changes {
+        let verb = if dry_run { "would apply" } else { "applied" };
+        let outcome = if change.action == FixPolicy::Skip.as_str() {
+            colors::muted().apply_to("skipped".to_string())
+        } else {
+            colors::success().apply_to(format!("{verb} {}", change.action))
+        };
+        println!(
+            "  {}:{} {} {}",
+            colors::secondary().apply_to(&change.file),
+            colors::secondary().apply_to(change.line),
+            colors::primary().apply_to(&change.pattern_id),
+            outcome,
+        );
+        if let Some(note) = &change.note {
+            println!("    {}", colors::muted().apply_to(note));
+        }
+    }
+}
+
 fn print_summary(stats: &FixStats, dry_run: bool) {
     println!();
     println!("{}", colors::muted().apply_to("─".repeat(60)));
@@ -199,6 +470,9 @@ fn print_summary(stats: &FixStats, dry_run: bool) {
             pluralise_word(stats.placeholders, "placeholder", "placeholders")
         ));
     }
+    if stats.extracted > 0 {
+        parts.push(format!("{} extracted to {DOTENV_FILENAME}", stats.extracted));
+    }
     if stats.deleted > 0 {
         parts.push(format!("{} deleted", stats.deleted));
     }
@@ -209,7 +483,7 @@ fn print_summary(stats: &FixStats, dry_run: bool) {
         parts.push(format!("{} skipped", stats.skipped));
     }
 
-    let actions_taken = stats.redacted + stats.placeholders + stats.deleted + stats.ignored;
+    let actions_taken = stats.redacted + stats.placeholders + stats.extracted + stats.deleted + stats.ignored;
 
     if actions_taken == 0 {
         println!("{} no changes made", colors::muted().apply_to("○"));
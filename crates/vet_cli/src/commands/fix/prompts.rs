@@ -26,8 +26,9 @@ pub fn prompt_for_action(
 
     let env_var_name = actions::derive_env_var_name(&finding.pattern_id);
     let comment_syntax = comment_syntax::for_path(path);
+    let env_lookup = actions::env_lookup_preview(content, finding, offset, &env_var_name);
 
-    let action = select_action(&env_var_name, comment_syntax.as_ref())?;
+    let action = select_action(&env_var_name, env_lookup.as_deref(), comment_syntax.as_ref())?;
 
     let Some(action) = action else {
         print_skipped();
@@ -115,20 +116,37 @@ fn print_context_line(line_number: usize, content: &str) {
 
 fn select_action(
     env_var_name: &str,
+    env_lookup: Option<&str>,
     comment_syntax: Option<&comment_syntax::CommentSyntax>,
 ) -> anyhow::Result<Option<FixAction>> {
-    let mut items = vec![
-        "Redact       → <REDACTED>".to_string(),
-        format!("Placeholder  → ${{{env_var_name}}}"),
-        "Delete line  → remove entire line".to_string(),
+    let mut choices = vec![
+        ("Redact       → <REDACTED>".to_string(), Some(FixAction::Redact)),
+        (
+            format!("Placeholder  → ${{{env_var_name}}}"),
+            Some(FixAction::Placeholder(env_var_name.to_string())),
+        ),
     ];
 
+    if let Some(lookup) = env_lookup {
+        choices.push((
+            format!("Extract env  → {lookup} (value moved to .env)"),
+            Some(FixAction::ExtractToEnv(env_var_name.to_string())),
+        ));
+    }
+
+    choices.push((
+        "Delete line  → remove entire line".to_string(),
+        Some(FixAction::DeleteLine),
+    ));
+
     if let Some(syntax) = comment_syntax {
         let formatted_ignore = syntax.format_ignore();
-        items.push(format!("Ignore       → {formatted_ignore}"));
+        choices.push((format!("Ignore       → {formatted_ignore}"), Some(FixAction::Ignore)));
     }
 
-    items.push("Skip         → no change".to_string());
+    choices.push(("Skip         → no change".to_string(), None));
+
+    let items: Vec<&str> = choices.iter().map(|(label, _)| label.as_str()).collect();
 
     let selection = Select::with_theme(&ColorfulTheme::default())
         .with_prompt("Action")
@@ -136,24 +154,7 @@ fn select_action(
         .default(0)
         .interact()?;
 
-    let action = if comment_syntax.is_some() {
-        match selection {
-            0 => Some(FixAction::Redact),
-            1 => Some(FixAction::Placeholder(env_var_name.to_string())),
-            2 => Some(FixAction::DeleteLine),
-            3 => Some(FixAction::Ignore),
-            _ => None,
-        }
-    } else {
-        match selection {
-            0 => Some(FixAction::Redact),
-            1 => Some(FixAction::Placeholder(env_var_name.to_string())),
-            2 => Some(FixAction::DeleteLine),
-            _ => None,
-        }
-    };
-
-    Ok(action)
+    Ok(choices.into_iter().nth(selection).and_then(|(_, action)| action))
 }
 
 fn print_preview(preview: &PreviewLines) {
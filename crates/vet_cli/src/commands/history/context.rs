@@ -38,9 +38,13 @@ impl HistoryContext {
         })
     }
 
-    /// Merges config and CLI exclude patterns into a compiled glob set.
-    pub fn build_excludes(&self, arg_excludes: &[String]) -> GlobSet {
-        build_excludes(&self.config.exclude_paths, arg_excludes)
+    /// Combines config and CLI exclude patterns with the `--path` pathspecs
+    /// into the filter applied to every changed file.
+    pub fn build_path_filter(&self, arg_excludes: &[String], pathspecs: &[String]) -> anyhow::Result<PathFilter> {
+        Ok(PathFilter {
+            excludes: build_excludes(&self.config.exclude_paths, arg_excludes),
+            pathspecs: build_pathspecs(pathspecs)?,
+        })
     }
 
     /// Returns the effective max file size, preferring the CLI argument over config.
@@ -48,12 +52,66 @@ impl HistoryContext {
         arg_max.or(self.config.max_file_size)
     }
 
+    /// Returns the key that ties a persisted scan state to the patterns,
+    /// configuration and options it was built with.
+    pub fn scan_key(&self, args: &HistoryArgs) -> String {
+        let excludes: Vec<String> = self.config.exclude_paths.iter().chain(&args.exclude).cloned().collect();
+        super::state::scan_key(
+            &self.patterns,
+            &self.config,
+            args.severity,
+            &excludes,
+            &args.paths,
+            args.max_file_size,
+        )
+    }
+
     /// Returns the effective minimum confidence level, preferring the CLI argument.
     pub fn minimum_confidence(&self, arg_minimum: Option<Confidence>) -> Confidence {
         arg_minimum.unwrap_or(self.config.minimum_confidence)
     }
 }
 
+/// Decides which changed paths a history scan reads.
+#[derive(Debug)]
+pub struct PathFilter {
+    excludes: GlobSet,
+    pathspecs: Option<GlobSet>,
+}
+
+impl PathFilter {
+    /// Returns `true` if `path` is not excluded and, when pathspecs were
+    /// given, matches one of them.
+    #[must_use]
+    pub fn matches(&self, path: &Path) -> bool {
+        !self.excludes.is_match(path) && self.pathspecs.as_ref().is_none_or(|specs| specs.is_match(path))
+    }
+}
+
+/// Compiles `--path` pathspecs. Like git, each matches the path itself,
+/// everything beneath it when it names a directory, or paths matching it as
+/// a glob. `.`, `./` and an empty spec name the repository root, so they
+/// match everything.
+fn build_pathspecs(pathspecs: &[String]) -> anyhow::Result<Option<GlobSet>> {
+    let specs: Vec<&str> = pathspecs
+        .iter()
+        .map(|spec| spec.trim_start_matches("./").trim_end_matches('/'))
+        .collect();
+
+    if specs.is_empty() || specs.iter().any(|spec| matches!(*spec, "" | ".")) {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+
+    for spec in specs {
+        builder.add(Glob::new(spec).with_context(|| format!("invalid pathspec '{spec}'"))?);
+        builder.add(Glob::new(&format!("{spec}/**")).with_context(|| format!("invalid pathspec '{spec}'"))?);
+    }
+
+    Ok(Some(builder.build()?))
+}
+
 fn build_excludes(config_excludes: &[String], arg_excludes: &[String]) -> GlobSet {
     let mut builder = GlobSetBuilder::new();
 
@@ -70,3 +128,31 @@ fn build_excludes(config_excludes: &[String], arg_excludes: &[String]) -> GlobSe
 
     builder.build().unwrap_or_else(|_| GlobSet::empty())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pathspecs(specs: &[&str]) -> Option<GlobSet> {
+        let specs: Vec<String> = specs.iter().map(ToString::to_string).collect();
+        build_pathspecs(&specs).unwrap()
+    }
+
+    #[test]
+    fn pathspec_matches_directory_contents_and_globs() {
+        let set = pathspecs(&["./src/", "*.env"]).unwrap();
+        assert!(set.is_match("src"));
+        assert!(set.is_match("src/main.rs"));
+        assert!(set.is_match("secret.env"));
+        assert!(!set.is_match("docs/readme.md"));
+    }
+
+    #[test]
+    fn root_pathspecs_match_everything() {
+        assert!(pathspecs(&[]).is_none());
+        assert!(pathspecs(&["."]).is_none());
+        assert!(pathspecs(&["./"]).is_none());
+        assert!(pathspecs(&[""]).is_none());
+        assert!(pathspecs(&["src", "."]).is_none());
+    }
+}
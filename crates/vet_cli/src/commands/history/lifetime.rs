@@ -0,0 +1,119 @@
+//! Secret lifetimes: when each secret was added to and removed from each
+//! path, whether it is still at `HEAD`, and which refs can reach it.
+
+use std::collections::{BTreeMap, HashMap, HashSet, btree_map, hash_map};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::{HistoryFinding, PathLifetime, SecretOccurrence};
+use crate::git::{CommitInfo, LocalRepo, ObjectId};
+
+/// Identifies a secret across history: its pattern ID and fingerprint.
+pub type SecretKey = (Arc<str>, u64);
+
+/// Accumulates occurrences and removals of each secret during a history scan.
+#[derive(Debug, Default)]
+pub struct LifetimeTracker {
+    trails: HashMap<SecretKey, BTreeMap<PathBuf, PathTrail>>,
+    removals: HashMap<(SecretKey, PathBuf), Arc<CommitInfo>>,
+}
+
+/// Everything seen of one secret at one path.
+#[derive(Debug)]
+struct PathTrail {
+    first: Arc<CommitInfo>,
+    last: Arc<CommitInfo>,
+    blobs: HashSet<ObjectId>,
+}
+
+impl LifetimeTracker {
+    /// Records that the secret `key` was added to a path in a commit.
+    pub fn record_occurrence(&mut self, key: &SecretKey, occurrence: &SecretOccurrence) {
+        let paths = self.trails.entry(key.clone()).or_default();
+        let commit = &occurrence.commit;
+
+        match paths.entry(occurrence.path.clone()) {
+            btree_map::Entry::Occupied(mut entry) => {
+                let trail = entry.get_mut();
+                if commit.date < trail.first.date {
+                    trail.first = Arc::clone(commit);
+                }
+                if commit.date > trail.last.date {
+                    trail.last = Arc::clone(commit);
+                }
+                trail.blobs.insert(occurrence.blob_id);
+            }
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(PathTrail {
+                    first: Arc::clone(commit),
+                    last: Arc::clone(commit),
+                    blobs: HashSet::from([occurrence.blob_id]),
+                });
+            }
+        }
+    }
+
+    /// Records that `commit` removed the secret `key` from `path`, keeping
+    /// only the latest removal per path.
+    pub fn record_removal(&mut self, key: SecretKey, path: PathBuf, commit: Arc<CommitInfo>) {
+        match self.removals.entry((key, path)) {
+            hash_map::Entry::Occupied(mut entry) => {
+                if commit.date > entry.get().date {
+                    entry.insert(commit);
+                }
+            }
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(commit);
+            }
+        }
+    }
+
+    /// Fills in the lifetime of each finding: per-path introduction and
+    /// removal, presence in the `HEAD` tree, and the branches and tags whose
+    /// history contains it.
+    pub fn apply(mut self, repo: &LocalRepo, findings: &mut [HistoryFinding]) {
+        let head_blobs = repo.blobs_at_head(
+            self.trails
+                .values()
+                .flat_map(|paths| paths.keys().map(PathBuf::as_path)),
+        );
+
+        let introductions: Vec<ObjectId> = self
+            .trails
+            .values()
+            .flat_map(|paths| paths.values().map(|trail| trail.first.id))
+            .collect();
+        let refs = repo.refs_containing(&introductions);
+
+        for finding in findings {
+            let key = (
+                Arc::clone(&finding.finding.pattern_id),
+                finding.finding.secret.fingerprint(),
+            );
+            let Some(paths) = self.trails.remove(&key) else {
+                continue;
+            };
+
+            let mut reachable_from = Vec::new();
+            for (path, trail) in paths {
+                let at_head = head_blobs.get(&path).is_some_and(|blob| trail.blobs.contains(blob));
+                let removed_in = self
+                    .removals
+                    .remove(&(key.clone(), path.clone()))
+                    .filter(|removal| !at_head && removal.date >= trail.last.date);
+
+                finding.present_at_head |= at_head;
+                reachable_from.extend(refs.get(&trail.first.id).into_iter().flatten().cloned());
+                finding.lifetimes.push(PathLifetime {
+                    path,
+                    introduced_in: trail.first,
+                    removed_in,
+                });
+            }
+
+            reachable_from.sort();
+            reachable_from.dedup();
+            finding.reachable_from = reachable_from;
+        }
+    }
+}
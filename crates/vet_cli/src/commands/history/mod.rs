@@ -1,27 +1,42 @@
 //! History command - scans git history for secrets.
 
 mod context;
+mod lifetime;
 mod output;
+mod rewrite;
 mod scanner;
+mod state;
 mod types;
+mod verify;
 
 pub use scanner::scan_history;
+pub use state::HistoryState;
 pub use types::*;
 
 use std::time::Instant;
 
+use vet_providers::VerificationStatus;
+
 use self::context::HistoryContext;
 use self::output::{HistoryStats, OutputContext};
+use self::rewrite::RewritePlan;
+use self::state::Invalidation;
+use super::repos::{self, RepoCommand};
 use crate::git::Repo;
 use crate::scanning::configure_thread_pool;
-use crate::ui::{exit, print_command_header};
+use crate::ui::{exit, print_command_header, print_info};
 use crate::{HistoryArgs, OutputFormat};
 
 pub use crate::git::CommitInfo;
 
 /// Executes the `vet history` command.
 pub fn run(args: &HistoryArgs) -> super::Result {
+    if let Some(root) = &args.repos {
+        return repos::run(RepoCommand::History, root, args.format, args.output.as_deref());
+    }
+
     configure_thread_pool(args.concurrency)?;
+    output::check_report_format(args)?;
 
     let show_progress = should_show_progress(args);
     let start = Instant::now();
@@ -39,31 +54,64 @@ pub fn run(args: &HistoryArgs) -> super::Result {
         since: args.since.clone(),
         until: args.until.clone(),
         branch: args.branch.clone(),
+        range: args.range.clone(),
+        author: args.author.clone(),
+        committer: args.committer.clone(),
         first_parent: args.first_parent,
+        include: args.include.clone(),
         all: args.all,
     };
 
-    let excludes = context.build_excludes(&args.exclude);
+    let path_filter = context.build_path_filter(&args.exclude, &args.paths)?;
     let max_file_size = context.max_file_size(args.max_file_size);
 
+    let mut scan_state = args.state.as_deref().map(|path| {
+        let scan_key = context.scan_key(args);
+        let (state, invalidation) = HistoryState::load(path, &scan_key);
+        if show_progress {
+            match invalidation {
+                Some(Invalidation::ScanChanged) => print_info("patterns or config changed, rescanning full history"),
+                Some(Invalidation::Unreadable) => print_info("scan state unreadable, rescanning full history"),
+                Some(Invalidation::Missing) | None => {}
+            }
+        }
+        state
+    });
+
     let result = scan_history(
         &repo,
         &context.scanner,
         &options,
-        &excludes,
+        &path_filter,
         max_file_size,
         show_progress,
+        scan_state.as_mut(),
     )?;
 
-    let elapsed = start.elapsed();
+    if let (Some(path), Some(state)) = (&args.state, &scan_state) {
+        state.save(path)?;
+    }
 
     let minimum_confidence = context.minimum_confidence(args.minimum_confidence);
-    let findings: Vec<_> = result
+    let mut findings: Vec<_> = result
         .findings
         .into_iter()
         .filter(|f| f.finding.confidence >= minimum_confidence)
         .collect();
 
+    if args.verify {
+        verify::verify_findings(&repo, &mut findings, show_progress)?;
+    }
+
+    if args.live_only {
+        findings.retain(|f| {
+            f.verification
+                .as_ref()
+                .is_some_and(|v| v.status == VerificationStatus::Live)
+        });
+    }
+
+    let elapsed = start.elapsed();
     let total_occurrences: usize = findings.iter().map(|f| f.occurrence_count).sum();
 
     let stats = HistoryStats {
@@ -78,12 +126,22 @@ pub fn run(args: &HistoryArgs) -> super::Result {
         patterns: &context.patterns,
         stats,
         all: args.all,
+        exit_code: if args.exit_zero || findings.is_empty() {
+            exit::SUCCESS
+        } else {
+            exit::FINDINGS
+        },
     };
 
     output::write_output(args, &ctx)?;
 
-    if !args.exit_zero && !findings.is_empty() {
-        std::process::exit(exit::FINDINGS);
+    if let Some(path) = &args.emit_rewrite {
+        let plan = RewritePlan::build(&repo, &findings, &context.patterns);
+        rewrite::emit(&repo, &plan, path)?;
+    }
+
+    if ctx.exit_code != exit::SUCCESS {
+        std::process::exit(ctx.exit_code);
     }
 
     Ok(())
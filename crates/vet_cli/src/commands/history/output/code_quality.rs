@@ -0,0 +1,40 @@
+//! GitLab Code Quality (Code Climate) formatting for history scan results.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::OutputContext;
+use crate::commands::history::HistoryFinding;
+use crate::report::code_quality::{self, Issue};
+use crate::report::occurrence_id;
+
+/// Serialises history scan findings as a GitLab Code Quality report.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let issues: Vec<Issue> = ctx.findings.iter().map(|f| convert_issue(f, ctx.patterns)).collect();
+    code_quality::write(&issues, writer)
+}
+
+fn convert_issue(finding: &HistoryFinding, patterns: &[Pattern]) -> Issue {
+    let pattern_id = finding.finding.pattern_id.as_ref();
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == pattern_id);
+    let pattern_name = crate::report::pattern_name(pattern_id, pattern);
+    let introduced = &finding.introduced_in;
+
+    Issue::new(
+        format!(
+            "{pattern_name} introduced in commit {} ({})",
+            introduced.commit.short_hash,
+            finding.status()
+        ),
+        pattern_id,
+        occurrence_id(
+            &Fingerprint::calculate(pattern_id, &introduced.path, finding.finding.secret.hash_hex()),
+            introduced.line,
+            finding.finding.span.column,
+        ),
+        finding.finding.severity,
+        introduced.path.display().to_string(),
+        introduced.line,
+    )
+}
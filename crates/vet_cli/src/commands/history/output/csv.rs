@@ -0,0 +1,68 @@
+//! CSV output formatting for history scan results.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::OutputContext;
+use crate::report::write_csv_record;
+
+const HEADER: &[&str] = &[
+    "severity",
+    "pattern_id",
+    "pattern_name",
+    "file",
+    "line",
+    "column",
+    "confidence",
+    "secret_masked",
+    "fingerprint",
+    "commit",
+    "author",
+    "date",
+    "occurrences",
+    "present_at_head",
+    "removed_in",
+    "reachable_from",
+    "found_via",
+];
+
+/// Serialises history scan findings as CSV, one row per unique secret.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    write_csv_record(writer, HEADER)?;
+
+    for finding in ctx.findings {
+        let pattern_id = finding.finding.pattern_id.as_ref();
+        let pattern_name = ctx
+            .patterns
+            .iter()
+            .find(|p| p.id.as_ref() == pattern_id)
+            .map_or(pattern_id, |p| p.name.as_ref());
+        let introduced = &finding.introduced_in;
+
+        write_csv_record(
+            writer,
+            &[
+                finding.finding.severity.to_string().to_lowercase(),
+                pattern_id.to_string(),
+                pattern_name.to_string(),
+                introduced.path.display().to_string(),
+                introduced.line.to_string(),
+                introduced.column.to_string(),
+                finding.finding.confidence.to_string(),
+                finding.finding.secret.as_masked().to_string(),
+                Fingerprint::calculate(pattern_id, &introduced.path, finding.finding.secret.hash_hex()).to_string(),
+                introduced.commit.hash.clone(),
+                introduced.commit.author_email.clone(),
+                introduced.commit.date.to_rfc3339(),
+                finding.occurrence_count.to_string(),
+                finding.present_at_head.to_string(),
+                finding.removed_in().map(|c| c.hash.clone()).unwrap_or_default(),
+                finding.reachable_from.join(" "),
+                introduced.found_via.as_deref().unwrap_or_default().to_string(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
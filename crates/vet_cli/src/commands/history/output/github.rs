@@ -0,0 +1,43 @@
+//! GitHub Actions workflow command output for history scan results.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::{OutputContext, markdown};
+use crate::commands::history::HistoryFinding;
+use crate::report::github::{Annotation, append_step_summary};
+
+/// Writes history findings as GitHub Actions annotations, and appends a job
+/// summary when running inside Actions.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    for finding in ctx.findings {
+        write_annotation(finding, ctx.patterns, writer)?;
+    }
+
+    append_step_summary(|summary| markdown::write(ctx, summary))
+}
+
+fn write_annotation(finding: &HistoryFinding, patterns: &[Pattern], writer: &mut dyn Write) -> anyhow::Result<()> {
+    let pattern_id = finding.finding.pattern_id.as_ref();
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == pattern_id);
+    let pattern_name = crate::report::pattern_name(pattern_id, pattern);
+    let introduced = &finding.introduced_in;
+
+    Annotation {
+        severity: finding.finding.severity,
+        file: &introduced.path.display().to_string(),
+        line: introduced.line,
+        column: introduced.column,
+        pattern,
+        title: pattern_name,
+        message: format!(
+            "{pattern_name} ({}) introduced in commit {}, {}: {}",
+            finding.finding.severity.to_string().to_lowercase(),
+            introduced.commit.short_hash,
+            finding.status(),
+            finding.finding.secret.as_masked()
+        ),
+    }
+    .write(writer)
+}
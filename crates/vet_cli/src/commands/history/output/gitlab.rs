@@ -0,0 +1,61 @@
+//! GitLab Secret Detection report formatting for history scan results.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::OutputContext;
+use crate::commands::history::{CommitInfo, HistoryFinding};
+use crate::report::gitlab::{self, Commit, Location, Vulnerability};
+use crate::report::occurrence_id;
+
+/// Serialises history scan findings as a GitLab Secret Detection report.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let vulnerabilities = ctx
+        .findings
+        .iter()
+        .map(|f| convert_vulnerability(f, ctx.patterns))
+        .collect();
+
+    gitlab::write(vulnerabilities, ctx.stats.elapsed, writer)
+}
+
+fn convert_vulnerability(finding: &HistoryFinding, patterns: &[Pattern]) -> Vulnerability {
+    let pattern_id = finding.finding.pattern_id.as_ref();
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == pattern_id);
+    let name = crate::report::pattern_name(pattern_id, pattern);
+    let introduced = &finding.introduced_in;
+
+    Vulnerability::new(
+        occurrence_id(
+            &Fingerprint::calculate(pattern_id, &introduced.path, finding.finding.secret.hash_hex()),
+            introduced.line,
+            finding.finding.span.column,
+        ),
+        pattern_id,
+        pattern,
+        finding.finding.severity,
+        format!(
+            "{} ({})",
+            pattern.map_or_else(
+                || format!("{name} detected in git history"),
+                |p| p.description.to_string()
+            ),
+            finding.status()
+        ),
+        Location {
+            file: introduced.path.display().to_string(),
+            line: introduced.line,
+            commit: convert_commit(&introduced.commit),
+        },
+    )
+}
+
+fn convert_commit(commit: &CommitInfo) -> Commit {
+    Commit::new(
+        commit.hash.clone(),
+        format!("{} <{}>", commit.author_name, commit.author_email),
+        commit.date,
+        commit.message.clone(),
+    )
+}
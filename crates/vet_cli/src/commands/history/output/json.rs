@@ -3,9 +3,10 @@
 use std::io::Write;
 
 use serde::Serialize;
+use vet_providers::VerificationResult;
 
 use super::OutputContext;
-use crate::commands::history::{CommitInfo, HistoryFinding, SecretOccurrence};
+use crate::commands::history::{CommitInfo, HistoryFinding, PathLifetime, SecretOccurrence};
 
 #[derive(Serialize)]
 struct JsonReport {
@@ -47,11 +48,41 @@ struct JsonFinding {
     introduced_in: JsonOccurrence,
     occurrences: Vec<JsonOccurrence>,
     occurrence_count: usize,
+    present_at_head: bool,
+    reachable_from: Vec<String>,
+    lifetimes: Vec<JsonLifetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification: Option<JsonVerification>,
+}
+
+#[derive(Serialize)]
+struct JsonMetadataEntry {
+    label: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+struct JsonVerification {
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    metadata: Vec<JsonMetadataEntry>,
+    verified_at: String,
+}
+
+#[derive(Serialize)]
+struct JsonLifetime {
+    path: String,
+    introduced_in: JsonCommit,
+    removed_in: Option<JsonCommit>,
 }
 
 #[derive(Serialize)]
 struct JsonOccurrence {
     commit: JsonCommit,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    found_via: Option<String>,
     location: JsonLocation,
     line_content: String,
 }
@@ -131,12 +162,49 @@ fn convert_finding(f: &HistoryFinding) -> JsonFinding {
         introduced_in: convert_occurrence(&f.introduced_in),
         occurrences: f.occurrences.iter().map(convert_occurrence).collect(),
         occurrence_count: f.occurrence_count,
+        present_at_head: f.present_at_head,
+        reachable_from: f.reachable_from.clone(),
+        lifetimes: f.lifetimes.iter().map(convert_lifetime).collect(),
+        verification: f.verification.as_ref().map(convert_verification),
+    }
+}
+
+fn convert_verification(v: &VerificationResult) -> JsonVerification {
+    JsonVerification {
+        status: v.status.to_string(),
+        provider: v
+            .service
+            .as_ref()
+            .and_then(|s| s.provider.as_ref().map(ToString::to_string)),
+        metadata: v
+            .service
+            .as_ref()
+            .map(|s| {
+                s.metadata
+                    .iter()
+                    .map(|m| JsonMetadataEntry {
+                        label: m.label.to_string(),
+                        value: m.value.to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        verified_at: v.verified_at.to_string(),
+    }
+}
+
+fn convert_lifetime(lifetime: &PathLifetime) -> JsonLifetime {
+    JsonLifetime {
+        path: lifetime.path.display().to_string(),
+        introduced_in: convert_commit(&lifetime.introduced_in),
+        removed_in: lifetime.removed_in.as_deref().map(convert_commit),
     }
 }
 
 fn convert_occurrence(occ: &SecretOccurrence) -> JsonOccurrence {
     JsonOccurrence {
         commit: convert_commit(&occ.commit),
+        found_via: occ.found_via.as_deref().map(str::to_string),
         location: JsonLocation {
             path: occ.path.display().to_string(),
             line: occ.line,
@@ -0,0 +1,90 @@
+//! `JUnit` XML output formatting for history scan results.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::OutputContext;
+use crate::commands::history::HistoryFinding;
+use crate::report::escape_xml;
+
+const SUITE_NAME: &str = "vet history";
+
+/// Serialises history scan findings as a `JUnit` XML report.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let failures = ctx.findings.len();
+    // A clean scan still reports a passing case so dashboards record the run.
+    let tests = failures.max(1);
+    let time = ctx.stats.elapsed.as_secs_f64();
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="vet" tests="{tests}" failures="{failures}" time="{time:.3}">"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <testsuite name="{SUITE_NAME}" tests="{tests}" failures="{failures}" errors="0" skipped="0" time="{time:.3}">"#
+    )?;
+
+    if ctx.findings.is_empty() {
+        writeln!(writer, r#"    <testcase name="no secrets found" classname="vet"/>"#)?;
+    }
+
+    for finding in ctx.findings {
+        write_testcase(finding, ctx.patterns, writer)?;
+    }
+
+    writeln!(writer, "  </testsuite>")?;
+    writeln!(writer, "</testsuites>")?;
+    Ok(())
+}
+
+fn write_testcase(finding: &HistoryFinding, patterns: &[Pattern], writer: &mut dyn Write) -> anyhow::Result<()> {
+    let pattern_id = finding.finding.pattern_id.as_ref();
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == pattern_id);
+    let pattern_name = pattern.map_or(pattern_id, |p| p.name.as_ref());
+    let introduced = &finding.introduced_in;
+    let path = introduced.path.display().to_string();
+    let location = format!("{path}:{}:{}", introduced.line, introduced.column);
+
+    writeln!(
+        writer,
+        r#"    <testcase name="{}" classname="{}" file="{}" line="{}">"#,
+        escape_xml(&format!(
+            "{pattern_id} at {location} ({})",
+            introduced.commit.short_hash
+        )),
+        escape_xml(&path),
+        escape_xml(&path),
+        introduced.line,
+    )?;
+
+    let mut body = format!(
+        "{location}\nSecret: {}\nIntroduced in: {} by {} on {}\nOccurrences: {}\nStatus: {}",
+        finding.finding.secret.as_masked(),
+        introduced.commit.hash,
+        introduced.commit.author_email,
+        introduced.commit.date.format("%Y-%m-%d"),
+        finding.occurrence_count,
+        finding.status(),
+    );
+    if !finding.reachable_from.is_empty() {
+        body.push_str("\nReachable from: ");
+        body.push_str(&finding.reachable_from.join(", "));
+    }
+    if let Some(pattern) = pattern {
+        body.push_str("\n\n");
+        body.push_str(pattern.remediation());
+    }
+
+    writeln!(
+        writer,
+        r#"      <failure message="{}" type="{}">{}</failure>"#,
+        escape_xml(&format!("{pattern_name} detected in git history")),
+        finding.finding.severity.to_string().to_lowercase(),
+        escape_xml(&body),
+    )?;
+    writeln!(writer, "    </testcase>")?;
+    Ok(())
+}
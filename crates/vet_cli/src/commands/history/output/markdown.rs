@@ -0,0 +1,76 @@
+//! Markdown output formatting for history scan results.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::OutputContext;
+use crate::commands::history::HistoryFinding;
+use crate::report::markdown::{self, Layout, write_remediation};
+use crate::report::{escape_markdown, markdown_code};
+use crate::ui::pluralise_word;
+
+/// Renders history findings as a Markdown report grouped by the file each
+/// secret was introduced in.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let layout = Layout {
+        title: "vet history",
+        scanned: format!(
+            "{} {} scanned",
+            ctx.stats.commits_scanned,
+            pluralise_word(ctx.stats.commits_scanned, "commit", "commits")
+        ),
+        path: |f: &HistoryFinding| f.introduced_in.path.display().to_string(),
+        severity: |f: &HistoryFinding| f.finding.severity,
+    };
+
+    markdown::write(&layout, ctx.findings, writer, |finding, writer| {
+        write_finding(finding, ctx.patterns, writer)
+    })
+}
+
+fn write_finding(finding: &HistoryFinding, patterns: &[Pattern], writer: &mut dyn Write) -> anyhow::Result<()> {
+    let pattern_id = finding.finding.pattern_id.as_ref();
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == pattern_id);
+    let pattern_name = pattern.map_or(pattern_id, |p| p.name.as_ref());
+    let introduced = &finding.introduced_in;
+
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "**{}** · {} · line {} · introduced in {} by {} on {}",
+        escape_markdown(pattern_name),
+        finding.finding.severity.to_string().to_lowercase(),
+        introduced.line,
+        markdown_code(&introduced.commit.short_hash),
+        escape_markdown(&introduced.commit.author_name),
+        introduced.commit.date.format("%Y-%m-%d"),
+    )?;
+    if let Some(source) = &introduced.found_via {
+        writeln!(writer)?;
+        writeln!(writer, "Found via {}.", markdown_code(source))?;
+    }
+    writeln!(writer)?;
+    writeln!(writer, "{}", markdown_code(introduced.line_content.trim()))?;
+
+    writeln!(writer)?;
+    let status = if finding.present_at_head {
+        "**Still present at `HEAD`.**".to_string()
+    } else if let Some(commit) = finding.removed_in() {
+        format!("Removed in {}.", markdown_code(&commit.short_hash))
+    } else {
+        "Not present at `HEAD`.".to_string()
+    };
+    if finding.occurrence_count > 1 {
+        write!(writer, "Found in {} commits. ", finding.occurrence_count)?;
+    }
+    writeln!(writer, "{status}")?;
+
+    if !finding.reachable_from.is_empty() {
+        let refs: Vec<String> = finding.reachable_from.iter().map(|r| markdown_code(r)).collect();
+        writeln!(writer)?;
+        writeln!(writer, "Reachable from {}.", refs.join(", "))?;
+    }
+
+    write_remediation(pattern, writer)
+}
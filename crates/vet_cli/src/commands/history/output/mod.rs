@@ -1,7 +1,14 @@
 //! Output formatting for history scan results.
 
+mod code_quality;
+mod csv;
+mod github;
+mod gitlab;
 mod json;
+mod junit;
+mod markdown;
 mod sarif;
+mod summary;
 mod text;
 
 use std::fs::File;
@@ -12,7 +19,7 @@ use anyhow::Context as _;
 use vet_core::prelude::*;
 
 use super::HistoryFinding;
-use crate::{HistoryArgs, OutputFormat};
+use crate::{HistoryArgs, HistoryReport, OutputFormat};
 
 /// Aggregate statistics for a completed history scan.
 #[derive(Debug)]
@@ -38,6 +45,8 @@ pub struct OutputContext<'a> {
     pub stats: HistoryStats,
     /// Whether `--all` was passed (affects ref display).
     pub all: bool,
+    /// The exit code the scan will terminate with.
+    pub exit_code: i32,
 }
 
 /// Writes history output to a file or stdout in the requested format.
@@ -45,11 +54,27 @@ pub fn write_output(args: &HistoryArgs, ctx: &OutputContext) -> anyhow::Result<(
     if let Some(path) = &args.output {
         let file = File::create(path).with_context(|| format!("failed to create output file: {}", path.display()))?;
         let mut writer = BufWriter::new(file);
-        write_format(args.format, ctx, &mut writer)
+        write_report(args, ctx, &mut writer)
     } else {
         let stdout = std::io::stdout();
         let mut writer = stdout.lock();
-        write_format(args.format, ctx, &mut writer)
+        write_report(args, ctx, &mut writer)
+    }
+}
+
+/// Fails early if `--report` does not support the requested format.
+pub fn check_report_format(args: &HistoryArgs) -> anyhow::Result<()> {
+    if args.report == HistoryReport::Summary && !matches!(args.format, OutputFormat::Text | OutputFormat::Json) {
+        anyhow::bail!("--report summary only supports text and json output");
+    }
+    Ok(())
+}
+
+fn write_report(args: &HistoryArgs, ctx: &OutputContext, writer: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    match (args.report, args.format) {
+        (HistoryReport::Summary, OutputFormat::Json) => summary::write_json(ctx, writer),
+        (HistoryReport::Summary, _) => summary::write_text(ctx, writer),
+        (HistoryReport::Findings, format) => write_format(format, ctx, writer),
     }
 }
 
@@ -58,5 +83,11 @@ fn write_format(format: OutputFormat, ctx: &OutputContext, writer: &mut dyn std:
         OutputFormat::Text => text::write(ctx, writer),
         OutputFormat::Json => json::write(ctx, writer),
         OutputFormat::Sarif => sarif::write(ctx, writer),
+        OutputFormat::Gitlab => gitlab::write(ctx, writer),
+        OutputFormat::CodeQuality => code_quality::write(ctx, writer),
+        OutputFormat::Junit => junit::write(ctx, writer),
+        OutputFormat::Csv => csv::write(ctx, writer),
+        OutputFormat::Markdown => markdown::write(ctx, writer),
+        OutputFormat::Github => github::write(ctx, writer),
     }
 }
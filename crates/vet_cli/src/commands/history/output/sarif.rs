@@ -2,17 +2,18 @@
 
 use std::io::Write;
 
+use chrono::{TimeDelta, Utc};
 use serde::Serialize;
 use vet_core::prelude::*;
 
 use super::OutputContext;
 use crate::commands::history::HistoryFinding;
+use crate::git::CommitInfo;
+use crate::report::sarif::{self, HELP_URI, TOOL_NAME, TOOL_URI, TOOL_VERSION};
 
 const SARIF_VERSION: &str = "2.1.0";
 const SARIF_SCHEMA: &str =
     "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json";
-const TOOL_NAME: &str = "vet";
-const TOOL_URI: &str = "https://github.com/spikermint/vet";
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -27,6 +28,7 @@ struct SarifReport {
 #[serde(rename_all = "camelCase")]
 struct SarifRun {
     tool: SarifTool,
+    invocations: Vec<SarifInvocation>,
     results: Vec<SarifResult>,
 }
 
@@ -40,6 +42,7 @@ struct SarifTool {
 #[serde(rename_all = "camelCase")]
 struct SarifDriver {
     name: &'static str,
+    version: &'static str,
     information_uri: &'static str,
     rules: Vec<SarifRule>,
 }
@@ -51,6 +54,8 @@ struct SarifRule {
     name: String,
     short_description: SarifMessage,
     full_description: SarifMessage,
+    help_uri: &'static str,
+    help: SarifMessage,
     default_configuration: SarifRuleConfig,
     properties: SarifRuleProperties,
 }
@@ -65,6 +70,18 @@ struct SarifRuleConfig {
 #[serde(rename_all = "camelCase")]
 struct SarifRuleProperties {
     severity: String,
+    tags: [&'static str; 2],
+    #[serde(rename = "security-severity")]
+    security_severity: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifInvocation {
+    execution_successful: bool,
+    exit_code: i32,
+    start_time_utc: String,
+    end_time_utc: String,
 }
 
 #[derive(Serialize)]
@@ -75,6 +92,7 @@ struct SarifResult {
     message: SarifMessage,
     locations: Vec<SarifLocation>,
     fingerprints: SarifFingerprints,
+    partial_fingerprints: SarifPartialFingerprints,
     properties: SarifResultProperties,
 }
 
@@ -117,12 +135,30 @@ struct SarifFingerprints {
     secret_v1: String,
 }
 
+#[derive(Serialize)]
+struct SarifPartialFingerprints {
+    #[serde(rename = "vetBaselineFingerprint/v1")]
+    baseline: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SarifResultProperties {
     scan_type: &'static str,
     commit: SarifCommitInfo,
     occurrence_count: usize,
+    present_at_head: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    removed_in: Option<SarifCommitInfo>,
+    reachable_from: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    found_via: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification_provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verified_at: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -136,6 +172,11 @@ struct SarifCommitInfo {
 }
 
 /// Serialises history scan findings as a SARIF v2.1.0 report.
+///
+/// Unlike `vet scan`, results carry no `fixes` or `baselineState`. Each one
+/// points at the commit that introduced a secret, where an in-place edit
+/// cannot be applied (`--emit-rewrite` covers purging it), and history scans
+/// do not consult the baseline.
 pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
     let report = build_report(ctx);
     serde_json::to_writer_pretty(&mut *writer, &report)?;
@@ -144,6 +185,9 @@ pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()>
 }
 
 fn build_report(ctx: &OutputContext) -> SarifReport {
+    let end = Utc::now();
+    let start = end - TimeDelta::from_std(ctx.stats.elapsed).unwrap_or_default();
+
     SarifReport {
         schema: SARIF_SCHEMA,
         version: SARIF_VERSION,
@@ -151,10 +195,17 @@ fn build_report(ctx: &OutputContext) -> SarifReport {
             tool: SarifTool {
                 driver: SarifDriver {
                     name: TOOL_NAME,
+                    version: TOOL_VERSION,
                     information_uri: TOOL_URI,
                     rules: build_rules(ctx.findings, ctx.patterns),
                 },
             },
+            invocations: vec![SarifInvocation {
+                execution_successful: true,
+                exit_code: ctx.exit_code,
+                start_time_utc: start.to_rfc3339(),
+                end_time_utc: end.to_rfc3339(),
+            }],
             results: ctx.findings.iter().map(|f| convert_result(f, ctx.patterns)).collect(),
         }],
     }
@@ -178,11 +229,17 @@ fn convert_rule(pattern: &Pattern) -> SarifRule {
         full_description: SarifMessage {
             text: pattern.description.to_string(),
         },
+        help_uri: HELP_URI,
+        help: SarifMessage {
+            text: pattern.remediation().to_string(),
+        },
         default_configuration: SarifRuleConfig {
-            level: severity_to_level(pattern.severity),
+            level: sarif::level(pattern.severity),
         },
         properties: SarifRuleProperties {
             severity: pattern.severity.to_string().to_lowercase(),
+            tags: ["security", "secret"],
+            security_severity: sarif::security_severity(pattern.severity),
         },
     }
 }
@@ -192,10 +249,11 @@ fn convert_result(finding: &HistoryFinding, patterns: &[Pattern]) -> SarifResult
         .iter()
         .find(|p| p.id.as_ref() == finding.finding.pattern_id.as_ref())
         .map_or(finding.finding.pattern_id.as_ref(), |p| p.name.as_ref());
+    let verification = finding.verification.as_ref();
 
     SarifResult {
         rule_id: finding.finding.pattern_id.to_string(),
-        level: severity_to_level(finding.finding.severity),
+        level: sarif::level(finding.finding.severity),
         message: SarifMessage {
             text: format!("{pattern_name} detected in git history"),
         },
@@ -213,24 +271,37 @@ fn convert_result(finding: &HistoryFinding, patterns: &[Pattern]) -> SarifResult
         fingerprints: SarifFingerprints {
             secret_v1: finding.finding.id.to_string(),
         },
+        partial_fingerprints: SarifPartialFingerprints {
+            baseline: Fingerprint::calculate(
+                &finding.finding.pattern_id,
+                &finding.introduced_in.path,
+                finding.finding.secret.hash_hex(),
+            )
+            .to_string(),
+        },
         properties: SarifResultProperties {
             scan_type: "history",
-            commit: SarifCommitInfo {
-                hash: finding.introduced_in.commit.hash.clone(),
-                short_hash: finding.introduced_in.commit.short_hash.clone(),
-                date: finding.introduced_in.commit.date.to_rfc3339(),
-                author: finding.introduced_in.commit.author_email.clone(),
-                message: finding.introduced_in.commit.message.clone(),
-            },
+            commit: convert_commit(&finding.introduced_in.commit),
             occurrence_count: finding.occurrence_count,
+            present_at_head: finding.present_at_head,
+            removed_in: finding.removed_in().map(convert_commit),
+            reachable_from: finding.reachable_from.clone(),
+            found_via: finding.introduced_in.found_via.as_deref().map(str::to_string),
+            verification_status: verification.map(|v| v.status.to_string()),
+            verification_provider: verification
+                .and_then(|v| v.service.as_ref())
+                .and_then(|s| s.provider.as_ref().map(ToString::to_string)),
+            verified_at: verification.map(|v| v.verified_at.to_string()),
         },
     }
 }
 
-fn severity_to_level(severity: Severity) -> &'static str {
-    match severity {
-        Severity::Critical | Severity::High => "error",
-        Severity::Medium => "warning",
-        Severity::Low => "note",
+fn convert_commit(commit: &CommitInfo) -> SarifCommitInfo {
+    SarifCommitInfo {
+        hash: commit.hash.clone(),
+        short_hash: commit.short_hash.clone(),
+        date: commit.date.to_rfc3339(),
+        author: commit.author_email.clone(),
+        message: commit.message.clone(),
     }
 }
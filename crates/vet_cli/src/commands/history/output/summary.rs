@@ -0,0 +1,243 @@
+//! Exposure summary for `vet history --report summary`.
+//!
+//! Aggregates findings by the author who introduced them, the top-level
+//! directory they were introduced in, their pattern group and the year they
+//! were introduced (in UTC). Only masked secrets are ever included.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::{Component, Path};
+
+use console::style;
+use serde::Serialize;
+use vet_core::prelude::*;
+
+use super::OutputContext;
+use crate::commands::history::HistoryFinding;
+use crate::commands::history::scanner::synthetic_prefix;
+use crate::ui::{colors, format_duration, pluralise_word, truncate_with_ellipsis};
+
+const VERSION: &str = "1.0";
+const SCAN_TYPE: &str = "history";
+const KEY_WIDTH: usize = 48;
+
+#[derive(Serialize)]
+struct JsonSummaryReport<'a> {
+    version: &'static str,
+    scan_type: &'static str,
+    report: &'static str,
+    metadata: JsonMetadata,
+    #[serde(flatten)]
+    summary: &'a ExposureSummary,
+}
+
+#[derive(Serialize)]
+struct JsonMetadata {
+    commits_scanned: usize,
+    secrets_found: usize,
+    total_occurrences: usize,
+    duration_ms: u64,
+}
+
+/// Findings aggregated along each reporting dimension.
+#[derive(Debug, Serialize)]
+struct ExposureSummary {
+    authors: Vec<ExposureRow>,
+    directories: Vec<ExposureRow>,
+    pattern_groups: Vec<ExposureRow>,
+    years: Vec<ExposureRow>,
+}
+
+/// One entry of a summary table.
+#[derive(Debug, Default, Serialize)]
+struct ExposureRow {
+    key: String,
+    secrets: usize,
+    occurrences: usize,
+    present_at_head: usize,
+    secrets_masked: BTreeSet<String>,
+}
+
+impl ExposureSummary {
+    fn build(findings: &[HistoryFinding], patterns: &[Pattern]) -> Self {
+        let mut authors = BTreeMap::new();
+        let mut directories = BTreeMap::new();
+        let mut pattern_groups = BTreeMap::new();
+        let mut years = BTreeMap::new();
+
+        for finding in findings {
+            let commit = &finding.introduced_in.commit;
+            let group = patterns
+                .iter()
+                .find(|p| p.id == finding.finding.pattern_id)
+                .map_or("unknown", |p| p.group.as_str());
+
+            tally(
+                &mut authors,
+                format!("{} <{}>", commit.author_name, commit.author_email),
+                finding,
+            );
+            tally(
+                &mut directories,
+                top_level_directory(&finding.introduced_in.path),
+                finding,
+            );
+            tally(&mut pattern_groups, group.to_string(), finding);
+            tally(&mut years, commit.date.format("%Y").to_string(), finding);
+        }
+
+        Self {
+            authors: ranked(authors),
+            directories: ranked(directories),
+            pattern_groups: ranked(pattern_groups),
+            years: years.into_values().collect(),
+        }
+    }
+}
+
+fn tally(rows: &mut BTreeMap<String, ExposureRow>, key: String, finding: &HistoryFinding) {
+    let row = rows.entry(key).or_insert_with_key(|key| ExposureRow {
+        key: key.clone(),
+        ..ExposureRow::default()
+    });
+    row.secrets += 1;
+    row.occurrences += finding.occurrence_count;
+    row.present_at_head += usize::from(finding.present_at_head);
+    row.secrets_masked
+        .insert(finding.finding.secret.as_masked().to_string());
+}
+
+/// Orders rows by secret count, most exposed first.
+fn ranked(rows: BTreeMap<String, ExposureRow>) -> Vec<ExposureRow> {
+    let mut rows: Vec<_> = rows.into_values().collect();
+    rows.sort_by(|a, b| b.secrets.cmp(&a.secrets).then_with(|| a.key.cmp(&b.key)));
+    rows
+}
+
+/// Returns the first directory of `path`, `.` for files at the repository
+/// root, or the kind of message for commit messages, notes and tags.
+fn top_level_directory(path: &Path) -> String {
+    if let Some(prefix) = synthetic_prefix(path) {
+        return format!("({prefix})");
+    }
+
+    let mut components = path.components().filter(|c| matches!(c, Component::Normal(_)));
+    match (components.next(), components.next()) {
+        (Some(first), Some(_)) => format!("{}/", first.as_os_str().to_string_lossy()),
+        _ => ".".to_string(),
+    }
+}
+
+/// Writes the exposure summary as JSON.
+pub fn write_json(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let summary = ExposureSummary::build(ctx.findings, ctx.patterns);
+    let report = JsonSummaryReport {
+        version: VERSION,
+        scan_type: SCAN_TYPE,
+        report: "summary",
+        metadata: JsonMetadata {
+            commits_scanned: ctx.stats.commits_scanned,
+            secrets_found: ctx.stats.secrets_found,
+            total_occurrences: ctx.stats.total_occurrences,
+            duration_ms: u64::try_from(ctx.stats.elapsed.as_millis()).unwrap_or(u64::MAX),
+        },
+        summary: &summary,
+    };
+
+    serde_json::to_writer_pretty(&mut *writer, &report)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Writes the exposure summary as text tables.
+pub fn write_text(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let summary = ExposureSummary::build(ctx.findings, ctx.patterns);
+
+    writeln!(writer)?;
+    write_table("By author", &summary.authors, writer)?;
+    write_table("By directory", &summary.directories, writer)?;
+    write_table("By pattern group", &summary.pattern_groups, writer)?;
+    write_table("By year introduced", &summary.years, writer)?;
+
+    let secrets = ctx.stats.secrets_found;
+    let occurrences = ctx.stats.total_occurrences;
+    let commits = ctx.stats.commits_scanned;
+    writeln!(
+        writer,
+        "{} {} {} {} {}",
+        colors::primary().apply_to(format!("{secrets} {}", pluralise_word(secrets, "secret", "secrets"))),
+        colors::muted().apply_to("·"),
+        colors::muted().apply_to(format!(
+            "{occurrences} {}",
+            pluralise_word(occurrences, "occurrence", "occurrences")
+        )),
+        colors::muted().apply_to(format!("· {commits} {}", pluralise_word(commits, "commit", "commits"))),
+        colors::muted().apply_to(format!("({})", format_duration(ctx.stats.elapsed))),
+    )?;
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+fn write_table(title: &str, rows: &[ExposureRow], writer: &mut dyn Write) -> anyhow::Result<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let keys: Vec<String> = rows.iter().map(|r| truncate_with_ellipsis(&r.key, KEY_WIDTH)).collect();
+    let width = keys
+        .iter()
+        .map(|k| k.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(title.len());
+
+    writeln!(
+        writer,
+        "{}  {}",
+        style(format!("{title:<width$}")).bold(),
+        colors::muted().apply_to(format!("{:>7}  {:>11}  {:>7}", "secrets", "occurrences", "at HEAD")),
+    )?;
+
+    for (row, key) in rows.iter().zip(&keys) {
+        writeln!(
+            writer,
+            "{}  {:>7}  {:>11}  {:>7}",
+            colors::secondary().apply_to(format!("{key:<width$}")),
+            row.secrets,
+            row.occurrences,
+            row.present_at_head,
+        )?;
+    }
+
+    writeln!(writer)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_level_directory_uses_first_directory() {
+        assert_eq!(top_level_directory(Path::new("src/config/keys.rs")), "src/");
+        assert_eq!(top_level_directory(Path::new("./deploy/.env")), "deploy/");
+    }
+
+    #[test]
+    fn top_level_directory_groups_root_files() {
+        assert_eq!(top_level_directory(Path::new(".env")), ".");
+        assert_eq!(top_level_directory(Path::new("./secret.txt")), ".");
+    }
+
+    #[test]
+    fn top_level_directory_names_message_kinds() {
+        assert_eq!(
+            top_level_directory(Path::new("commit-message:abc1234")),
+            "(commit-message)"
+        );
+        assert_eq!(top_level_directory(Path::new("tag-message:v1.0")), "(tag-message)");
+        assert_eq!(top_level_directory(Path::new("note:abc1234")), "(note)");
+        assert_eq!(top_level_directory(Path::new("notes/todo.md")), "notes/");
+    }
+}
@@ -5,9 +5,10 @@ use std::io::Write;
 use chrono::Local;
 use console::style;
 use vet_core::prelude::*;
+use vet_providers::VerificationStatus;
 
 use super::OutputContext;
-use crate::commands::history::HistoryFinding;
+use crate::commands::history::{HistoryFinding, SecretOccurrence};
 use crate::ui::{self, colors, format_duration, indicators, severity_indicator, truncate_with_ellipsis};
 
 /// Renders history scan findings as styled, human-readable text.
@@ -51,12 +52,13 @@ fn write_finding(finding: &HistoryFinding, patterns: &[Pattern], writer: &mut dy
         ui::severity_style(finding.finding.severity).apply_to(&severity),
     )?;
 
-    writeln!(
+    write!(
         writer,
         "  {} {}",
         colors::emphasis().apply_to(&commit.short_hash),
         colors::secondary().apply_to(truncate_with_ellipsis(&commit.message, 50)),
     )?;
+    write_found_via(&finding.introduced_in, writer)?;
 
     writeln!(
         writer,
@@ -75,6 +77,8 @@ fn write_finding(finding: &HistoryFinding, patterns: &[Pattern], writer: &mut dy
     )?;
 
     writeln!(writer, "     {}", colors::code().apply_to(&finding.finding.masked_line))?;
+    write_lifetime(finding, writer)?;
+    write_verification(finding, writer)?;
 
     writeln!(writer)?;
     Ok(())
@@ -117,7 +121,7 @@ fn write_finding_grouped(finding: &HistoryFinding, patterns: &[Pattern], writer:
             String::new()
         };
 
-        writeln!(
+        write!(
             writer,
             "  {} {} {}:{} {} {}{}",
             colors::muted().apply_to(prefix),
@@ -128,12 +132,105 @@ fn write_finding_grouped(finding: &HistoryFinding, patterns: &[Pattern], writer:
             colors::secondary().apply_to(occ.commit.date.with_timezone(&Local).format("%Y-%m-%d").to_string()),
             marker,
         )?;
+        write_found_via(occ, writer)?;
     }
 
+    write_lifetime(finding, writer)?;
+    write_verification(finding, writer)?;
+
     writeln!(writer)?;
     Ok(())
 }
 
+/// Ends an occurrence's line, naming the stash entry, tag, remote branch or
+/// reflog entry it was found through when it is not on a branch.
+fn write_found_via(occurrence: &SecretOccurrence, writer: &mut dyn Write) -> anyhow::Result<()> {
+    match &occurrence.found_via {
+        Some(source) => writeln!(
+            writer,
+            " {} {}",
+            colors::muted().apply_to("·"),
+            colors::warning().apply_to(format!("via {source}"))
+        )?,
+        None => writeln!(writer)?,
+    }
+    Ok(())
+}
+
+/// Writes whether the secret is still at `HEAD` (or which commit removed it)
+/// and the branches and tags that can reach it.
+fn write_lifetime(finding: &HistoryFinding, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let status = finding.status();
+    let status = if finding.present_at_head {
+        colors::warning().apply_to(status)
+    } else {
+        colors::secondary().apply_to(status)
+    };
+
+    if finding.reachable_from.is_empty() {
+        writeln!(writer, "  {status}")?;
+    } else {
+        writeln!(
+            writer,
+            "  {status} {} {}",
+            colors::muted().apply_to("·"),
+            colors::muted().apply_to(format!("reachable from {}", finding.reachable_from.join(", "))),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Writes the live verification result, if the secret was verified.
+fn write_verification(finding: &HistoryFinding, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let Some(v) = &finding.verification else {
+        return Ok(());
+    };
+
+    let summary = v
+        .service
+        .as_ref()
+        .map(|s| {
+            s.metadata
+                .iter()
+                .map(|m| format!("{}: {}", m.label, m.value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default();
+
+    let (indicator, label, fallback) = match v.status {
+        VerificationStatus::Live => (
+            colors::error().apply_to(indicators::ERROR),
+            colors::error().bold().apply_to("LIVE"),
+            "secret is active",
+        ),
+        VerificationStatus::Inactive => (
+            colors::success().apply_to(indicators::SUCCESS),
+            colors::success().apply_to("inactive"),
+            "key is revoked or expired",
+        ),
+        VerificationStatus::Inconclusive => (
+            colors::warning().apply_to(indicators::WARNING),
+            colors::warning().apply_to("inconclusive"),
+            "could not determine, try again later",
+        ),
+    };
+    let message = if summary.is_empty() || v.status == VerificationStatus::Inactive {
+        fallback.to_string()
+    } else {
+        summary
+    };
+
+    writeln!(
+        writer,
+        "  {indicator} {label} {} {}",
+        colors::muted().apply_to("-"),
+        colors::secondary().apply_to(message)
+    )?;
+    Ok(())
+}
+
 fn write_summary(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
     let commits = format!("{} commits", ctx.stats.commits_scanned);
     let timing = format!("({})", format_duration(ctx.stats.elapsed));
@@ -0,0 +1,429 @@
+//! History rewrite plans for secrets found in git history.
+//!
+//! Removing a committed secret means rewriting every commit that contains it.
+//! `vet history --emit-rewrite <file>` writes a `git filter-repo --replace-text`
+//! expressions file and a BFG `--replace-text` list that replace each secret
+//! with its masked form, and prints the branches and tags that need to be
+//! rewritten and force-pushed. Secrets in commit and tag messages go into a
+//! separate `--replace-message` file, since BFG cannot rewrite messages.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use console::style;
+use vet_core::fs_util::atomic_write_private;
+use vet_core::prelude::*;
+
+use super::HistoryFinding;
+use super::scanner::{COMMIT_MESSAGE_PREFIX, TAG_MESSAGE_PREFIX, synthetic_prefix};
+use crate::git::Repo;
+use crate::ui::{colors, indicators, pluralise_word, print_warning};
+
+/// Separator between the text to find and its replacement in both formats.
+const REPLACE_SEPARATOR: &str = "==>";
+
+/// Literal replacements for every secret that can be rewritten.
+#[derive(Debug, Default)]
+pub struct RewritePlan {
+    entries: Vec<RewriteEntry>,
+    unreadable: usize,
+}
+
+#[derive(Debug)]
+struct RewriteEntry {
+    secret: String,
+    replacement: String,
+    pattern_name: String,
+    introduced_in: String,
+    refs: Vec<String>,
+    /// Found in file contents or notes, which `--replace-text` rewrites.
+    in_files: bool,
+    /// Found in commit or tag messages, which only `--replace-message` rewrites.
+    in_messages: bool,
+}
+
+/// The files a plan is written to.
+struct PlanFiles<'a> {
+    expressions: &'a Path,
+    messages: Option<PathBuf>,
+    bfg: PathBuf,
+}
+
+impl RewritePlan {
+    /// Builds a plan by reading each secret back from the blob it was
+    /// introduced in and finding the branches and tags that contain it.
+    #[must_use]
+    pub fn build(repo: &Repo, findings: &[HistoryFinding], patterns: &[Pattern]) -> Self {
+        let local = repo.thread_local();
+
+        let mut plan = Self::default();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+
+        for finding in findings {
+            let introduced = &finding.introduced_in;
+            let Some(secret) = finding.raw_secret(&local).filter(|s| is_rewritable(s)) else {
+                plan.unreadable += 1;
+                continue;
+            };
+
+            let commit_refs = finding.reachable_from.clone();
+            let (in_files, in_messages) = locations(finding);
+
+            if let Some(&index) = seen.get(&secret) {
+                let entry = &mut plan.entries[index];
+                entry.refs.extend(commit_refs);
+                entry.refs.sort();
+                entry.refs.dedup();
+                entry.in_files |= in_files;
+                entry.in_messages |= in_messages;
+                continue;
+            }
+
+            let pattern_id = finding.finding.pattern_id.as_ref();
+            let pattern_name = patterns
+                .iter()
+                .find(|p| p.id.as_ref() == pattern_id)
+                .map_or(pattern_id, |p| p.name.as_ref());
+
+            seen.insert(secret.clone(), plan.entries.len());
+            plan.entries.push(RewriteEntry {
+                secret,
+                replacement: finding.finding.secret.as_masked().to_string(),
+                pattern_name: pattern_name.to_string(),
+                introduced_in: introduced.commit.short_hash.clone(),
+                refs: commit_refs,
+                in_files,
+                in_messages,
+            });
+        }
+
+        plan
+    }
+
+    /// Renders the `git filter-repo --replace-text` expressions file.
+    #[must_use]
+    pub fn filter_repo_expressions(&self) -> String {
+        self.expressions("literal:", |e| e.in_files)
+    }
+
+    /// Renders the `git filter-repo --replace-message` expressions file, or
+    /// `None` when no secret was found in a commit or tag message.
+    #[must_use]
+    pub fn filter_repo_message_expressions(&self) -> Option<String> {
+        self.has_message_secrets()
+            .then(|| self.expressions("literal:", |e| e.in_messages))
+    }
+
+    /// Renders the BFG `--replace-text` list.
+    #[must_use]
+    pub fn bfg_expressions(&self) -> String {
+        self.expressions("", |e| e.in_files)
+    }
+
+    fn has_message_secrets(&self) -> bool {
+        self.entries.iter().any(|e| e.in_messages)
+    }
+
+    fn expressions(&self, prefix: &str, include: impl Fn(&RewriteEntry) -> bool) -> String {
+        let mut out = String::new();
+        for entry in self.entries.iter().filter(|e| include(e)) {
+            out.push_str(prefix);
+            out.push_str(&entry.secret);
+            out.push_str(REPLACE_SEPARATOR);
+            out.push_str(&entry.replacement);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Writes the filter-repo expressions to `path` and the BFG list next to it,
+/// readable by the owner only, then prints the rewrite checklist to stderr.
+///
+/// Both files hold the raw secrets, so a warning is printed when they land in
+/// the working tree without an ignore rule keeping them out of commits.
+pub fn emit(repo: &Repo, plan: &RewritePlan, path: &Path) -> anyhow::Result<()> {
+    let message_expressions = plan.filter_repo_message_expressions();
+    let files = PlanFiles {
+        expressions: path,
+        messages: message_expressions.is_some().then(|| sibling_path(path, "messages")),
+        bfg: sibling_path(path, "bfg"),
+    };
+
+    let mut written = vec![(path, plan.filter_repo_expressions())];
+    if let (Some(messages), Some(content)) = (&files.messages, message_expressions) {
+        written.push((messages, content));
+    }
+    written.push((&files.bfg, plan.bfg_expressions()));
+
+    let local = repo.thread_local();
+    for (file, content) in written {
+        atomic_write_private(file, &content).with_context(|| format!("failed to write {}", file.display()))?;
+        if local.is_unignored_worktree_path(file) {
+            print_warning(&format!(
+                "{} contains raw secrets and is not gitignored; move it out of the repository or ignore it",
+                file.display()
+            ));
+        }
+    }
+
+    let stderr = std::io::stderr();
+    write_checklist(plan, &files, &mut stderr.lock())
+}
+
+/// Returns where a companion file is written for a given expressions file
+/// (`rewrite.txt` becomes `rewrite.bfg.txt` for the `bfg` kind).
+fn sibling_path(path: &Path, kind: &str) -> PathBuf {
+    path.with_extension(format!("{kind}.txt"))
+}
+
+/// Returns whether the secret appears in file contents (including notes) and
+/// whether it appears in commit or tag messages.
+fn locations(finding: &HistoryFinding) -> (bool, bool) {
+    let paths = std::iter::once(&finding.introduced_in.path).chain(finding.lifetimes.iter().map(|l| &l.path));
+
+    let (mut in_files, mut in_messages) = (false, false);
+    for path in paths {
+        match synthetic_prefix(path) {
+            Some(COMMIT_MESSAGE_PREFIX | TAG_MESSAGE_PREFIX) => in_messages = true,
+            _ => in_files = true,
+        }
+    }
+    (in_files, in_messages)
+}
+
+/// Both formats are line-based and split on `==>`, so secrets containing a
+/// line break or the separator cannot be expressed as literals.
+fn is_rewritable(secret: &str) -> bool {
+    !secret.is_empty() && !secret.contains(['\n', '\r']) && !secret.contains(REPLACE_SEPARATOR)
+}
+
+fn write_checklist(plan: &RewritePlan, files: &PlanFiles<'_>, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let count = plan.entries.len();
+    let path = files.expressions;
+
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{} wrote rewrite plan for {count} {} to {} {}",
+        colors::success().apply_to(indicators::SUCCESS),
+        pluralise_word(count, "secret", "secrets"),
+        colors::emphasis().apply_to(path.display()),
+        colors::muted().apply_to(format!("(BFG list: {})", files.bfg.display())),
+    )?;
+
+    if let Some(messages) = &files.messages {
+        writeln!(
+            writer,
+            "{} secrets in commit and tag messages are in {}",
+            colors::info().apply_to(indicators::INFO),
+            colors::emphasis().apply_to(messages.display()),
+        )?;
+    }
+
+    if plan.unreadable > 0 {
+        writeln!(
+            writer,
+            "{} {} {} could not be read back as a single-line literal and must be removed by hand",
+            colors::warning().apply_to(indicators::WARNING),
+            plan.unreadable,
+            pluralise_word(plan.unreadable, "secret", "secrets"),
+        )?;
+    }
+
+    if count == 0 {
+        writeln!(writer)?;
+        return Ok(());
+    }
+
+    writeln!(writer)?;
+    for entry in &plan.entries {
+        let location = if entry.in_messages && !entry.in_files {
+            " (message only)"
+        } else if entry.in_messages {
+            " (files and messages)"
+        } else {
+            ""
+        };
+        writeln!(
+            writer,
+            "{} {} {} {}",
+            style(&entry.pattern_name).bold(),
+            colors::muted().apply_to("·"),
+            colors::code().apply_to(&entry.replacement),
+            colors::muted().apply_to(format!("introduced in {}{location}", entry.introduced_in)),
+        )?;
+
+        if entry.refs.is_empty() {
+            writeln!(
+                writer,
+                "  {} {}",
+                colors::muted().apply_to("└─"),
+                colors::secondary().apply_to("not on any branch or tag")
+            )?;
+        }
+
+        for (i, name) in entry.refs.iter().enumerate() {
+            let prefix = if i + 1 == entry.refs.len() { "└─" } else { "├─" };
+            writeln!(
+                writer,
+                "  {} {}",
+                colors::muted().apply_to(prefix),
+                colors::secondary().apply_to(name)
+            )?;
+        }
+    }
+
+    let filter_repo = match &files.messages {
+        Some(messages) => format!(
+            "git filter-repo --replace-text {} --replace-message {}",
+            path.display(),
+            messages.display()
+        ),
+        None => format!("git filter-repo --replace-text {}", path.display()),
+    };
+    let bfg = if plan.has_message_secrets() {
+        format!(
+            "`bfg --replace-text {}` (BFG leaves messages untouched)",
+            files.bfg.display()
+        )
+    } else {
+        format!("`bfg --replace-text {}`", files.bfg.display())
+    };
+
+    let steps = [
+        "Rotate every secret above first; rewriting history does not revoke them".to_string(),
+        format!("In a fresh mirror clone, run `{filter_repo}` or {bfg}"),
+        "Force-push every branch and tag listed above (`git push --force --all && git push --force --tags`)"
+            .to_string(),
+        "Ask collaborators to re-clone, and purge forks, CI caches and pull request refs".to_string(),
+    ];
+
+    writeln!(writer)?;
+    writeln!(writer, "{}", style("Rewrite checklist").bold())?;
+    for (i, step) in steps.iter().enumerate() {
+        writeln!(writer, "  {} {}", colors::muted().apply_to(format!("{}.", i + 1)), step)?;
+    }
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(secret: &str, replacement: &str, in_messages: bool) -> RewriteEntry {
+        RewriteEntry {
+            secret: secret.to_string(),
+            replacement: replacement.to_string(),
+            pattern_name: "GitHub Token".to_string(),
+            introduced_in: "abc1234".to_string(),
+            refs: vec!["refs/heads/main".to_string()],
+            in_files: !in_messages,
+            in_messages,
+        }
+    }
+
+    fn plan(secret: &str, replacement: &str) -> RewritePlan {
+        RewritePlan {
+            entries: vec![entry(secret, replacement, false)],
+            unreadable: 0,
+        }
+    }
+
+    fn files(messages: Option<&str>) -> PlanFiles<'static> {
+        PlanFiles {
+            expressions: Path::new("rewrite.txt"),
+            messages: messages.map(PathBuf::from),
+            bfg: PathBuf::from("rewrite.bfg.txt"),
+        }
+    }
+
+    fn checklist(plan: &RewritePlan, files: &PlanFiles<'_>) -> String {
+        let mut out = Vec::new();
+        write_checklist(plan, files, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn filter_repo_expressions_use_literal_prefix() {
+        let plan = plan("ghp_secret123", "ghp_••••t123");
+        assert_eq!(plan.filter_repo_expressions(), "literal:ghp_secret123==>ghp_••••t123\n");
+    }
+
+    #[test]
+    fn bfg_expressions_are_plain_literals() {
+        let plan = plan("ghp_secret123", "ghp_••••t123");
+        assert_eq!(plan.bfg_expressions(), "ghp_secret123==>ghp_••••t123\n");
+    }
+
+    #[test]
+    fn message_secrets_only_get_message_expressions() {
+        let plan = RewritePlan {
+            entries: vec![
+                entry("ghp_infile", "ghp_••••file", false),
+                entry("ghp_inmessage", "ghp_••••sage", true),
+            ],
+            unreadable: 0,
+        };
+
+        assert_eq!(plan.filter_repo_expressions(), "literal:ghp_infile==>ghp_••••file\n");
+        assert_eq!(plan.bfg_expressions(), "ghp_infile==>ghp_••••file\n");
+        assert_eq!(
+            plan.filter_repo_message_expressions().as_deref(),
+            Some("literal:ghp_inmessage==>ghp_••••sage\n")
+        );
+    }
+
+    #[test]
+    fn file_only_plans_have_no_message_expressions() {
+        assert_eq!(plan("ghp_secret123", "x").filter_repo_message_expressions(), None);
+    }
+
+    #[test]
+    fn sibling_paths_sit_next_to_expressions_file() {
+        assert_eq!(
+            sibling_path(Path::new("out/rewrite.txt"), "bfg"),
+            Path::new("out/rewrite.bfg.txt")
+        );
+        assert_eq!(
+            sibling_path(Path::new("expressions"), "messages"),
+            Path::new("expressions.messages.txt")
+        );
+    }
+
+    #[test]
+    fn multi_line_and_separator_secrets_are_not_rewritable() {
+        assert!(is_rewritable("sk_live_abc123"));
+        assert!(!is_rewritable("-----BEGIN KEY-----\nabc"));
+        assert!(!is_rewritable("abc==>def"));
+        assert!(!is_rewritable(""));
+    }
+
+    #[test]
+    fn checklist_lists_refs_and_commands() {
+        let out = checklist(&plan("ghp_secret123", "ghp_••••t123"), &files(None));
+
+        assert!(out.contains("refs/heads/main"));
+        assert!(out.contains("git filter-repo --replace-text rewrite.txt`"));
+        assert!(out.contains("bfg --replace-text rewrite.bfg.txt"));
+        assert!(!out.contains("--replace-message"));
+        assert!(!out.contains("ghp_secret123"));
+    }
+
+    #[test]
+    fn checklist_passes_message_expressions_to_filter_repo() {
+        let plan = RewritePlan {
+            entries: vec![entry("ghp_inmessage", "ghp_••••sage", true)],
+            unreadable: 0,
+        };
+        let out = checklist(&plan, &files(Some("rewrite.messages.txt")));
+
+        assert!(out.contains("--replace-message rewrite.messages.txt"));
+        assert!(out.contains("(message only)"));
+        assert!(out.contains("BFG leaves messages untouched"));
+    }
+}
@@ -1,38 +1,128 @@
 //! History scanning and deduplication logic.
+//!
+//! Scanning runs in two parallel passes. Every commit is first diffed against
+//! its first parent to find the blobs it introduced; each unique blob is then
+//! scanned exactly once, and its findings are attributed back to every commit
+//! and path that introduced it. A file that flips between two versions across
+//! a thousand commits is therefore scanned twice, not a thousand times.
+//!
+//! Commit messages, annotated tag messages and notes are scanned alongside
+//! the files, under synthetic paths such as `commit-message:<sha>`.
 
-use std::collections::HashMap;
 use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-use globset::GlobSet;
+use indicatif::ProgressBar;
 use rayon::prelude::*;
+use vet_core::ast::{FileKind, file_kind};
 use vet_core::prelude::*;
 
-use super::{HistoryFinding, HistoryOptions, HistoryScanResult, SecretOccurrence};
-use crate::git::{ObjectId, Repo};
-use crate::ui::{create_commit_progress, print_warning};
+use super::context::PathFilter;
+use super::lifetime::{LifetimeTracker, SecretKey};
+use super::state::{HistoryState, Restored};
+use super::{BlobKey, HistoryFinding, HistoryOptions, HistoryScanResult, SecretOccurrence};
+use crate::HistoryInclude;
+use crate::git::{CommitInfo, ObjectId, Repo, WalkedCommit};
+use crate::ui::{create_blob_progress, create_commit_progress, print_warning};
 
-struct RawFinding {
-    pattern_id: Arc<str>,
-    fingerprint: u64,
-    finding: vet_core::Finding,
-    occurrence: SecretOccurrence,
+/// Minimum number of items handed to each rayon task.
+const MIN_CHUNK_SIZE: usize = 64;
+
+/// Synthetic path prefix for secrets in a commit's message.
+pub const COMMIT_MESSAGE_PREFIX: &str = "commit-message";
+
+/// Synthetic path prefix for secrets in a commit's note.
+pub const NOTE_PREFIX: &str = "note";
+
+/// Synthetic path prefix for secrets in an annotated tag's message.
+pub const TAG_MESSAGE_PREFIX: &str = "tag-message";
+
+/// Every synthetic path prefix; each is followed by `:` and a commit or tag.
+pub const SYNTHETIC_PREFIXES: [&str; 3] = [COMMIT_MESSAGE_PREFIX, NOTE_PREFIX, TAG_MESSAGE_PREFIX];
+
+/// Returns the synthetic prefix `path` starts with, or `None` for a path in
+/// the tree.
+#[must_use]
+pub fn synthetic_prefix(path: &Path) -> Option<&'static str> {
+    let text = path.to_str()?;
+    SYNTHETIC_PREFIXES
+        .into_iter()
+        .find(|prefix| text.strip_prefix(prefix).is_some_and(|rest| rest.starts_with(':')))
+}
+
+/// Notes and annotated tags attached to each commit.
+#[derive(Default)]
+struct Annotations {
+    /// The note blob attached to each commit.
+    notes: HashMap<ObjectId, ObjectId>,
+    /// The short name and tag object of each annotated tag, by tagged commit.
+    tags: HashMap<ObjectId, Vec<(String, ObjectId)>>,
+}
+
+impl Annotations {
+    fn load(repo: &Repo) -> Self {
+        let local_repo = repo.thread_local();
+        let mut tags: HashMap<ObjectId, Vec<(String, ObjectId)>> = HashMap::new();
+        for (name, tag, commit) in local_repo.annotated_tags() {
+            tags.entry(commit).or_default().push((name, tag));
+        }
+
+        Self {
+            notes: local_repo.notes(),
+            tags,
+        }
+    }
+
+    /// The commit's own message, its note and the messages of the tags
+    /// pointing at it, each under a synthetic path.
+    fn messages(&self, commit: ObjectId) -> Vec<(PathBuf, ObjectId)> {
+        let mut messages = vec![(PathBuf::from(format!("{COMMIT_MESSAGE_PREFIX}:{commit}")), commit)];
+
+        if let Some(&note) = self.notes.get(&commit) {
+            messages.push((PathBuf::from(format!("{NOTE_PREFIX}:{commit}")), note));
+        }
+
+        for (name, tag) in self.tags.get(&commit).into_iter().flatten() {
+            messages.push((PathBuf::from(format!("{TAG_MESSAGE_PREFIX}:{name}")), *tag));
+        }
+
+        messages
+    }
 }
 
-struct CommitScanResult {
-    findings: Vec<RawFinding>,
+/// The files a single commit changed, after exclusions.
+struct CommitChanges {
+    commit: Arc<CommitInfo>,
+    source: Option<Arc<str>>,
+    files: Vec<FileChange>,
+}
+
+/// One changed path: the blob the commit left there (if any) and the blob
+/// its first parent had there (if any).
+struct FileChange {
+    path: PathBuf,
+    key: Option<BlobKey>,
+    previous: Option<ObjectId>,
 }
 
 /// Scans git history for secrets, deduplicating by fingerprint and tracking
-/// the earliest commit each secret was introduced in.
+/// the earliest commit each secret was introduced in, the commits that
+/// removed it, and whether it is still reachable.
+///
+/// With a `state`, commits it has already scanned are skipped and their
+/// recorded findings are merged in instead, and the state is updated with
+/// everything scanned in this run.
 pub fn scan_history(
     repo: &Repo,
     scanner: &Scanner,
     opts: &HistoryOptions,
-    excludes: &GlobSet,
+    path_filter: &PathFilter,
     max_file_size: Option<u64>,
     show_progress: bool,
+    state: Option<&mut HistoryState>,
 ) -> anyhow::Result<HistoryScanResult> {
     if repo.is_shallow() {
         print_warning(
@@ -40,37 +130,279 @@ pub fn scan_history(
         );
     }
 
-    let commit_ids = repo.collect_commits(opts)?;
-    let commits_scanned = commit_ids.len();
+    let commits = repo.collect_commits(opts)?;
+    let commits_scanned = commits.len();
+    let include_dangling = opts.include.contains(&HistoryInclude::Unreachable);
 
-    if commits_scanned == 0 {
+    if commits_scanned == 0 && !include_dangling {
         return Ok(HistoryScanResult {
             findings: Vec::new(),
             commits_scanned: 0,
         });
     }
 
-    let pb = show_progress.then(|| create_commit_progress(commits_scanned));
+    let (restored, pending) = match state.as_deref() {
+        Some(state) => {
+            let in_range: HashMap<ObjectId, Option<Arc<str>>> =
+                commits.iter().map(|c| (c.id, c.source.clone())).collect();
+            let restored = state.restore(&repo.thread_local(), scanner, &in_range);
+            let pending: Vec<_> = commits.into_iter().filter(|c| !state.has_commit(c.id)).collect();
+            (restored, pending)
+        }
+        None => (Restored::default(), commits),
+    };
 
-    let progress = AtomicUsize::new(0);
+    let annotations = if pending.is_empty() {
+        Annotations::default()
+    } else {
+        Annotations::load(repo)
+    };
+    let mut changes = collect_changes(repo, &pending, &annotations, path_filter, show_progress);
+    if include_dangling {
+        changes.extend(dangling_blob_changes(repo));
+    }
+    let blob_findings = scan_blobs(repo, &changes, scanner, max_file_size, state.as_deref(), show_progress);
+
+    let occurrences = attribute_findings(&changes, &blob_findings);
+    let removals = find_removals(&changes, &blob_findings, &restored, &occurrences, state.as_deref());
+
+    if let Some(state) = state {
+        for commit in &pending {
+            state.record_commit(commit.id);
+        }
+        for (&key, findings) in &blob_findings {
+            if findings.is_empty() {
+                state.record_clean_blob(key);
+            }
+        }
+        for (finding, occurrence) in &occurrences {
+            state.record_occurrence(&finding.pattern_id, finding.secret.fingerprint(), occurrence);
+        }
+        for ((pattern_id, fingerprint), path, commit) in &removals {
+            state.record_removal(pattern_id, *fingerprint, path, commit);
+        }
+    }
+
+    let mut findings_map: HashMap<SecretKey, HistoryFinding> = HashMap::new();
+    let mut tracker = LifetimeTracker::default();
+
+    for (finding, occurrence) in restored.occurrences {
+        tracker.record_occurrence(&secret_key(&finding), &occurrence);
+        merge_occurrence(&mut findings_map, &finding, occurrence, opts.all);
+    }
+
+    for (finding, occurrence) in occurrences {
+        tracker.record_occurrence(&secret_key(finding), &occurrence);
+        merge_occurrence(&mut findings_map, finding, occurrence, opts.all);
+    }
+
+    for (pattern_id, fingerprint, path, commit) in restored.removals {
+        tracker.record_removal((pattern_id, fingerprint), path, commit);
+    }
+
+    for (key, path, commit) in removals {
+        tracker.record_removal(key, path.to_path_buf(), Arc::clone(commit));
+    }
+
+    let mut findings: Vec<_> = findings_map.into_values().collect();
+    findings.sort_by_key(|f| f.introduced_in.commit.date);
+    tracker.apply(&repo.thread_local(), &mut findings);
+
+    Ok(HistoryScanResult {
+        findings,
+        commits_scanned,
+    })
+}
+
+/// Pairs each finding in a scanned blob with every commit and path that
+/// introduced that blob.
+fn attribute_findings<'a>(
+    changes: &[CommitChanges],
+    blob_findings: &'a HashMap<BlobKey, Vec<Finding>>,
+) -> Vec<(&'a Finding, SecretOccurrence)> {
+    // Commits are walked newest first; merging oldest first means commits with
+    // the same timestamp resolve to the one earlier in history.
+    let mut occurrences = Vec::new();
+    for change in changes.iter().rev() {
+        for file in &change.files {
+            let Some(key) = file.key else {
+                continue;
+            };
+
+            for finding in blob_findings.get(&key).into_iter().flatten() {
+                let occurrence = SecretOccurrence {
+                    commit: Arc::clone(&change.commit),
+                    path: file.path.clone(),
+                    blob_id: key.blob,
+                    found_via: change.source.clone(),
+                    line: finding.span.line,
+                    column: finding.span.column,
+                    line_content: finding.masked_line.to_string(),
+                };
+                occurrences.push((finding, occurrence));
+            }
+        }
+    }
+    occurrences
+}
+
+/// Finds the commits that removed a secret from a path: the parent's blob
+/// there held it, and the commit's blob (if the file still exists) does not.
+fn find_removals<'a>(
+    changes: &'a [CommitChanges],
+    blob_findings: &HashMap<BlobKey, Vec<Finding>>,
+    restored: &Restored,
+    occurrences: &[(&Finding, SecretOccurrence)],
+    state: Option<&HistoryState>,
+) -> Vec<(SecretKey, &'a Path, &'a Arc<CommitInfo>)> {
+    let known_findings = |key: BlobKey| -> Option<&[Finding]> {
+        blob_findings
+            .get(&key)
+            .map(Vec::as_slice)
+            .or_else(|| state.is_some_and(|s| s.is_clean_blob(key)).then_some(&[][..]))
+    };
+
+    let mut secrets_by_blob: HashMap<ObjectId, HashSet<SecretKey>> = HashMap::new();
+    let all_occurrences = restored
+        .occurrences
+        .iter()
+        .map(|(finding, occ)| (finding, occ))
+        .chain(occurrences.iter().map(|(finding, occ)| (*finding, occ)));
+    for (finding, occurrence) in all_occurrences {
+        secrets_by_blob
+            .entry(occurrence.blob_id)
+            .or_default()
+            .insert(secret_key(finding));
+    }
+
+    let mut removals = Vec::new();
+    for change in changes {
+        for file in &change.files {
+            let Some(secrets) = file.previous.and_then(|blob| secrets_by_blob.get(&blob)) else {
+                continue;
+            };
+            let remaining = match file.key {
+                Some(key) => known_findings(key),
+                None => Some(&[][..]),
+            };
+            let Some(remaining) = remaining else {
+                continue;
+            };
+
+            for secret in secrets {
+                if !remaining.iter().any(|f| secret_key(f) == *secret) {
+                    removals.push((secret.clone(), file.path.as_path(), &change.commit));
+                }
+            }
+        }
+    }
+    removals
+}
+
+fn secret_key(finding: &Finding) -> SecretKey {
+    (Arc::clone(&finding.pattern_id), finding.secret.fingerprint())
+}
+
+/// Adds one occurrence of `finding` to the deduplicated set, moving its
+/// introduction point if the occurrence is earlier.
+fn merge_occurrence(
+    findings_map: &mut HashMap<SecretKey, HistoryFinding>,
+    finding: &Finding,
+    occurrence: SecretOccurrence,
+    all: bool,
+) {
+    let at_occurrence = |finding: &Finding, path: &Path| Finding {
+        path: path.into(),
+        ..finding.clone()
+    };
+
+    match findings_map.entry(secret_key(finding)) {
+        Entry::Occupied(mut entry) => {
+            let existing = entry.get_mut();
+            existing.occurrence_count += 1;
+
+            if all {
+                existing.occurrences.push(occurrence.clone());
+            }
+
+            if occurrence.commit.date < existing.introduced_in.commit.date {
+                existing.finding = at_occurrence(finding, &occurrence.path);
+                existing.introduced_in = occurrence;
+            }
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(HistoryFinding {
+                finding: at_occurrence(finding, &occurrence.path),
+                introduced_in: occurrence.clone(),
+                occurrences: if all { vec![occurrence] } else { vec![] },
+                occurrence_count: 1,
+                lifetimes: Vec::new(),
+                present_at_head: false,
+                reachable_from: Vec::new(),
+                verification: None,
+            });
+        }
+    }
+}
 
-    let chunk_size = (commits_scanned / rayon::current_num_threads().max(1)).max(64);
+/// Diffs each commit against its first parent in parallel, keeping the files
+/// that are not excluded, and adds the commit's messages.
+fn collect_changes(
+    repo: &Repo,
+    commits: &[WalkedCommit],
+    annotations: &Annotations,
+    path_filter: &PathFilter,
+    show_progress: bool,
+) -> Vec<CommitChanges> {
+    if commits.is_empty() {
+        return Vec::new();
+    }
 
-    let chunk_results: Vec<CommitScanResult> = commit_ids
-        .par_chunks(chunk_size)
+    let pb = show_progress.then(|| create_commit_progress(commits.len()));
+    let progress = AtomicUsize::new(0);
+
+    let changes = commits
+        .par_chunks(chunk_size(commits.len()))
         .flat_map(|chunk| {
             let local_repo = repo.thread_local();
             chunk
                 .iter()
-                .filter_map(|&oid| {
-                    let result = scan_commit_isolated(&local_repo, oid, scanner, excludes, max_file_size);
+                .filter_map(|walked| {
+                    let commit = local_repo.commit_info(walked.id).map(Arc::new);
+                    let files = local_repo
+                        .commit_changes(walked.id)
+                        .into_iter()
+                        .map(|file| FileChange {
+                            key: file.blob_id.map(|blob| BlobKey {
+                                blob,
+                                kind: file_kind(&file.path),
+                            }),
+                            previous: file.previous_blob_id,
+                            path: file.path,
+                        })
+                        .chain(
+                            annotations
+                                .messages(walked.id)
+                                .into_iter()
+                                .map(|(path, blob)| FileChange {
+                                    path,
+                                    key: Some(BlobKey {
+                                        blob,
+                                        kind: FileKind::Other,
+                                    }),
+                                    previous: None,
+                                }),
+                        )
+                        .filter(|file| path_filter.matches(&file.path))
+                        .collect();
 
-                    let completed = progress.fetch_add(1, Ordering::Relaxed) + 1;
-                    if let Some(ref pb) = pb {
-                        pb.set_position(completed as u64);
-                    }
+                    tick(pb.as_ref(), &progress);
 
-                    result
+                    Some(CommitChanges {
+                        commit: commit?,
+                        source: walked.source.clone(),
+                        files,
+                    })
                 })
                 .collect::<Vec<_>>()
         })
@@ -80,93 +412,98 @@ pub fn scan_history(
         pb.finish_and_clear();
     }
 
-    let mut findings_map: HashMap<(Arc<str>, u64), HistoryFinding> = HashMap::new();
-
-    for result in chunk_results {
-        for raw in result.findings {
-            let key = (raw.pattern_id, raw.fingerprint);
+    changes
+}
 
-            match findings_map.entry(key) {
-                Entry::Occupied(mut entry) => {
-                    let existing = entry.get_mut();
-                    existing.occurrence_count += 1;
+/// Treats each dangling blob as a commit that added it, under a path named
+/// after the blob.
+fn dangling_blob_changes(repo: &Repo) -> Vec<CommitChanges> {
+    let unreachable: Arc<str> = Arc::from("unreachable");
 
-                    if opts.all {
-                        existing.occurrences.push(raw.occurrence.clone());
-                    }
+    repo.thread_local()
+        .dangling_blobs()
+        .into_iter()
+        .map(|(blob, commit)| {
+            let path = PathBuf::from(&commit.hash);
+            CommitChanges {
+                commit: Arc::new(commit),
+                source: Some(Arc::clone(&unreachable)),
+                files: vec![FileChange {
+                    key: Some(BlobKey {
+                        blob,
+                        kind: file_kind(&path),
+                    }),
+                    previous: None,
+                    path,
+                }],
+            }
+        })
+        .collect()
+}
 
-                    if raw.occurrence.commit.date < existing.introduced_in.commit.date {
-                        existing.introduced_in = raw.occurrence;
-                    }
-                }
-                Entry::Vacant(entry) => {
-                    entry.insert(HistoryFinding {
-                        finding: raw.finding,
-                        introduced_in: raw.occurrence.clone(),
-                        occurrences: if opts.all { vec![raw.occurrence] } else { vec![] },
-                        occurrence_count: 1,
-                    });
-                }
+/// Scans every unique blob once in parallel, returning its findings. Blobs
+/// the state already knows to be clean are skipped, and blobs that are too
+/// large or binary are left out.
+fn scan_blobs(
+    repo: &Repo,
+    changes: &[CommitChanges],
+    scanner: &Scanner,
+    max_file_size: Option<u64>,
+    state: Option<&HistoryState>,
+    show_progress: bool,
+) -> HashMap<BlobKey, Vec<Finding>> {
+    let mut unique: HashMap<BlobKey, &Path> = HashMap::new();
+    for change in changes {
+        for file in &change.files {
+            if let Some(key) = file.key
+                && !state.is_some_and(|s| s.is_clean_blob(key))
+            {
+                unique.entry(key).or_insert(&file.path);
             }
         }
     }
 
-    let mut findings: Vec<_> = findings_map.into_values().collect();
-    findings.sort_by_key(|f| f.introduced_in.commit.date);
+    if unique.is_empty() {
+        return HashMap::new();
+    }
 
-    Ok(HistoryScanResult {
-        findings,
-        commits_scanned,
-    })
-}
+    let blobs: Vec<(BlobKey, &Path)> = unique.into_iter().collect();
+    let pb = show_progress.then(|| create_blob_progress(blobs.len()));
+    let progress = AtomicUsize::new(0);
 
-fn scan_commit_isolated(
-    repo: &crate::git::LocalRepo,
-    oid: ObjectId,
-    scanner: &Scanner,
-    excludes: &GlobSet,
-    max_file_size: Option<u64>,
-) -> Option<CommitScanResult> {
-    let commit_info = repo.commit_info(oid)?;
-    let commit_info = Arc::new(commit_info);
-    let changed_files = repo.commit_changes(oid);
+    let findings = blobs
+        .par_chunks(chunk_size(blobs.len()))
+        .flat_map(|chunk| {
+            let local_repo = repo.thread_local();
+            chunk
+                .iter()
+                .filter_map(|&(key, path)| {
+                    let findings = local_repo
+                        .read_blob_as_text(key.blob, max_file_size)
+                        .map(|content| scanner.scan_content(&content, path));
 
-    let mut findings = Vec::new();
+                    tick(pb.as_ref(), &progress);
 
-    for file in changed_files {
-        if excludes.is_match(&file.path) {
-            continue;
-        }
-
-        let Some(content) = repo.read_blob_as_text(file.blob_id, max_file_size) else {
-            continue;
-        };
-
-        let file_findings = scanner.scan_content(&content, &file.path);
-
-        for finding in file_findings {
-            let line_content = content
-                .lines()
-                .nth(finding.span.line.saturating_sub(1) as usize)
-                .unwrap_or("")
-                .to_string();
-
-            let occurrence = SecretOccurrence {
-                commit: Arc::clone(&commit_info),
-                path: file.path.clone(),
-                line: finding.span.line,
-                column: finding.span.column,
-                line_content,
-            };
+                    Some((key, findings?))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
 
-            findings.push(RawFinding {
-                pattern_id: Arc::clone(&finding.pattern_id),
-                fingerprint: finding.secret.fingerprint(),
-                finding,
-                occurrence,
-            });
-        }
+    if let Some(pb) = pb {
+        pb.finish_and_clear();
     }
 
-    Some(CommitScanResult { findings })
+    findings
+}
+
+fn chunk_size(total: usize) -> usize {
+    (total / rayon::current_num_threads().max(1)).max(MIN_CHUNK_SIZE)
+}
+
+fn tick(pb: Option<&ProgressBar>, progress: &AtomicUsize) {
+    let completed = progress.fetch_add(1, Ordering::Relaxed) + 1;
+    if let Some(pb) = pb {
+        pb.set_position(completed as u64);
+    }
 }
@@ -0,0 +1,391 @@
+//! Persisted state for incremental history scans.
+//!
+//! With `vet history --state <file>`, the commits and clean blobs already
+//! scanned are recorded together with the secrets found in them, so the next
+//! run only scans new commits and merges their findings into the previous set.
+//! The state is keyed by a hash of the patterns and configuration, and is
+//! discarded whenever either changes. It never contains raw secrets: findings
+//! are restored by rescanning the blob each secret was introduced in.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use vet_core::fs_util::atomic_write;
+use vet_core::prelude::*;
+
+use super::{BlobKey, SecretOccurrence};
+use crate::git::{CommitInfo, LocalRepo, ObjectId};
+
+const STATE_VERSION: u32 = 2;
+
+/// Commits, clean blobs and findings recorded by previous history scans.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HistoryState {
+    version: u32,
+    scan_key: String,
+    commits: BTreeSet<String>,
+    clean_blobs: BTreeSet<String>,
+    findings: Vec<StoredFinding>,
+    #[serde(skip)]
+    index: HashMap<(String, u64), usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredFinding {
+    pattern_id: String,
+    fingerprint: u64,
+    occurrences: Vec<StoredOccurrence>,
+    removals: Vec<StoredRemoval>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredRemoval {
+    commit: String,
+    path: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredOccurrence {
+    commit: String,
+    path: PathBuf,
+    blob: String,
+    line: u32,
+    column: u32,
+    line_content: String,
+}
+
+/// Findings and removals recovered from the state for the commits in range.
+#[derive(Debug, Default)]
+pub struct Restored {
+    /// Each recorded occurrence, paired with its secret's finding.
+    pub occurrences: Vec<(Finding, SecretOccurrence)>,
+    /// Each recorded removal: the secret's pattern ID and fingerprint, the
+    /// path it was removed from and the removing commit.
+    pub removals: Vec<(Arc<str>, u64, PathBuf, Arc<CommitInfo>)>,
+}
+
+/// Why a state file could not be reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Invalidation {
+    /// No state file exists yet.
+    Missing,
+    /// The file could not be parsed or has an older layout.
+    Unreadable,
+    /// Patterns or configuration changed since the state was written.
+    ScanChanged,
+}
+
+impl HistoryState {
+    /// Loads the state at `path` if it was written with the same `scan_key`,
+    /// or starts an empty state and reports why the file was not reused.
+    #[must_use]
+    pub fn load(path: &Path, scan_key: &str) -> (Self, Option<Invalidation>) {
+        let fresh = || Self {
+            version: STATE_VERSION,
+            scan_key: scan_key.to_string(),
+            ..Self::default()
+        };
+
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return (fresh(), Some(Invalidation::Missing));
+        };
+
+        let Ok(mut state) = serde_json::from_str::<Self>(&content) else {
+            return (fresh(), Some(Invalidation::Unreadable));
+        };
+
+        if state.version != STATE_VERSION {
+            return (fresh(), Some(Invalidation::Unreadable));
+        }
+
+        if state.scan_key != scan_key {
+            return (fresh(), Some(Invalidation::ScanChanged));
+        }
+
+        state.rebuild_index();
+        (state, None)
+    }
+
+    /// Writes the state to `path`.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_string(self)?;
+        atomic_write(path, &json).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Returns `true` if `commit` was scanned by a previous run.
+    #[must_use]
+    pub fn has_commit(&self, commit: ObjectId) -> bool {
+        self.commits.contains(&commit.to_string())
+    }
+
+    /// Returns `true` if `key` was scanned without findings by a previous run.
+    #[must_use]
+    pub fn is_clean_blob(&self, key: BlobKey) -> bool {
+        self.clean_blobs.contains(&key.to_string())
+    }
+
+    /// Marks `commit` as scanned.
+    pub fn record_commit(&mut self, commit: ObjectId) {
+        self.commits.insert(commit.to_string());
+    }
+
+    /// Marks `key` as scanned without findings.
+    pub fn record_clean_blob(&mut self, key: BlobKey) {
+        self.clean_blobs.insert(key.to_string());
+    }
+
+    /// Records an occurrence of the secret identified by `pattern_id` and
+    /// `fingerprint`.
+    pub fn record_occurrence(&mut self, pattern_id: &str, fingerprint: u64, occurrence: &SecretOccurrence) {
+        let stored = StoredOccurrence {
+            commit: occurrence.commit.hash.clone(),
+            path: occurrence.path.clone(),
+            blob: occurrence.blob_id.to_string(),
+            line: occurrence.line,
+            column: occurrence.column,
+            line_content: occurrence.line_content.clone(),
+        };
+        self.entry(pattern_id, fingerprint).occurrences.push(stored);
+    }
+
+    /// Records that `commit` removed the secret identified by `pattern_id`
+    /// and `fingerprint` from `path`.
+    pub fn record_removal(&mut self, pattern_id: &str, fingerprint: u64, path: &Path, commit: &CommitInfo) {
+        let stored = StoredRemoval {
+            commit: commit.hash.clone(),
+            path: path.to_path_buf(),
+        };
+        self.entry(pattern_id, fingerprint).removals.push(stored);
+    }
+
+    fn entry(&mut self, pattern_id: &str, fingerprint: u64) -> &mut StoredFinding {
+        let key = (pattern_id.to_string(), fingerprint);
+        let index = *self.index.entry(key).or_insert_with(|| {
+            self.findings.push(StoredFinding {
+                pattern_id: pattern_id.to_string(),
+                fingerprint,
+                occurrences: Vec::new(),
+                removals: Vec::new(),
+            });
+            self.findings.len() - 1
+        });
+        &mut self.findings[index]
+    }
+
+    /// Restores the recorded occurrences and removals whose commit is in
+    /// `in_range` (labelled with the source it maps to), pairing each
+    /// occurrence with its secret's finding from the earliest of them.
+    ///
+    /// The finding is rebuilt by rescanning the blob it was introduced in;
+    /// secrets whose blob no longer yields a matching finding are dropped.
+    #[must_use]
+    pub fn restore(
+        &self,
+        repo: &LocalRepo,
+        scanner: &Scanner,
+        in_range: &HashMap<ObjectId, Option<Arc<str>>>,
+    ) -> Restored {
+        let mut commits: HashMap<ObjectId, Option<Arc<CommitInfo>>> = HashMap::new();
+        let mut commit_in_range = |hex: &str| {
+            let id = ObjectId::from_hex(hex).filter(|id| in_range.contains_key(id))?;
+            commits
+                .entry(id)
+                .or_insert_with(|| repo.commit_info(id).map(Arc::new))
+                .clone()
+        };
+
+        let mut restored = Restored::default();
+
+        for stored in &self.findings {
+            let mut occurrences: Vec<SecretOccurrence> = stored
+                .occurrences
+                .iter()
+                .filter_map(|occ| {
+                    let commit = commit_in_range(&occ.commit)?;
+                    Some(SecretOccurrence {
+                        found_via: in_range.get(&commit.id).cloned().flatten(),
+                        commit,
+                        path: occ.path.clone(),
+                        blob_id: ObjectId::from_hex(&occ.blob)?,
+                        line: occ.line,
+                        column: occ.column,
+                        line_content: occ.line_content.clone(),
+                    })
+                })
+                .collect();
+
+            occurrences.sort_by_key(|occ| occ.commit.date);
+            let Some(first) = occurrences.first() else {
+                continue;
+            };
+
+            let Some(finding) = rescan(repo, scanner, stored, first) else {
+                continue;
+            };
+
+            for removal in &stored.removals {
+                if let Some(commit) = commit_in_range(&removal.commit) {
+                    restored.removals.push((
+                        Arc::clone(&finding.pattern_id),
+                        stored.fingerprint,
+                        removal.path.clone(),
+                        commit,
+                    ));
+                }
+            }
+
+            restored
+                .occurrences
+                .extend(occurrences.into_iter().map(|occ| (finding.clone(), occ)));
+        }
+
+        restored
+    }
+
+    fn rebuild_index(&mut self) {
+        self.index = self
+            .findings
+            .iter()
+            .enumerate()
+            .map(|(i, f)| ((f.pattern_id.clone(), f.fingerprint), i))
+            .collect();
+    }
+}
+
+/// Hashes everything that affects which secrets a scan finds: the vet
+/// version, the loaded patterns, the detection settings of the
+/// configuration and the scan options.
+///
+/// Settings applied after scanning, such as ignores, fix policies and the
+/// baseline path, are left out so editing them keeps the state.
+#[must_use]
+pub fn scan_key(
+    patterns: &[Pattern],
+    config: &Config,
+    severity: Option<Severity>,
+    excludes: &[String],
+    paths: &[String],
+    max_file_size: Option<u64>,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+
+    for pattern in patterns {
+        hasher.update(format!(
+            "\0{}\0{}\0{}\0{:?}\0{}\0{:?}\0{:?}",
+            pattern.id,
+            pattern.severity,
+            pattern.regex.as_str(),
+            pattern.keywords,
+            pattern.default_enabled,
+            pattern.min_entropy,
+            pattern.strategy,
+        ));
+    }
+
+    hasher.update(serde_json::to_string(&config.patterns).unwrap_or_default());
+    hasher.update(format!(
+        "\0{:?}\0{:?}\0{:?}\0{:?}\0{:?}",
+        config.disabled_patterns,
+        config.exclude_paths,
+        config.severity,
+        config.max_file_size,
+        config.minimum_confidence,
+    ));
+    hasher.update(format!("\0{severity:?}\0{excludes:?}\0{paths:?}\0{max_file_size:?}"));
+
+    hex::encode(hasher.finalize())
+}
+
+fn rescan(
+    repo: &LocalRepo,
+    scanner: &Scanner,
+    stored: &StoredFinding,
+    occurrence: &SecretOccurrence,
+) -> Option<Finding> {
+    let content = repo.read_blob_as_text(occurrence.blob_id, None)?;
+    let findings = scanner.scan_content(&content, &occurrence.path);
+
+    findings
+        .into_iter()
+        .filter(|f| f.pattern_id.as_ref() == stored.pattern_id && f.secret.fingerprint() == stored.fingerprint)
+        .min_by_key(|f| f.span.line.abs_diff(occurrence.line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_reports_missing_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (state, invalidation) = HistoryState::load(&dir.path().join("state.json"), "key");
+
+        assert_eq!(invalidation, Some(Invalidation::Missing));
+        assert_eq!(state.scan_key, "key");
+    }
+
+    #[test]
+    fn load_discards_state_from_different_scan() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+
+        let (mut state, _) = HistoryState::load(&path, "old");
+        state.commits.insert("a".repeat(40));
+        state.save(&path).unwrap();
+
+        let (state, invalidation) = HistoryState::load(&path, "new");
+        assert_eq!(invalidation, Some(Invalidation::ScanChanged));
+        assert!(state.commits.is_empty());
+
+        let (state, invalidation) = HistoryState::load(&path, "old");
+        assert_eq!(invalidation, None);
+        assert_eq!(state.commits.len(), 1);
+    }
+
+    #[test]
+    fn load_discards_unparseable_state() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let (_, invalidation) = HistoryState::load(&path, "key");
+        assert_eq!(invalidation, Some(Invalidation::Unreadable));
+    }
+
+    #[test]
+    fn scan_key_changes_with_options() {
+        let config = Config::default();
+        let base = scan_key(&[], &config, None, &[], &[], None);
+
+        assert_eq!(base, scan_key(&[], &config, None, &[], &[], None));
+        assert_ne!(base, scan_key(&[], &config, Some(Severity::High), &[], &[], None));
+        assert_ne!(base, scan_key(&[], &config, None, &["vendor/**".into()], &[], None));
+        assert_ne!(base, scan_key(&[], &config, None, &[], &["src".into()], None));
+        assert_ne!(base, scan_key(&[], &config, None, &[], &[], Some(1024)));
+    }
+
+    #[test]
+    fn scan_key_tracks_only_detection_settings() {
+        let config = Config::default();
+        let base = scan_key(&[], &config, None, &[], &[], None);
+        let key = |config: &Config| scan_key(&[], config, None, &[], &[], None);
+
+        let mut unrelated = config.clone();
+        unrelated.baseline_path = Some("other-baseline.json".into());
+        unrelated.fix.insert("vcs/github-pat".into(), FixPolicy::Redact);
+        assert_eq!(base, key(&unrelated));
+
+        let mut disabled = config.clone();
+        disabled.disabled_patterns.push("vcs/github-pat".into());
+        assert_ne!(base, key(&disabled));
+
+        let mut confidence = config;
+        confidence.minimum_confidence = Confidence::Low;
+        assert_ne!(base, key(&confidence));
+    }
+}
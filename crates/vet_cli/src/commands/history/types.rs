@@ -1,9 +1,15 @@
 //! Types for history scanning.
 
+use std::fmt;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::git::CommitInfo;
+use vet_core::ast::FileKind;
+use vet_core::prelude::*;
+use vet_providers::VerificationResult;
+
+use crate::HistoryInclude;
+use crate::git::{CommitInfo, LocalRepo, ObjectId};
 
 /// Options controlling which commits to include in a history scan.
 #[derive(Debug, Clone)]
@@ -16,12 +22,41 @@ pub struct HistoryOptions {
     pub until: String,
     /// Branch to scan.
     pub branch: Option<String>,
+    /// Revision range to scan (`A..B`, `A...B` or a single ref).
+    pub range: Option<String>,
+    /// Regular expression commit authors must match.
+    pub author: Option<String>,
+    /// Regular expression committers must match.
+    pub committer: Option<String>,
     /// Follow only the first parent of merge commits.
     pub first_parent: bool,
+    /// Extra sources of commits to walk.
+    pub include: Vec<HistoryInclude>,
     /// Scan all branches and refs.
     pub all: bool,
 }
 
+/// A blob together with how the scanner treats the path it appears under.
+///
+/// The same content can yield different generic findings as `config.py` and
+/// as `config.txt`, so each blob is scanned once per kind of path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlobKey {
+    /// The blob's object ID.
+    pub blob: ObjectId,
+    /// How the scanner treats the blob's path.
+    pub kind: FileKind,
+}
+
+impl fmt::Display for BlobKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            FileKind::Other => write!(f, "{}", self.blob),
+            kind => write!(f, "{}:{kind:?}", self.blob),
+        }
+    }
+}
+
 /// A single occurrence of a secret in a specific commit.
 #[derive(Debug, Clone)]
 pub struct SecretOccurrence {
@@ -29,6 +64,11 @@ pub struct SecretOccurrence {
     pub commit: Arc<CommitInfo>,
     /// File path within the commit tree.
     pub path: PathBuf,
+    /// Blob the secret was found in.
+    pub blob_id: ObjectId,
+    /// The stash entry, tag, remote branch, reflog entry or `unreachable`
+    /// the commit was reached through, or `None` if it is on the branch walk.
+    pub found_via: Option<Arc<str>>,
     /// One-based line number.
     pub line: u32,
     /// One-based column number.
@@ -48,6 +88,68 @@ pub struct HistoryFinding {
     pub occurrences: Vec<SecretOccurrence>,
     /// Total number of occurrences across all commits.
     pub occurrence_count: usize,
+    /// When the secret was added to and removed from each path it appeared under.
+    pub lifetimes: Vec<PathLifetime>,
+    /// Whether the secret is still in the `HEAD` tree.
+    pub present_at_head: bool,
+    /// Branches and tags (full ref names) whose history contains the secret.
+    pub reachable_from: Vec<String>,
+    /// Result of checking the secret against its provider, with `--verify`.
+    pub verification: Option<VerificationResult>,
+}
+
+impl HistoryFinding {
+    /// Reads the raw secret back from the blob it was introduced in, checking
+    /// that it still matches the finding.
+    #[must_use]
+    pub fn raw_secret(&self, repo: &LocalRepo) -> Option<String> {
+        let content = repo.read_blob_as_text(self.introduced_in.blob_id, None)?;
+        let text = content.get(self.finding.span.byte_start..self.finding.span.byte_end)?;
+        (Secret::new(text).fingerprint() == self.finding.secret.fingerprint()).then(|| text.to_string())
+    }
+
+    /// Returns the commit that removed the secret from the last path it was
+    /// in, or `None` if it has not been removed everywhere.
+    #[must_use]
+    pub fn removed_in(&self) -> Option<&CommitInfo> {
+        if self.present_at_head {
+            return None;
+        }
+
+        let mut latest: Option<&CommitInfo> = None;
+        for lifetime in &self.lifetimes {
+            let removed = lifetime.removed_in.as_deref()?;
+            if latest.is_none_or(|l| removed.date > l.date) {
+                latest = Some(removed);
+            }
+        }
+        latest
+    }
+
+    /// Describes where the secret stands now: `present at HEAD`,
+    /// `removed in <commit>`, or `not at HEAD` when it only lives on other refs.
+    #[must_use]
+    pub fn status(&self) -> String {
+        if self.present_at_head {
+            "present at HEAD".to_string()
+        } else if let Some(commit) = self.removed_in() {
+            format!("removed in {}", commit.short_hash)
+        } else {
+            "not at HEAD".to_string()
+        }
+    }
+}
+
+/// When a secret appeared in and disappeared from a single path.
+#[derive(Debug, Clone)]
+pub struct PathLifetime {
+    /// File path within the commit tree.
+    pub path: PathBuf,
+    /// The earliest commit that added the secret to this path.
+    pub introduced_in: Arc<CommitInfo>,
+    /// The commit that removed the secret from this path, or `None` if it is
+    /// still there at the end of the scanned history.
+    pub removed_in: Option<Arc<CommitInfo>>,
 }
 
 /// Results from a complete history scan.
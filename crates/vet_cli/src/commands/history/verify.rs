@@ -0,0 +1,73 @@
+//! Live verification of secrets found in git history.
+//!
+//! A secret removed years ago is only an incident if it still works, so
+//! `vet history --verify` reads each deduplicated secret back from the blob
+//! it was introduced in and checks it against its provider.
+
+use vet_providers::{ProviderRegistry, VerificationResult};
+
+use super::HistoryFinding;
+use crate::git::Repo;
+use crate::ui::{colors, pluralise_word, print_verification_progress};
+
+/// Verifies each finding whose pattern has a verifier, storing the result
+/// on the finding.
+pub fn verify_findings(repo: &Repo, findings: &mut [HistoryFinding], show_progress: bool) -> anyhow::Result<()> {
+    if findings.is_empty() {
+        return Ok(());
+    }
+
+    let registry =
+        ProviderRegistry::with_verification().map_err(|e| anyhow::anyhow!("failed to initialize verifier: {e}"))?;
+    let total = findings.len();
+
+    if show_progress {
+        println!(
+            "{} verifying {} {} against live services...",
+            colors::info().apply_to("ℹ"),
+            total,
+            pluralise_word(total, "secret", "secrets")
+        );
+    }
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| anyhow::anyhow!("failed to create async runtime: {e}"))?;
+
+    let local = repo.thread_local();
+
+    rt.block_on(async {
+        for (idx, finding) in findings.iter_mut().enumerate() {
+            let pattern_id = &finding.finding.pattern_id;
+
+            let secret = registry
+                .supports_verification(pattern_id)
+                .then(|| finding.raw_secret(&local))
+                .flatten();
+
+            let Some(secret) = secret else {
+                if show_progress {
+                    print_verification_progress(idx + 1, total, pattern_id, None);
+                }
+                continue;
+            };
+
+            let result = match registry.verify(&secret, pattern_id).await {
+                Ok(result) => result,
+                Err(e) => VerificationResult::inconclusive(&e.to_string()),
+            };
+
+            if show_progress {
+                print_verification_progress(idx + 1, total, pattern_id, Some(&result.status));
+            }
+            finding.verification = Some(result);
+        }
+    });
+
+    if show_progress {
+        println!();
+    }
+
+    Ok(())
+}
@@ -1,21 +1,30 @@
-//! Hook command - installs and manages git pre-commit hooks.
+//! Hook command - installs and manages git pre-commit hooks and the
+//! baseline merge driver.
 
 use std::path::Path;
 
 use anyhow::Context;
+use vet_core::prelude::*;
 
 use super::init::templates::{PRECOMMIT_HOOK_PATH, VET_HOOK_MARKER, install_hook};
-use crate::HookCommand;
 use crate::git;
 use crate::ui::{colors, exit, indicators, print_command_header, print_hint, print_info};
+use crate::{CONFIG_FILENAME, HookCommand};
+
+/// Name of the merge driver in git config and `.gitattributes`.
+const MERGE_DRIVER_NAME: &str = "vet-baseline";
+/// Command git runs to merge the baseline (ancestor, ours, theirs).
+const MERGE_DRIVER_COMMAND: &str = "vet baseline merge %O %A %B";
+const GITATTRIBUTES_PATH: &str = ".gitattributes";
+const DEFAULT_BASELINE_PATH: &str = ".vet-baseline.json";
 
 /// Executes the `vet hook` command, showing status or installing/uninstalling
-/// the git pre-commit hook.
+/// the git pre-commit hook, optionally with the baseline merge driver.
 pub fn run(command: Option<&HookCommand>) -> super::Result {
     let hook_path = Path::new(PRECOMMIT_HOOK_PATH);
 
     match command {
-        Some(HookCommand::Install) => install(hook_path),
+        Some(HookCommand::Install { merge_driver }) => install(hook_path, *merge_driver),
         Some(HookCommand::Uninstall) => uninstall(hook_path),
         None => {
             show_status(hook_path);
@@ -58,11 +67,15 @@ fn show_status(hook_path: &Path) {
     }
 }
 
-fn install(hook_path: &Path) -> super::Result {
+fn install(hook_path: &Path, merge_driver: bool) -> super::Result {
     print_command_header("hook install");
 
     verify_git_repository()?;
 
+    if merge_driver {
+        install_merge_driver()?;
+    }
+
     match check_hook_status(hook_path) {
         HookStatus::NotExists => {
             install_hook()?;
@@ -79,6 +92,32 @@ fn install(hook_path: &Path) -> super::Result {
     Ok(())
 }
 
+/// Registers `vet baseline merge` in the local git config and routes the
+/// baseline file to it in `.gitattributes`.
+fn install_merge_driver() -> super::Result {
+    git::register_merge_driver(MERGE_DRIVER_NAME, "vet baseline merge driver", MERGE_DRIVER_COMMAND)?;
+
+    let config = Config::load(Path::new(CONFIG_FILENAME))?;
+    let baseline_path = config.baseline_path.as_deref().unwrap_or(DEFAULT_BASELINE_PATH);
+    let attribute = format!("{} merge={MERGE_DRIVER_NAME}", baseline_path.trim_start_matches("./"));
+
+    let mut attributes = std::fs::read_to_string(GITATTRIBUTES_PATH).unwrap_or_default();
+    if attributes.lines().any(|line| line.trim() == attribute) {
+        print_merge_driver_installed(false);
+        return Ok(());
+    }
+
+    if !attributes.is_empty() && !attributes.ends_with('\n') {
+        attributes.push('\n');
+    }
+    attributes.push_str(&attribute);
+    attributes.push('\n');
+    std::fs::write(GITATTRIBUTES_PATH, attributes).context("writing .gitattributes")?;
+
+    print_merge_driver_installed(true);
+    Ok(())
+}
+
 fn uninstall(hook_path: &Path) -> super::Result {
     print_command_header("hook uninstall");
 
@@ -119,6 +158,22 @@ fn print_created(hook_path: &Path) {
     );
 }
 
+fn print_merge_driver_installed(added_attribute: bool) {
+    println!(
+        "{} {} {}",
+        colors::success().apply_to(indicators::SUCCESS),
+        colors::secondary().apply_to("merge driver registered as"),
+        colors::emphasis().apply_to(MERGE_DRIVER_NAME)
+    );
+    if added_attribute {
+        println!(
+            "{} {}",
+            colors::success().apply_to(indicators::ADDED),
+            colors::emphasis().apply_to(GITATTRIBUTES_PATH)
+        );
+    }
+}
+
 fn print_already_installed() {
     println!(
         "{} {}",
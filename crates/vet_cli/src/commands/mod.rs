@@ -10,10 +10,14 @@ pub mod history;
 pub mod hook;
 /// Project initialisation and `.vet.toml` creation.
 pub mod init;
-/// Pattern listing and inspection.
+/// Pattern listing, inspection and import.
 pub mod patterns;
+/// Running scans across every git repository beneath a directory.
+pub mod repos;
 /// File and directory scanning for secrets.
 pub mod scan;
+/// Continuous rescanning of files as they change on disk.
+pub mod watch;
 
 /// Convenience alias for command return types.
 pub type Result<T = ()> = anyhow::Result<T>;
@@ -0,0 +1,379 @@
+//! Patterns import - converts gitleaks rules into custom patterns.
+//!
+//! Rules are appended to `.vet.toml` as `[[patterns]]` tables rather than
+//! re-serialising the whole file, so existing comments survive. Anything a
+//! custom pattern cannot express is reported instead of silently dropped.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use vet_core::CustomPattern;
+use vet_core::fs_util::atomic_write;
+use vet_core::prelude::*;
+
+use crate::ui::{colors, indicators, pluralise_word};
+use crate::{CONFIG_FILENAME, PatternsImportArgs};
+
+const CUSTOM_PREFIX: &str = "custom/";
+
+/// The parts of a gitleaks config that affect detection.
+#[derive(Deserialize)]
+struct GitleaksConfig {
+    #[serde(default)]
+    rules: Vec<GitleaksRule>,
+    extend: Option<toml::Table>,
+    allowlist: Option<toml::Table>,
+    #[serde(default)]
+    allowlists: Vec<toml::Table>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GitleaksRule {
+    id: String,
+    description: Option<String>,
+    regex: Option<String>,
+    #[serde(default)]
+    secret_group: usize,
+    entropy: Option<f64>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    path: Option<String>,
+    allowlist: Option<toml::Table>,
+    #[serde(default)]
+    allowlists: Vec<toml::Table>,
+}
+
+/// A rule converted into a custom pattern, with what the conversion lost.
+#[derive(Debug)]
+struct Converted {
+    pattern: CustomPattern,
+    caveats: Vec<String>,
+}
+
+/// A rule that could not be converted.
+#[derive(Debug)]
+struct Skipped {
+    id: String,
+    reason: String,
+}
+
+#[derive(Serialize)]
+struct PatternsTable<'a> {
+    patterns: Vec<&'a CustomPattern>,
+}
+
+/// Converts gitleaks rules into custom patterns and appends them to the
+/// config file, or only reports the conversion with `--dry-run`.
+pub fn run(args: &PatternsImportArgs) -> Result<()> {
+    let config_path = args.config.as_deref().unwrap_or_else(|| Path::new(CONFIG_FILENAME));
+    let config = Config::load(config_path)?;
+
+    let content = std::fs::read_to_string(&args.gitleaks)
+        .with_context(|| format!("failed to read {}", args.gitleaks.display()))?;
+    let gitleaks: GitleaksConfig =
+        toml::from_str(&content).with_context(|| format!("failed to parse {}", args.gitleaks.display()))?;
+
+    let mut defined: HashSet<String> = config.patterns.iter().map(|p| p.id.clone()).collect();
+    let mut converted = Vec::new();
+    let mut skipped = Vec::new();
+
+    for rule in gitleaks.rules {
+        let id = format!("{CUSTOM_PREFIX}{}", rule.id);
+        if !defined.insert(id.clone()) {
+            skipped.push(Skipped {
+                id: rule.id,
+                reason: format!("{id} is already defined"),
+            });
+            continue;
+        }
+
+        match convert(rule, id, args.severity) {
+            Ok(pattern) => converted.push(pattern),
+            Err(skip) => skipped.push(skip),
+        }
+    }
+
+    let mut warnings = Vec::new();
+    if gitleaks.extend.is_some() {
+        warnings.push("[extend] is not followed; import the extended config separately");
+    }
+    if gitleaks.allowlist.is_some() || !gitleaks.allowlists.is_empty() {
+        warnings.push("global allowlist dropped; use exclude_paths or [[ignore]] in .vet.toml instead");
+    }
+
+    if !args.dry_run && !converted.is_empty() {
+        append_patterns(config_path, &converted)?;
+    }
+
+    print_report(args, config_path, &converted, &skipped, &warnings);
+    Ok(())
+}
+
+/// Converts one rule, noting every part of it a custom pattern cannot
+/// express. Rules whose matches would change entirely are skipped.
+fn convert(rule: GitleaksRule, id: String, severity: Severity) -> Result<Converted, Skipped> {
+    let skip = |reason: String| Skipped {
+        id: rule.id.clone(),
+        reason,
+    };
+
+    let Some(regex) = rule.regex.clone().filter(|r| !r.is_empty()) else {
+        return Err(skip("path-only rules match file names, not content".to_string()));
+    };
+
+    let pattern = CustomPattern {
+        id,
+        name: rule.description.clone().unwrap_or_else(|| rule.id.clone()),
+        regex,
+        severity,
+        description: None,
+        keywords: rule.keywords,
+        min_entropy: rule.entropy,
+    };
+
+    let compiled = pattern
+        .compile()
+        .map_err(|e| skip(format!("regex is not supported: {}", root_cause(&e))))?;
+
+    let mut caveats = Vec::new();
+    if compiled.regex.captures_len() > 1 {
+        caveats.push(match rule.secret_group {
+            0 => "reports the whole match instead of the first capture group".to_string(),
+            group => format!("reports the whole match instead of capture group {group}"),
+        });
+    }
+    if let Some(path) = rule.path {
+        caveats.push(format!(
+            "path filter `{path}` dropped; matches are reported in every file"
+        ));
+    }
+    let allowlists = usize::from(rule.allowlist.is_some()) + rule.allowlists.len();
+    if allowlists > 0 {
+        caveats.push(format!(
+            "{allowlists} {} dropped",
+            pluralise_word(allowlists, "allowlist", "allowlists")
+        ));
+    }
+
+    Ok(Converted { pattern, caveats })
+}
+
+fn root_cause(error: &dyn std::error::Error) -> String {
+    let mut cause = error;
+    while let Some(source) = cause.source() {
+        cause = source;
+    }
+    cause.to_string().lines().last().unwrap_or_default().trim().to_string()
+}
+
+/// Appends the patterns to the config file as `[[patterns]]` tables,
+/// checking the result still loads before writing it.
+fn append_patterns(config_path: &Path, converted: &[Converted]) -> Result<()> {
+    let existing = if config_path.exists() {
+        std::fs::read_to_string(config_path).with_context(|| format!("failed to read {}", config_path.display()))?
+    } else {
+        String::new()
+    };
+
+    let table = PatternsTable {
+        patterns: converted.iter().map(|c| &c.pattern).collect(),
+    };
+    let appended = toml::to_string(&table).context("failed to serialise imported patterns")?;
+
+    let mut content = existing;
+    if !content.is_empty() {
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push('\n');
+    }
+    content.push_str(&appended);
+
+    Config::from_toml(&content)
+        .with_context(|| format!("imported patterns would leave {} invalid", config_path.display()))?;
+
+    atomic_write(config_path, &content).with_context(|| format!("failed to write {}", config_path.display()))
+}
+
+fn print_report(
+    args: &PatternsImportArgs,
+    config_path: &Path,
+    converted: &[Converted],
+    skipped: &[Skipped],
+    warnings: &[&str],
+) {
+    let count = converted.len();
+    println!(
+        "{} {} {} {} from {}",
+        colors::success().apply_to(indicators::SUCCESS),
+        if args.dry_run { "would import" } else { "imported" },
+        count,
+        pluralise_word(count, "pattern", "patterns"),
+        args.gitleaks.display()
+    );
+
+    let lossy: Vec<&Converted> = converted.iter().filter(|c| !c.caveats.is_empty()).collect();
+    if !lossy.is_empty() {
+        println!();
+        println!(
+            "{} {} {} not represented exactly",
+            colors::warning().apply_to(indicators::WARNING),
+            lossy.len(),
+            pluralise_word(lossy.len(), "pattern is", "patterns are")
+        );
+        for c in lossy {
+            for caveat in &c.caveats {
+                print_item(&c.pattern.id, caveat);
+            }
+        }
+    }
+
+    if !skipped.is_empty() {
+        println!();
+        println!(
+            "{} {} {} skipped",
+            colors::warning().apply_to(indicators::WARNING),
+            skipped.len(),
+            pluralise_word(skipped.len(), "rule", "rules")
+        );
+        for s in skipped {
+            print_item(&s.id, &s.reason);
+        }
+    }
+
+    for warning in warnings {
+        println!();
+        println!("{} {}", colors::warning().apply_to(indicators::WARNING), warning);
+    }
+
+    println!();
+    if args.dry_run {
+        println!(
+            "{} dry run, {} not modified",
+            colors::info().apply_to(indicators::INFO),
+            config_path.display()
+        );
+    } else if count > 0 {
+        println!(
+            "{} added to {}",
+            colors::success().apply_to(indicators::SUCCESS),
+            config_path.display()
+        );
+    }
+}
+
+fn print_item(id: &str, detail: &str) {
+    println!(
+        "  {} {} {}",
+        colors::muted().apply_to("-"),
+        colors::accent().apply_to(id),
+        colors::secondary().apply_to(detail)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> GitleaksConfig {
+        toml::from_str(content).unwrap()
+    }
+
+    fn convert_one(content: &str) -> Result<Converted, Skipped> {
+        let rule = parse(content).rules.remove(0);
+        let id = format!("{CUSTOM_PREFIX}{}", rule.id);
+        convert(rule, id, Severity::High)
+    }
+
+    #[test]
+    fn converts_plain_rule_without_caveats() {
+        let converted = convert_one(
+            r#"
+            [[rules]]
+            id = "acme-token"
+            description = "Acme API token"
+            regex = '''acme_[a-z0-9]{32}'''
+            keywords = ["acme_"]
+            entropy = 3.5
+            "#,
+        )
+        .unwrap();
+
+        assert!(converted.caveats.is_empty());
+        assert_eq!(converted.pattern.id, "custom/acme-token");
+        assert_eq!(converted.pattern.name, "Acme API token");
+        assert_eq!(converted.pattern.keywords, ["acme_"]);
+        assert_eq!(converted.pattern.min_entropy, Some(3.5));
+    }
+
+    #[test]
+    fn notes_capture_groups_path_and_allowlists() {
+        let converted = convert_one(
+            r#"
+            [[rules]]
+            id = "acme-token"
+            regex = '''token\s*=\s*(acme_[a-z0-9]{32})'''
+            secretGroup = 1
+            path = '''\.env$'''
+            [[rules.allowlists]]
+            stopwords = ["example"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(converted.pattern.name, "acme-token");
+        assert_eq!(converted.caveats.len(), 3);
+        assert!(converted.caveats[0].contains("capture group 1"));
+        assert!(converted.caveats[1].contains(r"\.env$"));
+        assert_eq!(converted.caveats[2], "1 allowlist dropped");
+    }
+
+    #[test]
+    fn skips_path_only_rules() {
+        let skipped = convert_one(
+            r#"
+            [[rules]]
+            id = "pkcs12-file"
+            path = '''\.p12$'''
+            "#,
+        )
+        .unwrap_err();
+
+        assert_eq!(skipped.id, "pkcs12-file");
+        assert!(skipped.reason.contains("path-only"));
+    }
+
+    #[test]
+    fn skips_rules_with_unsupported_regex() {
+        let skipped = convert_one(
+            r#"
+            [[rules]]
+            id = "lookahead"
+            regex = '''key(?=[0-9])'''
+            "#,
+        )
+        .unwrap_err();
+
+        assert!(skipped.reason.starts_with("regex is not supported"));
+    }
+
+    #[test]
+    fn reads_global_extend_and_allowlist() {
+        let config = parse(
+            r"
+            [extend]
+            useDefault = true
+
+            [allowlist]
+            paths = ['''vendor/''']
+            ",
+        );
+
+        assert!(config.rules.is_empty());
+        assert!(config.extend.is_some());
+        assert!(config.allowlist.is_some());
+    }
+}
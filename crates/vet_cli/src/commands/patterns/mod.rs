@@ -1,19 +1,31 @@
 //! Patterns command - lists available detection patterns.
 
+mod import;
+
 use std::collections::HashMap;
 
 use console::style;
 use vet_core::prelude::*;
 
 use crate::ui::{colors, indicators, print_command_header, severity_indicator, severity_style, truncate_with_ellipsis};
+use crate::{PatternsArgs, PatternsSubcommand};
 
 const NAME_TRUNCATE_WIDTH: usize = 35;
 const DESCRIPTION_WIDTH: usize = 60;
 
 const SEVERITY_ORDER: [Severity; 4] = [Severity::Critical, Severity::High, Severity::Medium, Severity::Low];
 
+/// Executes the `vet patterns` command, dispatching to a subcommand or
+/// listing patterns.
+pub fn run(args: &PatternsArgs) -> super::Result {
+    match &args.command {
+        Some(PatternsSubcommand::Import(import_args)) => import::run(import_args),
+        None => list(args.group.as_deref(), args.severity.as_deref(), args.verbose),
+    }
+}
+
 /// Lists built-in detection patterns, optionally filtered by group or severity.
-pub fn run(group_filter: Option<&str>, severity_filter: Option<&str>, verbose: bool) -> super::Result {
+fn list(group_filter: Option<&str>, severity_filter: Option<&str>, verbose: bool) -> super::Result {
     print_command_header("patterns");
 
     let registry = PatternRegistry::builtin()?;
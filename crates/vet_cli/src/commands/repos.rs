@@ -0,0 +1,461 @@
+//! Multi-repository scans for `vet scan --repos` and `vet history --repos`.
+//!
+//! Every git repository beneath the given directory is scanned by running
+//! vet again from inside it, so each one is scanned with its own `.vet.toml`
+//! and baseline. The runs are combined into one report that records which
+//! repository each finding came from and how each run ended.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::Context as _;
+use clap::CommandFactory;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::git::find_repositories;
+use crate::ui::{colors, exit, indicators, pluralise_word};
+use crate::{HistoryArgs, OutputFormat, ScanArgs};
+
+/// IDs of the options removed from the arguments passed to each
+/// per-repository run. The combined report replaces all of them.
+const PARENT_OPTIONS: [&str; 3] = ["repos", "output", "format"];
+
+/// The command being run across repositories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoCommand {
+    /// `vet scan`, which needs a working tree.
+    Scan,
+    /// `vet history`, which also works on bare repositories.
+    History,
+}
+
+impl RepoCommand {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Scan => "scan",
+            Self::History => "history",
+        }
+    }
+
+    /// The command's clap definition, used to tell options from their values.
+    fn definition(self) -> clap::Command {
+        match self {
+            Self::Scan => ScanArgs::command(),
+            Self::History => HistoryArgs::command(),
+        }
+    }
+}
+
+/// How a repository's run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RepoStatus {
+    Clean,
+    Findings,
+    Error,
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+struct RepoOutcome {
+    repository: String,
+    bare: bool,
+    status: RepoStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    /// The run's full report when it has no findings list of its own, such
+    /// as `vet history --report summary`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    report: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct JsonReport {
+    version: &'static str,
+    scan_type: &'static str,
+    repositories: Vec<RepoOutcome>,
+    findings: Vec<Value>,
+}
+
+/// Runs `command` in every git repository beneath `root` and writes the
+/// combined report.
+pub fn run(command: RepoCommand, root: &Path, format: OutputFormat, output: Option<&Path>) -> super::Result {
+    if !matches!(format, OutputFormat::Text | OutputFormat::Json) {
+        anyhow::bail!("--repos only supports text and json output");
+    }
+
+    let repos = find_repositories(root).with_context(|| format!("failed to read {}", root.display()))?;
+    if repos.is_empty() {
+        anyhow::bail!("no git repositories found beneath {}", root.display());
+    }
+
+    let exe = std::env::current_exe().context("failed to locate the vet executable")?;
+    let json = format == OutputFormat::Json;
+    let args = child_args(&command.definition(), std::env::args_os().skip(1), json);
+    let capture = json || output.is_some();
+
+    let mut text = Vec::new();
+    let mut outcomes = Vec::new();
+    let mut findings = Vec::new();
+
+    for repo in &repos {
+        let name = repository_name(root, &repo.path);
+
+        if !capture {
+            print_repo_header(&name, repo.bare);
+        }
+
+        let mut outcome = RepoOutcome {
+            repository: name,
+            bare: repo.bare,
+            status: RepoStatus::Skipped,
+            exit_code: None,
+            message: None,
+            report: None,
+        };
+
+        if repo.bare && command == RepoCommand::Scan {
+            outcome.message = Some("bare repository has no working tree".to_string());
+            outcomes.push(outcome);
+            continue;
+        }
+
+        let mut child = Command::new(&exe);
+        child.args(&args).current_dir(&repo.path);
+
+        if capture {
+            let result = child
+                .stdin(Stdio::null())
+                .output()
+                .with_context(|| format!("failed to run vet in {}", repo.path.display()))?;
+            record_exit(&mut outcome, result.status.code(), &result.stderr);
+
+            if json {
+                collect_json(&mut outcome, &result.stdout, &mut findings);
+            } else {
+                writeln!(text, "==> {} <==", outcome.repository)?;
+                text.extend_from_slice(&result.stdout);
+            }
+        } else {
+            let status = child
+                .status()
+                .with_context(|| format!("failed to run vet in {}", repo.path.display()))?;
+            record_exit(&mut outcome, status.code(), &[]);
+        }
+
+        outcomes.push(outcome);
+    }
+
+    let exit_code = combined_exit_code(&outcomes);
+
+    if json {
+        let report = JsonReport {
+            version: "1.0",
+            scan_type: command.name(),
+            repositories: outcomes,
+            findings,
+        };
+        let mut rendered = serde_json::to_vec_pretty(&report)?;
+        rendered.push(b'\n');
+        write_report(output, &rendered)?;
+    } else {
+        write_status_table(&outcomes, &mut text)?;
+        write_report(output, &text)?;
+    }
+
+    if exit_code != exit::SUCCESS {
+        std::process::exit(exit_code);
+    }
+
+    Ok(())
+}
+
+/// Returns the arguments for a per-repository run: the original arguments
+/// without `--repos`, `--output` and `--format`, asking for JSON when the
+/// results are combined.
+///
+/// The first argument is the subcommand name. The rest are read against
+/// `definition` the way clap reads them, so attached values (`-ojson`,
+/// `--format=json`) and clustered short flags (`-vf json`) are handled.
+fn child_args(definition: &clap::Command, args: impl IntoIterator<Item = OsString>, json: bool) -> Vec<OsString> {
+    let mut args = args.into_iter();
+    let mut kept: Vec<OsString> = args.next().into_iter().collect();
+
+    while let Some(arg) = args.next() {
+        let Some(text) = arg.to_str() else {
+            kept.push(arg);
+            continue;
+        };
+
+        if text == "--" {
+            kept.push(arg);
+            kept.extend(args.by_ref());
+        } else if let Some(long) = text.strip_prefix("--") {
+            let (name, attached) = long.split_once('=').map_or((long, false), |(name, _)| (name, true));
+            let option = definition.get_arguments().find(|a| a.get_long() == Some(name));
+            let value = if !attached && option.is_some_and(takes_value) {
+                args.next()
+            } else {
+                None
+            };
+            if !option.is_some_and(is_parent_option) {
+                kept.push(arg);
+                kept.extend(value);
+            }
+        } else if let Some(cluster) = text.strip_prefix('-').filter(|c| !c.is_empty()) {
+            kept.extend(split_short_cluster(definition, cluster, &mut args));
+        } else {
+            kept.push(arg);
+        }
+    }
+
+    if json {
+        kept.push("--format=json".into());
+    }
+    kept
+}
+
+/// Splits a cluster of short options (the text after `-`) into separate
+/// arguments, dropping parent options. A value-taking option ends the
+/// cluster: the rest of it, or else the next argument, is its value.
+fn split_short_cluster(
+    definition: &clap::Command,
+    cluster: &str,
+    args: &mut impl Iterator<Item = OsString>,
+) -> Vec<OsString> {
+    let mut kept = Vec::new();
+
+    for (index, short) in cluster.char_indices() {
+        let option = definition.get_arguments().find(|a| a.get_short() == Some(short));
+        let parent = option.is_some_and(is_parent_option);
+
+        if option.is_some_and(takes_value) {
+            let rest = &cluster[index + short.len_utf8()..];
+            let value = if rest.is_empty() {
+                args.next()
+            } else {
+                Some(OsString::from(rest.strip_prefix('=').unwrap_or(rest)))
+            };
+            if !parent {
+                kept.push(format!("-{short}").into());
+                kept.extend(value);
+            }
+            break;
+        }
+
+        if !parent {
+            kept.push(format!("-{short}").into());
+        }
+    }
+
+    kept
+}
+
+fn takes_value(option: &clap::Arg) -> bool {
+    option.get_action().takes_values()
+}
+
+fn is_parent_option(option: &clap::Arg) -> bool {
+    PARENT_OPTIONS.contains(&option.get_id().as_str())
+}
+
+/// Names a repository by its path relative to the searched directory.
+fn repository_name(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    if relative.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn record_exit(outcome: &mut RepoOutcome, code: Option<i32>, stderr: &[u8]) {
+    outcome.exit_code = code;
+    outcome.status = match code {
+        Some(exit::SUCCESS) => RepoStatus::Clean,
+        Some(exit::FINDINGS) => RepoStatus::Findings,
+        _ => RepoStatus::Error,
+    };
+
+    if outcome.status == RepoStatus::Error {
+        outcome.message = String::from_utf8_lossy(stderr)
+            .lines()
+            .rev()
+            .map(|line| line.trim_start_matches(indicators::ERROR).trim())
+            .find(|line| !line.is_empty())
+            .map(str::to_string);
+    }
+}
+
+/// Adds the run's findings to the combined list, each tagged with its
+/// repository, or keeps the whole report if it has no findings list.
+fn collect_json(outcome: &mut RepoOutcome, stdout: &[u8], findings: &mut Vec<Value>) {
+    let Ok(report) = serde_json::from_slice::<Value>(stdout) else {
+        return;
+    };
+
+    let repo_findings = match report {
+        Value::Array(items) => items,
+        Value::Object(mut map) => {
+            if let Some(Value::Array(items)) = map.remove("findings") {
+                items
+            } else {
+                outcome.report = Some(Value::Object(map));
+                return;
+            }
+        }
+        _ => return,
+    };
+
+    findings.extend(repo_findings.into_iter().map(|mut finding| {
+        if let Value::Object(map) = &mut finding {
+            map.insert("repository".to_string(), Value::String(outcome.repository.clone()));
+        }
+        finding
+    }));
+}
+
+/// Fails if any repository failed, otherwise reports findings if any had them.
+fn combined_exit_code(outcomes: &[RepoOutcome]) -> i32 {
+    if outcomes.iter().any(|o| o.status == RepoStatus::Error) {
+        exit::ERROR
+    } else if outcomes.iter().any(|o| o.status == RepoStatus::Findings) {
+        exit::FINDINGS
+    } else {
+        exit::SUCCESS
+    }
+}
+
+fn write_report(output: Option<&Path>, content: &[u8]) -> anyhow::Result<()> {
+    if let Some(path) = output {
+        let file = File::create(path).with_context(|| format!("failed to create output file: {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(content)?;
+        writer.flush()?;
+    } else {
+        std::io::stdout().lock().write_all(content)?;
+    }
+    Ok(())
+}
+
+fn print_repo_header(name: &str, bare: bool) {
+    println!();
+    println!(
+        "{} {}{}",
+        colors::accent().bold().apply_to("==>"),
+        colors::primary().apply_to(name),
+        if bare {
+            format!(" {}", colors::muted().apply_to("(bare)"))
+        } else {
+            String::new()
+        }
+    );
+}
+
+fn write_status_table(outcomes: &[RepoOutcome], writer: &mut dyn Write) -> anyhow::Result<()> {
+    let width = outcomes.iter().map(|o| o.repository.chars().count()).max().unwrap_or(0);
+    let count = outcomes.len();
+
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{} {}",
+        colors::primary().apply_to(format!(
+            "{count} {}",
+            pluralise_word(count, "repository", "repositories")
+        )),
+        colors::muted().apply_to("scanned"),
+    )?;
+
+    for outcome in outcomes {
+        let (indicator, status) = match outcome.status {
+            RepoStatus::Clean => (colors::success().apply_to(indicators::SUCCESS), "clean"),
+            RepoStatus::Findings => (colors::error().apply_to(indicators::ERROR), "secrets found"),
+            RepoStatus::Error => (colors::warning().apply_to(indicators::WARNING), "failed"),
+            RepoStatus::Skipped => (colors::muted().apply_to("○"), "skipped"),
+        };
+        let detail = outcome
+            .message
+            .as_deref()
+            .map(|m| format!(" {}", colors::muted().apply_to(format!("({m})"))))
+            .unwrap_or_default();
+
+        writeln!(
+            writer,
+            "  {indicator} {}  {}{detail}",
+            colors::secondary().apply_to(format!("{:<width$}", outcome.repository)),
+            status,
+        )?;
+    }
+
+    writeln!(writer)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<OsString> {
+        list.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn child_args_drop_parent_options() {
+        let scan = RepoCommand::Scan.definition();
+        let input = args(&[
+            "scan",
+            "--repos",
+            "/src",
+            "-o",
+            "out.json",
+            "--format=json",
+            "-s",
+            "high",
+        ]);
+        assert_eq!(
+            child_args(&scan, input, true),
+            args(&["scan", "-s", "high", "--format=json"])
+        );
+
+        let history = RepoCommand::History.definition();
+        let input = args(&["history", "--repos=/src", "-fjson", "--first-parent"]);
+        assert_eq!(child_args(&history, input, false), args(&["history", "--first-parent"]));
+    }
+
+    #[test]
+    fn child_args_split_clustered_short_options() {
+        let scan = RepoCommand::Scan.definition();
+
+        let input = args(&["scan", "--repos", "/src", "-vvf", "json", "-shigh", "-vo", "out.json"]);
+        assert_eq!(
+            child_args(&scan, input, false),
+            args(&["scan", "-v", "-v", "-s", "high", "-v"])
+        );
+
+        let input = args(&["scan", "--repos", "/src", "-e", "-o.txt", "--", "-f"]);
+        assert_eq!(
+            child_args(&scan, input, false),
+            args(&["scan", "-e", "-o.txt", "--", "-f"])
+        );
+    }
+
+    #[test]
+    fn repository_name_is_relative_to_root() {
+        let root = Path::new("/src");
+        assert_eq!(repository_name(root, Path::new("/src")), ".");
+        assert_eq!(repository_name(root, &PathBuf::from("/src/team/api")), "team/api");
+    }
+}
@@ -4,21 +4,29 @@ mod context;
 mod output;
 mod runner;
 
+use std::collections::HashSet;
 use std::path::Path;
 use std::time::Instant;
 
 use vet_core::prelude::*;
-use vet_providers::{ProviderRegistry, VerificationResult, VerificationStatus};
+use vet_providers::{ProviderRegistry, VerificationResult};
 
 use self::context::{ScanContext, VerboseInfo};
-use self::output::{OutputContext, ScanStats, VerificationMap, write_output};
+use self::output::{ExpiredMap, OutputContext, ScanStats, VerificationMap, write_output};
 use self::runner::{ContentCache, collect_scan_files, run_scan};
+use super::baseline::relocate_moved;
+use super::repos::{self, RepoCommand};
+use crate::files::is_in_scope;
 use crate::scanning::configure_thread_pool;
-use crate::ui::{colors, exit, print_command_header};
+use crate::ui::{colors, exit, indicators, pluralise_word, print_command_header, print_verification_progress};
 use crate::{CONFIG_FILENAME, OutputFormat, ScanArgs};
 
 /// Executes the `vet scan` command.
 pub fn run(args: &ScanArgs) -> super::Result {
+    if let Some(root) = &args.repos {
+        return repos::run(RepoCommand::Scan, root, args.format, args.output.as_deref());
+    }
+
     configure_thread_pool(args.concurrency)?;
 
     let show_progress = should_show_progress(args);
@@ -47,10 +55,20 @@ pub fn run(args: &ScanArgs) -> super::Result {
 
     let all_findings_count = scan_result.findings.len();
 
-    let (findings_after_baseline, baseline_count) =
-        filter_by_baseline(scan_result.findings, args.baseline.as_deref(), &context.config)?;
+    let BaselineFilter {
+        reported,
+        baselined,
+        expired,
+        stale,
+    } = filter_by_baseline(scan_result.findings, args.baseline.as_deref(), &context.config)?;
+    let baseline_count = baselined.as_ref().map_or(0, Vec::len);
+
+    // A staged-only run sees too few files to judge any entry stale.
+    if args.report_stale_baseline && !args.staged {
+        print_stale_baseline(&stale_in_scope(stale, args, &context.config));
+    }
 
-    let findings = filter_by_confidence(findings_after_baseline, minimum_confidence);
+    let findings = filter_by_confidence(reported, minimum_confidence);
     let filtered_count = all_findings_count - findings.len() - baseline_count;
 
     let verifications = if args.verify {
@@ -75,11 +93,16 @@ pub fn run(args: &ScanArgs) -> super::Result {
         content_cache: &scan_result.content_cache,
         stats,
         verifications: verifications.as_ref(),
+        baselined: baselined.as_deref(),
+        expired: &expired,
+        exit_code: exit_code(args, &findings),
     };
 
     write_output(args, &ctx)?;
 
-    handle_exit_code(args, &findings);
+    if ctx.exit_code != exit::SUCCESS {
+        std::process::exit(ctx.exit_code);
+    }
 
     Ok(())
 }
@@ -108,19 +131,41 @@ fn filter_by_confidence(findings: Vec<Finding>, minimum: Confidence) -> Vec<Find
     findings.into_iter().filter(|f| f.confidence >= minimum).collect()
 }
 
+/// Findings split by the baseline and config ignores.
+struct BaselineFilter {
+    /// Findings still reported, including expired acceptances.
+    reported: Vec<Finding>,
+    /// Findings suppressed by the baseline or config ignores, or `None`
+    /// when neither is in use.
+    baselined: Option<Vec<Finding>>,
+    /// Reported findings whose baseline acceptance has expired.
+    expired: ExpiredMap,
+    /// Baseline entries that matched none of the findings.
+    stale: Vec<BaselineFinding>,
+}
+
+/// Splits findings into those still reported and those suppressed by the
+/// baseline or config ignores, noting which reported findings were
+/// accepted in the baseline until an expiry that has passed.
 fn filter_by_baseline(
     findings: Vec<vet_core::Finding>,
     baseline_path: Option<&Path>,
     config: &Config,
-) -> super::Result<(Vec<vet_core::Finding>, usize)> {
+) -> super::Result<BaselineFilter> {
     let explicit_baseline = baseline_path;
     let baseline_path = baseline_path.or_else(|| config.baseline_path.as_deref().map(Path::new));
+    let unfiltered = |findings| BaselineFilter {
+        reported: findings,
+        baselined: None,
+        expired: ExpiredMap::new(),
+        stale: Vec::new(),
+    };
 
-    let baseline = match baseline_path {
+    let mut baseline = match baseline_path {
         None => {
             // No baseline configured, but still apply config ignores
             if config.ignores.is_empty() {
-                return Ok((findings, 0));
+                return Ok(unfiltered(findings));
             }
             None
         }
@@ -134,38 +179,108 @@ fn filter_by_baseline(
                 }
                 // No baseline file, but still apply config ignores
                 if config.ignores.is_empty() {
-                    return Ok((findings, 0));
+                    return Ok(unfiltered(findings));
                 }
                 None
             }
         }
     };
 
+    if let Some(baseline) = baseline.as_mut().filter(|_| config.baseline_follow_renames) {
+        relocate_moved(baseline, &findings);
+    }
+
     let matcher = IgnoreMatcher::new(baseline.as_ref(), &config.ignores);
 
-    let mut filtered_findings = Vec::new();
-    let mut baseline_count = 0;
+    let seen: HashSet<Fingerprint> = findings.iter().map(Finding::baseline_fingerprint).collect();
+    let stale = baseline
+        .as_ref()
+        .map(|baseline| baseline.stale_findings(&seen).cloned().collect())
+        .unwrap_or_default();
+
+    let (baselined, reported): (Vec<_>, Vec<_>) = findings
+        .into_iter()
+        .partition(|finding| matcher.is_ignored(&finding.baseline_fingerprint()));
+
+    let expired = baseline
+        .map(|baseline| {
+            reported
+                .iter()
+                .filter_map(|finding| {
+                    let fingerprint = finding.baseline_fingerprint();
+                    if !matcher.is_expired(&fingerprint) {
+                        return None;
+                    }
+                    let entry = baseline.get_finding(&fingerprint)?;
+                    Some((finding.id.as_str().to_string(), entry.clone()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(BaselineFilter {
+        reported,
+        baselined: Some(baselined),
+        expired,
+        stale,
+    })
+}
 
-    for finding in findings {
-        if matcher.is_ignored(&finding.baseline_fingerprint()) {
-            baseline_count += 1;
-        } else {
-            filtered_findings.push(finding);
-        }
+/// Keeps the stale entries this scan could have matched: those for files
+/// under the scanned paths, not excluded, and at or above the severity
+/// filter.
+fn stale_in_scope(stale: Vec<BaselineFinding>, args: &ScanArgs, config: &Config) -> Vec<BaselineFinding> {
+    let excludes: Vec<String> = config
+        .exclude_paths
+        .iter()
+        .chain(args.exclude.iter())
+        .cloned()
+        .collect();
+    let severity = args.severity.or(config.severity);
+
+    stale
+        .into_iter()
+        .filter(|f| severity.is_none_or(|threshold| f.severity >= threshold))
+        .filter(|f| is_in_scope(&args.paths, &excludes, Path::new(&f.file)))
+        .collect()
+}
+
+/// Warns on stderr about baseline entries whose secret was not found, so
+/// reports written to stdout or `--output` are unaffected.
+fn print_stale_baseline(stale: &[BaselineFinding]) {
+    if stale.is_empty() {
+        return;
     }
 
-    Ok((filtered_findings, baseline_count))
+    let count = stale.len();
+    eprintln!(
+        "{} {} baseline {} no longer {} anything (remove with `vet baseline prune`)",
+        colors::warning().apply_to(indicators::WARNING),
+        count,
+        pluralise_word(count, "entry", "entries"),
+        pluralise_word(count, "matches", "match"),
+    );
+    for finding in stale {
+        eprintln!(
+            "  {} {} {}",
+            colors::muted().apply_to("-"),
+            finding.pattern_id,
+            colors::muted().apply_to(&finding.file)
+        );
+    }
 }
 
-fn handle_exit_code(args: &ScanArgs, findings: &[vet_core::Finding]) {
+fn exit_code(args: &ScanArgs, findings: &[vet_core::Finding]) -> i32 {
     if args.exit_zero || args.allow_new {
-        return;
+        return exit::SUCCESS;
     }
 
     let high_confidence_count = findings.iter().filter(|f| f.confidence == Confidence::High).count();
 
     if high_confidence_count > 0 {
-        std::process::exit(exit::FINDINGS);
+        exit::FINDINGS
+    } else {
+        exit::SUCCESS
     }
 }
 
@@ -235,31 +350,3 @@ fn run_verification(
 
     Ok(results)
 }
-
-fn print_verification_progress(idx: usize, total: usize, pattern_id: &str, status: Option<&VerificationStatus>) {
-    let counter = format!("[{idx}/{total}]");
-    let prefix = format!("  {counter} ");
-    let prefix_len = prefix.chars().count();
-
-    let pattern_display = pattern_id;
-    let pattern_len = pattern_display.chars().count();
-
-    let result_col: usize = 50;
-    let available_for_dots = result_col.saturating_sub(prefix_len + pattern_len + 1);
-    let dots = " ".to_string() + &"·".repeat(available_for_dots);
-
-    let status_display = match status {
-        Some(VerificationStatus::Live) => format!("{}", colors::error().bold().apply_to("LIVE")),
-        Some(VerificationStatus::Inactive) => format!("{}", colors::success().apply_to("inactive")),
-        Some(VerificationStatus::Inconclusive) => format!("{}", colors::warning().apply_to("inconclusive")),
-        None => format!("{}", colors::muted().apply_to("skipped")),
-    };
-
-    println!(
-        "{}{}{}{}",
-        prefix,
-        colors::secondary().apply_to(pattern_display),
-        colors::muted().apply_to(dots),
-        status_display
-    );
-}
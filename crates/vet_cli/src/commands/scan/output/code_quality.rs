@@ -0,0 +1,30 @@
+//! GitLab Code Quality (Code Climate) formatter for scan findings.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::report_path;
+use crate::report::code_quality::{self, Issue};
+use crate::report::occurrence_id;
+
+/// Serialises scan findings as a GitLab Code Quality report.
+pub fn write(findings: &[Finding], patterns: &[Pattern], writer: &mut dyn Write) -> anyhow::Result<()> {
+    let issues: Vec<Issue> = findings.iter().map(|f| convert_issue(f, patterns)).collect();
+    code_quality::write(&issues, writer)
+}
+
+fn convert_issue(finding: &Finding, patterns: &[Pattern]) -> Issue {
+    let pattern_id = finding.pattern_id.as_ref();
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == pattern_id);
+    let pattern_name = crate::report::pattern_name(pattern_id, pattern);
+
+    Issue::new(
+        format!("{pattern_name} detected"),
+        pattern_id,
+        occurrence_id(&finding.baseline_fingerprint(), finding.span.line, finding.span.column),
+        finding.severity,
+        report_path(&finding.path),
+        finding.span.line,
+    )
+}
@@ -0,0 +1,61 @@
+//! CSV output formatter for scan findings.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::{VerificationMap, report_path};
+use crate::report::write_csv_record;
+
+const HEADER: &[&str] = &[
+    "severity",
+    "pattern_id",
+    "pattern_name",
+    "file",
+    "line",
+    "column",
+    "confidence",
+    "secret_masked",
+    "fingerprint",
+    "verification",
+];
+
+/// Serialises scan findings as CSV, one row per finding.
+pub fn write(
+    findings: &[Finding],
+    patterns: &[Pattern],
+    verifications: Option<&VerificationMap>,
+    writer: &mut dyn Write,
+) -> anyhow::Result<()> {
+    write_csv_record(writer, HEADER)?;
+
+    for finding in findings {
+        let pattern_name = patterns
+            .iter()
+            .find(|p| p.id.as_ref() == finding.pattern_id.as_ref())
+            .map_or(finding.pattern_id.as_ref(), |p| p.name.as_ref());
+
+        let verification = verifications
+            .and_then(|v| v.get(finding.id.as_str()))
+            .map(|v| v.status.to_string())
+            .unwrap_or_default();
+
+        write_csv_record(
+            writer,
+            &[
+                finding.severity.to_string().to_lowercase(),
+                finding.pattern_id.to_string(),
+                pattern_name.to_string(),
+                report_path(&finding.path),
+                finding.span.line.to_string(),
+                finding.span.column.to_string(),
+                finding.confidence.to_string(),
+                finding.secret.as_masked().to_string(),
+                finding.baseline_fingerprint().to_string(),
+                verification,
+            ],
+        )?;
+    }
+
+    Ok(())
+}
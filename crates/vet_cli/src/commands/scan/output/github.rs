@@ -0,0 +1,39 @@
+//! GitHub Actions workflow command output for scan findings.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::{OutputContext, markdown, report_path};
+use crate::report::github::{Annotation, append_step_summary};
+
+/// Writes scan findings as GitHub Actions annotations, and appends a job
+/// summary when running inside Actions.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    for finding in ctx.findings {
+        write_annotation(finding, ctx.patterns, writer)?;
+    }
+
+    append_step_summary(|summary| markdown::write(ctx, summary))
+}
+
+fn write_annotation(finding: &Finding, patterns: &[Pattern], writer: &mut dyn Write) -> anyhow::Result<()> {
+    let pattern_id = finding.pattern_id.as_ref();
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == pattern_id);
+    let pattern_name = crate::report::pattern_name(pattern_id, pattern);
+
+    Annotation {
+        severity: finding.severity,
+        file: &report_path(&finding.path),
+        line: finding.span.line,
+        column: finding.span.column,
+        pattern,
+        title: pattern_name,
+        message: format!(
+            "{pattern_name} ({}) detected: {}",
+            finding.severity.to_string().to_lowercase(),
+            finding.secret.as_masked()
+        ),
+    }
+    .write(writer)
+}
@@ -0,0 +1,39 @@
+//! GitLab Secret Detection report formatter for scan findings.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::{OutputContext, report_path};
+use crate::report::gitlab::{self, Commit, Location, Vulnerability};
+use crate::report::occurrence_id;
+
+/// Serialises scan findings as a GitLab Secret Detection report.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let vulnerabilities = ctx
+        .findings
+        .iter()
+        .map(|f| convert_vulnerability(f, ctx.patterns))
+        .collect();
+
+    gitlab::write(vulnerabilities, ctx.stats.elapsed, writer)
+}
+
+fn convert_vulnerability(finding: &Finding, patterns: &[Pattern]) -> Vulnerability {
+    let pattern_id = finding.pattern_id.as_ref();
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == pattern_id);
+    let name = crate::report::pattern_name(pattern_id, pattern);
+
+    Vulnerability::new(
+        occurrence_id(&finding.baseline_fingerprint(), finding.span.line, finding.span.column),
+        pattern_id,
+        pattern,
+        finding.severity,
+        pattern.map_or_else(|| format!("{name} detected"), |p| p.description.to_string()),
+        Location {
+            file: report_path(&finding.path),
+            line: finding.span.line,
+            commit: Commit::uncommitted(),
+        },
+    )
+}
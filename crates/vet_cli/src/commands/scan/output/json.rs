@@ -5,7 +5,7 @@ use std::io::Write;
 use serde::Serialize;
 use vet_core::prelude::*;
 
-use super::VerificationMap;
+use super::{ExpiredMap, VerificationMap};
 
 #[derive(Serialize)]
 struct JsonFinding {
@@ -20,6 +20,18 @@ struct JsonFinding {
     fingerprint: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     verification: Option<JsonVerification>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expired_acceptance: Option<JsonExpiredAcceptance>,
+}
+
+#[derive(Serialize)]
+struct JsonExpiredAcceptance {
+    expired_at: String,
+    reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reviewed_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ticket: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -39,7 +51,7 @@ struct JsonVerification {
     verified_at: Option<String>,
 }
 
-fn to_json_finding(f: &Finding, verifications: Option<&VerificationMap>) -> JsonFinding {
+fn to_json_finding(f: &Finding, verifications: Option<&VerificationMap>, expired: &ExpiredMap) -> JsonFinding {
     let verification = verifications
         .and_then(|v| v.get(f.id.as_str()))
         .map(|v| JsonVerification {
@@ -75,6 +87,12 @@ fn to_json_finding(f: &Finding, verifications: Option<&VerificationMap>) -> Json
         secret_masked: f.secret.as_masked().to_string(),
         fingerprint: f.baseline_fingerprint().as_str().to_string(),
         verification,
+        expired_acceptance: expired.get(f.id.as_str()).map(|entry| JsonExpiredAcceptance {
+            expired_at: entry.expires_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+            reason: entry.reason.clone(),
+            reviewed_by: entry.reviewed_by.clone(),
+            ticket: entry.ticket.clone(),
+        }),
     }
 }
 
@@ -82,9 +100,13 @@ fn to_json_finding(f: &Finding, verifications: Option<&VerificationMap>) -> Json
 pub fn write(
     findings: &[Finding],
     verifications: Option<&VerificationMap>,
+    expired: &ExpiredMap,
     writer: &mut dyn Write,
 ) -> anyhow::Result<()> {
-    let json_findings: Vec<JsonFinding> = findings.iter().map(|f| to_json_finding(f, verifications)).collect();
+    let json_findings: Vec<JsonFinding> = findings
+        .iter()
+        .map(|f| to_json_finding(f, verifications, expired))
+        .collect();
     serde_json::to_writer_pretty(&mut *writer, &json_findings)?;
     writeln!(writer)?;
     Ok(())
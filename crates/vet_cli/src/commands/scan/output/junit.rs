@@ -0,0 +1,79 @@
+//! `JUnit` XML output formatter for scan findings.
+//!
+//! Each finding becomes a failed test case so CI dashboards can track them
+//! alongside test results.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::{OutputContext, report_path};
+use crate::report::escape_xml;
+
+const SUITE_NAME: &str = "vet scan";
+
+/// Serialises scan findings as a `JUnit` XML report.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let failures = ctx.findings.len();
+    // A clean scan still reports a passing case so dashboards record the run.
+    let tests = failures.max(1);
+    let time = ctx.stats.elapsed.as_secs_f64();
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="vet" tests="{tests}" failures="{failures}" time="{time:.3}">"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <testsuite name="{SUITE_NAME}" tests="{tests}" failures="{failures}" errors="0" skipped="0" time="{time:.3}">"#
+    )?;
+
+    if ctx.findings.is_empty() {
+        writeln!(writer, r#"    <testcase name="no secrets found" classname="vet"/>"#)?;
+    }
+
+    for finding in ctx.findings {
+        write_testcase(finding, ctx.patterns, writer)?;
+    }
+
+    writeln!(writer, "  </testsuite>")?;
+    writeln!(writer, "</testsuites>")?;
+    Ok(())
+}
+
+fn write_testcase(finding: &Finding, patterns: &[Pattern], writer: &mut dyn Write) -> anyhow::Result<()> {
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == finding.pattern_id.as_ref());
+    let pattern_name = pattern.map_or(finding.pattern_id.as_ref(), |p| p.name.as_ref());
+    let path = report_path(&finding.path);
+    let location = format!("{path}:{}:{}", finding.span.line, finding.span.column);
+
+    writeln!(
+        writer,
+        r#"    <testcase name="{}" classname="{}" file="{}" line="{}">"#,
+        escape_xml(&format!("{} at {location}", finding.pattern_id)),
+        escape_xml(&path),
+        escape_xml(&path),
+        finding.span.line,
+    )?;
+
+    let mut body = format!(
+        "{location}\nSecret: {}\nConfidence: {}",
+        finding.secret.as_masked(),
+        finding.confidence
+    );
+    if let Some(pattern) = pattern {
+        body.push_str("\n\n");
+        body.push_str(pattern.remediation());
+    }
+
+    writeln!(
+        writer,
+        r#"      <failure message="{}" type="{}">{}</failure>"#,
+        escape_xml(&format!("{pattern_name} detected")),
+        finding.severity.to_string().to_lowercase(),
+        escape_xml(&body),
+    )?;
+    writeln!(writer, "    </testcase>")?;
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! Markdown output formatter for scan findings.
+//!
+//! Findings are grouped by file, suitable for posting as a pull request
+//! comment.
+
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::{OutputContext, report_path};
+use crate::report::markdown::{self, Layout, write_remediation};
+use crate::report::{escape_markdown, markdown_code};
+use crate::ui::pluralise_word;
+
+/// Renders scan findings as a Markdown report grouped by file.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let layout = Layout {
+        title: "vet scan",
+        scanned: format!(
+            "{} {} scanned",
+            ctx.stats.file_count,
+            pluralise_word(ctx.stats.file_count, "file", "files")
+        ),
+        path: |f: &Finding| report_path(&f.path),
+        severity: |f: &Finding| f.severity,
+    };
+
+    markdown::write(&layout, ctx.findings, writer, |finding, writer| {
+        write_finding(finding, ctx.patterns, writer)
+    })
+}
+
+fn write_finding(finding: &Finding, patterns: &[Pattern], writer: &mut dyn Write) -> anyhow::Result<()> {
+    let pattern = patterns.iter().find(|p| p.id.as_ref() == finding.pattern_id.as_ref());
+    let pattern_name = pattern.map_or(finding.pattern_id.as_ref(), |p| p.name.as_ref());
+
+    let confidence = if finding.confidence == Confidence::Low {
+        " · low confidence"
+    } else {
+        ""
+    };
+
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "**{}** · {} · line {}{confidence}",
+        escape_markdown(pattern_name),
+        finding.severity.to_string().to_lowercase(),
+        finding.span.line,
+    )?;
+    writeln!(writer)?;
+    writeln!(writer, "{}", markdown_code(finding.masked_line.trim()))?;
+
+    write_remediation(pattern, writer)
+}
@@ -1,13 +1,19 @@
 //! Output formatting for scan results.
 
+mod code_quality;
+mod csv;
+mod github;
+mod gitlab;
 mod json;
+mod junit;
+mod markdown;
 mod sarif;
 mod text;
 
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufWriter;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::Context as _;
@@ -20,6 +26,9 @@ use crate::{OutputFormat, ScanArgs};
 /// Maps finding IDs to their verification results.
 pub type VerificationMap = HashMap<String, VerificationResult>;
 
+/// Maps finding IDs to the baseline entries whose acceptance has expired.
+pub type ExpiredMap = HashMap<String, BaselineFinding>;
+
 /// Aggregate statistics for a completed scan.
 #[derive(Debug)]
 pub struct ScanStats {
@@ -48,6 +57,12 @@ pub struct OutputContext<'a> {
     pub stats: ScanStats,
     /// Verification results, if verification was requested.
     pub verifications: Option<&'a VerificationMap>,
+    /// Findings suppressed by the baseline or config ignores, if either was in use.
+    pub baselined: Option<&'a [Finding]>,
+    /// Reported findings whose baseline acceptance has expired.
+    pub expired: &'a ExpiredMap,
+    /// The exit code the scan will terminate with.
+    pub exit_code: i32,
 }
 
 /// Writes scan output to a file or stdout in the requested format.
@@ -64,8 +79,14 @@ fn write_to_file(path: &PathBuf, format: OutputFormat, ctx: &OutputContext) -> a
 
     match format {
         OutputFormat::Text => text::write(ctx, &mut writer, true, 0),
-        OutputFormat::Json => json::write(ctx.findings, ctx.verifications, &mut writer),
-        OutputFormat::Sarif => sarif::write(ctx.findings, ctx.patterns, ctx.verifications, &mut writer),
+        OutputFormat::Json => json::write(ctx.findings, ctx.verifications, ctx.expired, &mut writer),
+        OutputFormat::Sarif => sarif::write(ctx, &mut writer),
+        OutputFormat::Gitlab => gitlab::write(ctx, &mut writer),
+        OutputFormat::CodeQuality => code_quality::write(ctx.findings, ctx.patterns, &mut writer),
+        OutputFormat::Junit => junit::write(ctx, &mut writer),
+        OutputFormat::Csv => csv::write(ctx.findings, ctx.patterns, ctx.verifications, &mut writer),
+        OutputFormat::Markdown => markdown::write(ctx, &mut writer),
+        OutputFormat::Github => github::write(ctx, &mut writer),
     }
 }
 
@@ -74,7 +95,19 @@ fn write_to_stdout(format: OutputFormat, verbose: u8, ctx: &OutputContext) -> an
 
     match format {
         OutputFormat::Text => text::write(ctx, &mut stdout, false, verbose),
-        OutputFormat::Json => json::write(ctx.findings, ctx.verifications, &mut stdout),
-        OutputFormat::Sarif => sarif::write(ctx.findings, ctx.patterns, ctx.verifications, &mut stdout),
+        OutputFormat::Json => json::write(ctx.findings, ctx.verifications, ctx.expired, &mut stdout),
+        OutputFormat::Sarif => sarif::write(ctx, &mut stdout),
+        OutputFormat::Gitlab => gitlab::write(ctx, &mut stdout),
+        OutputFormat::CodeQuality => code_quality::write(ctx.findings, ctx.patterns, &mut stdout),
+        OutputFormat::Junit => junit::write(ctx, &mut stdout),
+        OutputFormat::Csv => csv::write(ctx.findings, ctx.patterns, ctx.verifications, &mut stdout),
+        OutputFormat::Markdown => markdown::write(ctx, &mut stdout),
+        OutputFormat::Github => github::write(ctx, &mut stdout),
     }
 }
+
+/// Renders a finding path without the leading `./`, which CI report
+/// consumers do not resolve against the repository root.
+fn report_path(path: &Path) -> String {
+    path.strip_prefix(".").unwrap_or(path).display().to_string()
+}
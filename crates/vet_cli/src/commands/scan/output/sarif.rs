@@ -2,14 +2,17 @@
 
 use std::io::Write;
 
+use chrono::{TimeDelta, Utc};
 use serde::Serialize;
 use vet_core::prelude::*;
 
-use super::VerificationMap;
+use super::{OutputContext, VerificationMap, report_path};
+use crate::commands::scan::runner::ContentCache;
+use crate::report::sarif::{self, HELP_URI, TOOL_NAME, TOOL_URI, TOOL_VERSION};
 
 const SARIF_VERSION: &str = "2.1.0";
 const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
-const TOOL_NAME: &str = "vet";
+const REDACTED_MARKER: &str = "<REDACTED>";
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -24,6 +27,7 @@ struct SarifReport {
 #[serde(rename_all = "camelCase")]
 struct SarifRun {
     tool: SarifTool,
+    invocations: Vec<SarifInvocation>,
     results: Vec<SarifResult>,
 }
 
@@ -37,6 +41,8 @@ struct SarifTool {
 #[serde(rename_all = "camelCase")]
 struct SarifDriver {
     name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
     rules: Vec<SarifRule>,
 }
 
@@ -46,7 +52,10 @@ struct SarifRule {
     id: String,
     name: String,
     short_description: SarifMessage,
+    help_uri: &'static str,
+    help: SarifMessage,
     default_configuration: SarifRuleConfig,
+    properties: SarifRuleProperties,
 }
 
 #[derive(Serialize)]
@@ -55,6 +64,22 @@ struct SarifRuleConfig {
     level: &'static str,
 }
 
+#[derive(Serialize)]
+struct SarifRuleProperties {
+    tags: [&'static str; 2],
+    #[serde(rename = "security-severity")]
+    security_severity: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifInvocation {
+    execution_successful: bool,
+    exit_code: i32,
+    start_time_utc: String,
+    end_time_utc: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SarifResult {
@@ -62,10 +87,29 @@ struct SarifResult {
     level: &'static str,
     message: SarifMessage,
     locations: Vec<SarifLocation>,
+    partial_fingerprints: SarifPartialFingerprints,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    baseline_state: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<SarifSuppression>,
+    fixes: Vec<SarifFix>,
     #[serde(skip_serializing_if = "Option::is_none")]
     properties: Option<SarifProperties>,
 }
 
+#[derive(Serialize)]
+struct SarifPartialFingerprints {
+    #[serde(rename = "vetBaselineFingerprint/v1")]
+    baseline: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifSuppression {
+    kind: &'static str,
+    justification: &'static str,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SarifProperties {
@@ -106,25 +150,78 @@ struct SarifArtifactLocation {
 struct SarifRegion {
     start_line: u32,
     start_column: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<u32>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifFix {
+    description: SarifMessage,
+    artifact_changes: Vec<SarifArtifactChange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactChange {
+    artifact_location: SarifArtifactLocation,
+    replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifReplacement {
+    deleted_region: SarifRegion,
+    inserted_content: SarifInsertedContent,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifInsertedContent {
+    text: &'static str,
 }
 
 /// Serialises scan findings as a SARIF v2.1.0 report to the given writer.
-pub fn write(
-    findings: &[Finding],
-    patterns: &[Pattern],
-    verifications: Option<&VerificationMap>,
-    writer: &mut dyn Write,
-) -> anyhow::Result<()> {
-    let rules = build_rules(patterns);
-    let results = build_results(findings, verifications);
+///
+/// When a baseline is in use, findings it suppressed are included with
+/// `baselineState: "unchanged"` and an external suppression, and reported
+/// findings are marked `"new"`.
+pub fn write(ctx: &OutputContext, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let end = Utc::now();
+    let start = end - TimeDelta::from_std(ctx.stats.elapsed).unwrap_or_default();
+
+    let new_state = ctx.baselined.map(|_| "new");
+    let mut results = build_results(ctx.findings, ctx.content_cache, ctx.verifications, new_state);
+
+    if let Some(baselined) = ctx.baselined {
+        let mut unchanged = build_results(baselined, ctx.content_cache, None, Some("unchanged"));
+        for result in &mut unchanged {
+            result.suppressions.push(SarifSuppression {
+                kind: "external",
+                justification: "Accepted in the baseline or config ignores",
+            });
+        }
+        results.extend(unchanged);
+    }
 
     let report = SarifReport {
         schema: SARIF_SCHEMA,
         version: SARIF_VERSION,
         runs: vec![SarifRun {
             tool: SarifTool {
-                driver: SarifDriver { name: TOOL_NAME, rules },
+                driver: SarifDriver {
+                    name: TOOL_NAME,
+                    version: TOOL_VERSION,
+                    information_uri: TOOL_URI,
+                    rules: build_rules(ctx.patterns),
+                },
             },
+            invocations: vec![SarifInvocation {
+                execution_successful: true,
+                exit_code: ctx.exit_code,
+                start_time_utc: start.to_rfc3339(),
+                end_time_utc: end.to_rfc3339(),
+            }],
             results,
         }],
     };
@@ -143,14 +240,27 @@ fn build_rules(patterns: &[Pattern]) -> Vec<SarifRule> {
             short_description: SarifMessage {
                 text: p.description.to_string(),
             },
+            help_uri: HELP_URI,
+            help: SarifMessage {
+                text: p.remediation().to_string(),
+            },
             default_configuration: SarifRuleConfig {
-                level: severity_to_level(p.severity),
+                level: sarif::level(p.severity),
+            },
+            properties: SarifRuleProperties {
+                tags: ["security", "secret"],
+                security_severity: sarif::security_severity(p.severity),
             },
         })
         .collect()
 }
 
-fn build_results(findings: &[Finding], verifications: Option<&VerificationMap>) -> Vec<SarifResult> {
+fn build_results(
+    findings: &[Finding],
+    content_cache: &ContentCache,
+    verifications: Option<&VerificationMap>,
+    baseline_state: Option<&'static str>,
+) -> Vec<SarifResult> {
     findings
         .iter()
         .map(|f| {
@@ -165,33 +275,61 @@ fn build_results(findings: &[Finding], verifications: Option<&VerificationMap>)
                     verified_at: Some(v.verified_at.to_string()),
                 });
 
+            let uri = report_path(&f.path);
+
             SarifResult {
                 rule_id: f.pattern_id.to_string(),
-                level: severity_to_level(f.severity),
+                level: sarif::level(f.severity),
                 message: SarifMessage {
                     text: format!("Potential secret detected: {}", f.pattern_id),
                 },
                 locations: vec![SarifLocation {
                     physical_location: SarifPhysicalLocation {
-                        artifact_location: SarifArtifactLocation {
-                            uri: f.path.display().to_string(),
-                        },
+                        artifact_location: SarifArtifactLocation { uri: uri.clone() },
                         region: SarifRegion {
                             start_line: f.span.line,
                             start_column: f.span.column,
+                            end_column: None,
                         },
                     },
                 }],
+                partial_fingerprints: SarifPartialFingerprints {
+                    baseline: f.baseline_fingerprint().to_string(),
+                },
+                baseline_state,
+                suppressions: Vec::new(),
+                fixes: build_fix(f, uri, content_cache).into_iter().collect(),
                 properties,
             }
         })
         .collect()
 }
 
-const fn severity_to_level(severity: Severity) -> &'static str {
-    match severity {
-        Severity::Critical | Severity::High => "error",
-        Severity::Medium => "warning",
-        Severity::Low => "note",
-    }
+/// Describes replacing the secret with a redaction marker, as `vet fix`
+/// would. Omitted when the file content is not available to measure the
+/// secret's width.
+fn build_fix(finding: &Finding, uri: String, content_cache: &ContentCache) -> Option<SarifFix> {
+    let secret = content_cache
+        .get(finding.path.as_ref())?
+        .get(finding.span.byte_start..finding.span.byte_end)?;
+
+    // SARIF columns count UTF-16 code units by default.
+    let width = u32::try_from(secret.encode_utf16().count()).ok()?;
+
+    Some(SarifFix {
+        description: SarifMessage {
+            text: format!("Redact the secret and rotate it. Replaces the value with {REDACTED_MARKER}."),
+        },
+        artifact_changes: vec![SarifArtifactChange {
+            artifact_location: SarifArtifactLocation { uri },
+            replacements: vec![SarifReplacement {
+                deleted_region: SarifRegion {
+                    start_line: finding.span.line,
+                    start_column: finding.span.column,
+                    end_column: Some(finding.span.column + width),
+                },
+                inserted_content: SarifInsertedContent { text: REDACTED_MARKER },
+            }],
+        }],
+    })
 }
@@ -27,15 +27,7 @@ pub fn write(ctx: &OutputContext, writer: &mut dyn Write, strip_colors: bool, ve
     let pattern_index = index_patterns_by_id(ctx.patterns);
 
     for finding in ctx.findings {
-        write_finding(
-            finding,
-            ctx.findings,
-            &pattern_index,
-            ctx.content_cache,
-            ctx.verifications,
-            writer,
-            strip_colors,
-        )?;
+        write_finding(finding, ctx, &pattern_index, writer, strip_colors)?;
     }
 
     if let Some(verifications) = ctx.verifications {
@@ -51,18 +43,18 @@ fn index_patterns_by_id(patterns: &[Pattern]) -> HashMap<&str, &Pattern> {
 
 fn write_finding(
     finding: &Finding,
-    all_findings: &[Finding],
+    ctx: &OutputContext,
     pattern_index: &HashMap<&str, &Pattern>,
-    content_cache: &ContentCache,
-    verifications: Option<&VerificationMap>,
     writer: &mut dyn Write,
     strip_colors: bool,
 ) -> anyhow::Result<()> {
     let pattern = pattern_index.get(finding.pattern_id.as_ref());
-    let verification = verifications.and_then(|v| v.get(finding.id.as_str()));
+    let verification = ctx.verifications.and_then(|v| v.get(finding.id.as_str()));
+    let expired = ctx.expired.get(finding.id.as_str());
 
-    write_finding_header(finding, pattern, writer, strip_colors)?;
-    write_code_frame(finding, all_findings, content_cache, writer, strip_colors)?;
+    write_finding_header(finding, pattern, expired.is_some(), writer, strip_colors)?;
+    write_code_frame(finding, ctx.findings, ctx.content_cache, writer, strip_colors)?;
+    write_expired_acceptance(expired, writer, strip_colors)?;
     write_verification_status(verification, writer, strip_colors)?;
     write_remediation_hint(pattern, writer, strip_colors)?;
 
@@ -73,6 +65,7 @@ fn write_finding(
 fn write_finding_header(
     finding: &Finding,
     pattern: Option<&&Pattern>,
+    expired: bool,
     writer: &mut dyn Write,
     strip_colors: bool,
 ) -> anyhow::Result<()> {
@@ -98,15 +91,26 @@ fn write_finding_header(
         String::new()
     };
 
+    let expired_suffix = if expired {
+        format!(
+            " {} {}",
+            colors::muted().apply_to("·"),
+            colors::warning().apply_to("expired acceptance")
+        )
+    } else {
+        String::new()
+    };
+
     write_line(
         writer,
         format_args!(
-            "{} {} {} {}{}",
+            "{} {} {} {}{}{}",
             indicator,
             style(description).bold(),
             colors::muted().apply_to("·"),
             sev_style.apply_to(&severity_label),
             confidence_suffix,
+            expired_suffix,
         ),
         strip_colors,
     )?;
@@ -232,6 +236,39 @@ fn format_metadata_summary(service: Option<&vet_providers::ServiceInfo>) -> Stri
         .join(", ")
 }
 
+/// Explains why a baselined secret is reported again: when its acceptance
+/// lapsed, and the ticket and reviewer recorded with it.
+fn write_expired_acceptance(
+    entry: Option<&BaselineFinding>,
+    writer: &mut dyn Write,
+    strip_colors: bool,
+) -> anyhow::Result<()> {
+    let Some(entry) = entry else {
+        return Ok(());
+    };
+
+    let mut details = vec![format!(
+        "accepted until {}",
+        entry
+            .expires_at
+            .map(|at| at.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    )];
+    details.extend(entry.ticket.as_ref().map(|ticket| format!("ticket {ticket}")));
+    details.extend(entry.reviewed_by.as_ref().map(|by| format!("reviewed by {by}")));
+
+    writeln!(writer)?;
+    write_line(
+        writer,
+        format_args!(
+            "  {} {}",
+            colors::warning().apply_to(indicators::WARNING),
+            colors::secondary().apply_to(details.join(" · "))
+        ),
+        strip_colors,
+    )
+}
+
 fn write_verification_status(
     verification: Option<&vet_providers::VerificationResult>,
     writer: &mut dyn Write,
@@ -477,15 +514,7 @@ fn write_summary(ctx: &OutputContext, writer: &mut dyn Write, strip_colors: bool
     if ctx.findings.is_empty() {
         write_clean_summary(&files, &time, ctx.stats.baseline_count, writer, strip_colors)?;
     } else {
-        write_findings_summary(
-            ctx.findings,
-            &files,
-            &time,
-            ctx.stats.baseline_count,
-            verification_counts.as_ref(),
-            writer,
-            strip_colors,
-        )?;
+        write_findings_summary(ctx, &files, &time, verification_counts.as_ref(), writer, strip_colors)?;
     }
 
     if verbose > 0 && ctx.stats.filtered_count > 0 {
@@ -563,24 +592,41 @@ fn count_verification_statuses(findings: &[Finding], verifications: &Verificatio
 }
 
 fn write_findings_summary(
-    findings: &[Finding],
+    ctx: &OutputContext,
     files: &str,
     time: &str,
-    baseline_count: usize,
     verification_counts: Option<&VerificationCounts>,
     writer: &mut dyn Write,
     strip_colors: bool,
 ) -> anyhow::Result<()> {
-    let count = findings.len();
+    let findings = ctx.findings;
+    let baseline_count = ctx.stats.baseline_count;
+    let expired_count = findings
+        .iter()
+        .filter(|f| ctx.expired.contains_key(f.id.as_str()))
+        .count();
+    let count = findings.len() - expired_count;
     let word = pluralise_word(count, "secret", "secrets");
     let severity_summary = build_severity_summary(findings, |f| f.severity);
 
-    let message = if baseline_count > 0 {
+    let new_label = if baseline_count > 0 || expired_count > 0 {
+        "new "
+    } else {
+        ""
+    };
+    let expired_label = if expired_count > 0 {
+        let expired_word = pluralise_word(expired_count, "acceptance", "acceptances");
+        format!(", {expired_count} expired {expired_word}")
+    } else {
+        String::new()
+    };
+    let baseline_label = if baseline_count > 0 {
         let baseline_word = pluralise_word(baseline_count, "finding", "findings");
-        format!("{count} new {word} found ({baseline_count} {baseline_word} in baseline)")
+        format!(" ({baseline_count} {baseline_word} in baseline)")
     } else {
-        format!("{count} {word} found")
+        String::new()
     };
+    let message = format!("{count} {new_label}{word} found{expired_label}{baseline_label}");
 
     let verification_summary = match verification_counts {
         Some(counts) => {
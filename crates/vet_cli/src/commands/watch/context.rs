@@ -0,0 +1,86 @@
+//! Watch context - configuration, pattern and baseline loading.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use vet_core::prelude::*;
+
+use crate::scanning::{build_scanner, load_patterns};
+use crate::{CONFIG_FILENAME, WatchArgs};
+
+/// Loaded scanner, configuration and baseline matcher for a watch session.
+///
+/// Rebuilt from scratch whenever `.vet.toml` or the baseline file changes.
+#[derive(Debug)]
+pub struct WatchContext {
+    /// The compiled secret scanner shared by every rescan.
+    pub scanner: Scanner,
+    /// Parsed configuration from `.vet.toml`.
+    pub config: Config,
+    /// Suppresses findings acknowledged in the baseline or config ignores.
+    pub matcher: IgnoreMatcher,
+    /// Absolute path to the configuration file.
+    pub config_path: PathBuf,
+    /// Absolute path to the baseline file, if one is configured.
+    pub baseline_path: Option<PathBuf>,
+}
+
+impl WatchContext {
+    /// Loads configuration, patterns, and the baseline from CLI arguments.
+    pub fn load(args: &WatchArgs) -> anyhow::Result<Self> {
+        let config_path = args.config.as_deref().unwrap_or(Path::new(CONFIG_FILENAME));
+        let config = Config::load(config_path).context("loading config")?;
+
+        let registry = load_patterns(&config)?;
+        let severity = args.severity.or(config.severity);
+        let scanner = build_scanner(registry, severity);
+
+        let baseline_path = args
+            .baseline
+            .as_deref()
+            .or_else(|| config.baseline_path.as_deref().map(Path::new));
+
+        let baseline = match baseline_path {
+            Some(path) if path.exists() => Some(Baseline::load(path)?),
+            Some(path) if args.baseline.is_some() => {
+                anyhow::bail!("baseline file not found: {}", path.display());
+            }
+            _ => None,
+        };
+
+        let matcher = IgnoreMatcher::new(baseline.as_ref(), &config.ignores);
+
+        Ok(Self {
+            scanner,
+            matcher,
+            config_path: std::path::absolute(config_path)?,
+            baseline_path: baseline_path.map(std::path::absolute).transpose()?,
+            config,
+        })
+    }
+
+    /// Merges config and CLI exclude patterns.
+    pub fn excludes(&self, arg_excludes: &[String]) -> Vec<String> {
+        self.config
+            .exclude_paths
+            .iter()
+            .chain(arg_excludes.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the effective max file size, preferring the CLI argument over config.
+    pub fn max_file_size(&self, arg_max: Option<u64>) -> Option<u64> {
+        arg_max.or(self.config.max_file_size)
+    }
+
+    /// Returns the effective minimum confidence level, preferring the CLI argument.
+    pub fn minimum_confidence(&self, arg_minimum: Option<Confidence>) -> Confidence {
+        arg_minimum.unwrap_or(self.config.minimum_confidence)
+    }
+
+    /// Returns `true` if a change to `path` requires reloading this context.
+    pub fn is_reload_trigger(&self, path: &Path) -> bool {
+        path == self.config_path || self.baseline_path.as_deref() == Some(path)
+    }
+}
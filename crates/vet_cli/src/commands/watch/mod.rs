@@ -0,0 +1,243 @@
+//! Watch command - rescans files as they change.
+
+mod context;
+mod output;
+mod state;
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::Context as _;
+use notify::{Event, EventKind, RecursiveMode, Watcher as _};
+use rayon::prelude::*;
+use vet_core::prelude::*;
+
+use self::context::WatchContext;
+use self::state::WatchState;
+use crate::WatchArgs;
+use crate::files::{collect_files, collect_files_among, read_text_file};
+use crate::ui::{print_command_header, print_warning};
+
+/// Executes the `vet watch` command.
+///
+/// Performs an initial scan, then blocks forever rescanning files as the
+/// filesystem reports changes to them.
+pub fn run(args: &WatchArgs) -> super::Result {
+    print_command_header("watch");
+
+    let cwd = std::env::current_dir().context("failed to read current directory")?;
+    let mut session = WatchSession {
+        args,
+        cwd,
+        context: WatchContext::load(args)?,
+        state: WatchState::default(),
+    };
+
+    // Watch before the initial scan so edits made while it runs are queued.
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("failed to start file watcher")?;
+
+    for root in session.watch_roots()? {
+        watcher
+            .watch(&root.path, root.mode)
+            .with_context(|| format!("failed to watch {}", root.path.display()))?;
+    }
+
+    session.rescan_all();
+
+    let debounce = Duration::from_millis(args.debounce);
+
+    while let Ok(first) = rx.recv() {
+        let mut changed = HashSet::new();
+        collect_event_paths(first, &mut changed);
+
+        let deadline = Instant::now() + debounce;
+        while let Ok(event) = rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            collect_event_paths(event, &mut changed);
+        }
+
+        session.apply_changes(&changed);
+    }
+
+    Ok(())
+}
+
+struct WatchRoot {
+    path: PathBuf,
+    mode: RecursiveMode,
+}
+
+struct WatchSession<'a> {
+    args: &'a WatchArgs,
+    cwd: PathBuf,
+    context: WatchContext,
+    state: WatchState,
+}
+
+impl WatchSession<'_> {
+    /// Watches each scan path recursively, plus the directories holding the
+    /// config and baseline files so edits to them trigger a reload. The parent
+    /// directory is watched rather than the file itself because atomic saves
+    /// replace the inode.
+    fn watch_roots(&self) -> anyhow::Result<Vec<WatchRoot>> {
+        let mut roots = Vec::new();
+
+        for path in &self.args.paths {
+            let path = std::path::absolute(path)?;
+            if path.exists() {
+                roots.push(WatchRoot {
+                    path,
+                    mode: RecursiveMode::Recursive,
+                });
+            }
+        }
+
+        let reload_files = std::iter::once(&self.context.config_path).chain(self.context.baseline_path.as_ref());
+
+        for file in reload_files {
+            if let Some(parent) = file.parent()
+                && parent.is_dir()
+                && !roots.iter().any(|r| parent.starts_with(&r.path))
+            {
+                roots.push(WatchRoot {
+                    path: parent.to_path_buf(),
+                    mode: RecursiveMode::NonRecursive,
+                });
+            }
+        }
+
+        Ok(roots)
+    }
+
+    /// Rescans every collected file and prints the differences from the
+    /// previous state.
+    fn rescan_all(&mut self) {
+        let scannable = self.scannable_files();
+        self.drop_stale(|p| !scannable.contains(p));
+
+        let mut paths: Vec<PathBuf> = scannable.into_iter().collect();
+        paths.sort();
+        self.rescan(&paths);
+
+        output::print_status(&self.state);
+    }
+
+    fn apply_changes(&mut self, changed: &HashSet<PathBuf>) {
+        if let Some(trigger) = changed.iter().find(|p| self.context.is_reload_trigger(p)) {
+            let what = if *trigger == self.context.config_path {
+                "config"
+            } else {
+                "baseline"
+            };
+
+            match WatchContext::load(self.args) {
+                Ok(context) => {
+                    self.context = context;
+                    output::print_reloaded(what);
+                    self.rescan_all();
+                }
+                Err(e) => print_warning(&format!("{what} reload failed, keeping previous settings: {e:#}")),
+            }
+            return;
+        }
+
+        let changed: Vec<PathBuf> = changed.iter().cloned().collect();
+        let scannable = self.scannable_among(&changed);
+        let removed = self.drop_stale(|p| changed.iter().any(|c| p.starts_with(c)) && !scannable.contains(p));
+
+        let mut to_scan: Vec<PathBuf> = scannable.into_iter().collect();
+        to_scan.sort();
+
+        if self.rescan(&to_scan) || removed {
+            output::print_status(&self.state);
+        }
+    }
+
+    /// Resolves findings in files that were deleted or are no longer
+    /// scannable (for example, newly excluded), as decided by `is_stale`.
+    /// Returns `true` if any findings were resolved.
+    fn drop_stale(&mut self, is_stale: impl Fn(&Path) -> bool) -> bool {
+        let stale: Vec<PathBuf> = self
+            .state
+            .paths()
+            .filter(|p| is_stale(p))
+            .map(Path::to_path_buf)
+            .collect();
+
+        for path in &stale {
+            let delta = self.state.remove(path);
+            output::print_delta(&delta, &self.context.scanner);
+        }
+
+        !stale.is_empty()
+    }
+
+    /// Scans the given absolute paths in parallel and records the results.
+    /// Returns `true` if any file's findings changed.
+    fn rescan(&mut self, paths: &[PathBuf]) -> bool {
+        let max_file_size = self.context.max_file_size(self.args.max_file_size);
+        let minimum_confidence = self.context.minimum_confidence(self.args.minimum_confidence);
+        let context = &self.context;
+        let cwd = &self.cwd;
+
+        let results: Vec<(&PathBuf, Vec<Finding>)> = paths
+            .par_iter()
+            .map(|path| {
+                let display = path.strip_prefix(cwd).unwrap_or(path);
+                let findings = read_text_file(path, max_file_size)
+                    .map(|content| {
+                        context
+                            .scanner
+                            .scan_content(&content, display)
+                            .into_iter()
+                            .filter(|f| f.confidence >= minimum_confidence)
+                            .filter(|f| !context.matcher.is_ignored(&f.baseline_fingerprint()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (path, findings)
+            })
+            .collect();
+
+        let mut any_changed = false;
+
+        for (path, findings) in results {
+            let delta = self.state.update(path, &findings);
+            any_changed |= !delta.is_empty();
+            output::print_delta(&delta, &self.context.scanner);
+        }
+
+        any_changed
+    }
+
+    /// Collects scannable files with the same exclude and gitignore rules as
+    /// `vet scan`, keyed by absolute path.
+    fn scannable_files(&self) -> HashSet<PathBuf> {
+        let excludes = self.context.excludes(&self.args.exclude);
+
+        collect_files(&self.args.paths, &excludes, !self.args.skip_gitignore)
+            .into_iter()
+            .filter_map(|p| std::path::absolute(p).ok())
+            .collect()
+    }
+
+    /// Returns the scannable files among changed paths, including files
+    /// inside changed directories, without walking the rest of the tree.
+    fn scannable_among(&self, changed: &[PathBuf]) -> HashSet<PathBuf> {
+        let excludes = self.context.excludes(&self.args.exclude);
+
+        collect_files_among(&self.args.paths, &excludes, !self.args.skip_gitignore, changed)
+            .into_iter()
+            .collect()
+    }
+}
+
+fn collect_event_paths(event: notify::Result<Event>, changed: &mut HashSet<PathBuf>) {
+    match event {
+        Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+        Ok(event) => changed.extend(event.paths),
+        Err(e) => print_warning(&format!("watch error: {e}")),
+    }
+}
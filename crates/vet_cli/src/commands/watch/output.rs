@@ -0,0 +1,110 @@
+//! Incremental terminal output for watch mode.
+
+use chrono::Local;
+use console::style;
+use vet_core::prelude::*;
+
+use super::state::{FileDelta, WatchState};
+use crate::ui::{colors, indicators, pluralise_word, severity_indicator, severity_style};
+
+/// Prints the findings that appeared or disappeared in a single file.
+pub fn print_delta(delta: &FileDelta, scanner: &Scanner) {
+    for finding in &delta.resolved {
+        print_resolved(finding, scanner);
+    }
+
+    for finding in &delta.added {
+        print_added(finding, scanner);
+    }
+}
+
+fn print_added(finding: &Finding, scanner: &Scanner) {
+    let name = pattern_name(finding, scanner);
+
+    let suffix = if finding.confidence == Confidence::Low {
+        format!(
+            " {} {}",
+            colors::muted().apply_to("·"),
+            colors::warning().apply_to("low confidence")
+        )
+    } else {
+        String::new()
+    };
+
+    println!(
+        "{} {} {} {}{}",
+        severity_indicator(finding.severity),
+        style(name).bold(),
+        colors::muted().apply_to("·"),
+        severity_style(finding.severity).apply_to(finding.severity.to_string()),
+        suffix,
+    );
+    println!(
+        "  {}",
+        colors::secondary().apply_to(format!(
+            "{}:{}:{}",
+            finding.path.display(),
+            finding.span.line,
+            finding.span.column
+        ))
+    );
+    println!("  {}", colors::code().apply_to(finding.masked_line.trim()));
+    println!();
+}
+
+fn print_resolved(finding: &Finding, scanner: &Scanner) {
+    println!(
+        "{} {} {} {}",
+        colors::success().apply_to(indicators::SUCCESS),
+        colors::secondary().apply_to(pattern_name(finding, scanner)),
+        colors::muted().apply_to("resolved ·"),
+        colors::muted().apply_to(format!("{}:{}", finding.path.display(), finding.span.line)),
+    );
+}
+
+/// Prints a one-line status summarising the current state of the tree.
+pub fn print_status(state: &WatchState) {
+    let time = Local::now().format("%H:%M:%S").to_string();
+    let count = state.finding_count();
+
+    let message = if count == 0 {
+        format!(
+            "{} {}",
+            colors::success().apply_to(indicators::SUCCESS),
+            colors::primary().apply_to("No secrets found")
+        )
+    } else {
+        let files = state.file_count();
+        format!(
+            "{} {}",
+            colors::error().apply_to(indicators::ERROR),
+            colors::primary().apply_to(format!(
+                "{count} {} in {files} {}",
+                pluralise_word(count, "secret", "secrets"),
+                pluralise_word(files, "file", "files"),
+            ))
+        )
+    };
+
+    println!(
+        "{message} {} {}",
+        colors::muted().apply_to("·"),
+        colors::muted().apply_to(format!("{time} · watching for changes"))
+    );
+}
+
+/// Prints a notice that the configuration or baseline was reloaded.
+pub fn print_reloaded(what: &str) {
+    println!(
+        "{} {}",
+        colors::info().apply_to(indicators::INFO),
+        colors::secondary().apply_to(format!("{what} changed, rescanning"))
+    );
+    println!();
+}
+
+fn pattern_name<'a>(finding: &'a Finding, scanner: &'a Scanner) -> &'a str {
+    scanner
+        .get_pattern(&finding.pattern_id)
+        .map_or(finding.pattern_id.as_ref(), |p| p.name.as_ref())
+}
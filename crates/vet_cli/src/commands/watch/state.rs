@@ -0,0 +1,202 @@
+//! Per-file finding state and change tracking between rescans.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use vet_core::prelude::*;
+
+/// Findings that appeared or disappeared in a file since its previous scan.
+#[derive(Debug, Default)]
+pub struct FileDelta {
+    /// Findings present now that were not present before.
+    pub added: Vec<Finding>,
+    /// Findings present before that are no longer present.
+    pub resolved: Vec<Finding>,
+}
+
+impl FileDelta {
+    /// Returns `true` if nothing changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.resolved.is_empty()
+    }
+}
+
+/// Current findings for every watched file, keyed by absolute path.
+#[derive(Debug, Default)]
+pub struct WatchState {
+    files: HashMap<PathBuf, Vec<Finding>>,
+}
+
+impl WatchState {
+    /// Replaces the findings for a file, returning what changed.
+    ///
+    /// Findings are compared by baseline fingerprint, so a secret that only
+    /// moved to a different line is neither added nor resolved.
+    pub fn update(&mut self, path: &Path, findings: &[Finding]) -> FileDelta {
+        let previous = if findings.is_empty() {
+            self.files.remove(path).unwrap_or_default()
+        } else {
+            self.files
+                .insert(path.to_path_buf(), findings.to_vec())
+                .unwrap_or_default()
+        };
+
+        diff_findings(&previous, findings)
+    }
+
+    /// Forgets a file (deleted or no longer scannable), resolving its findings.
+    pub fn remove(&mut self, path: &Path) -> FileDelta {
+        FileDelta {
+            added: Vec::new(),
+            resolved: self.files.remove(path).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the paths of all files that currently have findings.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// Returns the total number of findings across all files.
+    #[must_use]
+    pub fn finding_count(&self) -> usize {
+        self.files.values().map(Vec::len).sum()
+    }
+
+    /// Returns the number of files with at least one finding.
+    #[must_use]
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+}
+
+fn diff_findings(previous: &[Finding], current: &[Finding]) -> FileDelta {
+    FileDelta {
+        added: unmatched(current, previous),
+        resolved: unmatched(previous, current),
+    }
+}
+
+/// Returns the findings in `from` that have no counterpart in `against`,
+/// treating repeated fingerprints as a multiset.
+fn unmatched(from: &[Finding], against: &[Finding]) -> Vec<Finding> {
+    let mut remaining: HashMap<Fingerprint, usize> = HashMap::new();
+    for finding in against {
+        *remaining.entry(finding.baseline_fingerprint()).or_default() += 1;
+    }
+
+    from.iter()
+        .filter(|finding| match remaining.get_mut(&finding.baseline_fingerprint()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(path: &str, secret: &str, line: u32) -> Finding {
+        let secret = Secret::new(secret);
+        Finding {
+            id: FindingId::new("test/pattern", &secret),
+            path: Path::new(path).into(),
+            span: Span::new(line, 1, 0, 10),
+            pattern_id: "test/pattern".into(),
+            secret,
+            severity: Severity::High,
+            masked_line: "masked".into(),
+            confidence: Confidence::High,
+        }
+    }
+
+    #[test]
+    fn first_scan_reports_all_findings_as_added() {
+        let mut state = WatchState::default();
+        let path = Path::new("/repo/a.env");
+
+        let delta = state.update(path, &[finding("a.env", "one", 1), finding("a.env", "two", 2)]);
+
+        assert_eq!(delta.added.len(), 2);
+        assert!(delta.resolved.is_empty());
+        assert_eq!(state.finding_count(), 2);
+        assert_eq!(state.file_count(), 1);
+    }
+
+    #[test]
+    fn rescan_with_same_findings_is_empty() {
+        let mut state = WatchState::default();
+        let path = Path::new("/repo/a.env");
+        let _ = state.update(path, &[finding("a.env", "one", 1)]);
+
+        let delta = state.update(path, &[finding("a.env", "one", 1)]);
+
+        assert!(delta.is_empty());
+    }
+
+    #[test]
+    fn moved_line_is_not_reported() {
+        let mut state = WatchState::default();
+        let path = Path::new("/repo/a.env");
+        let _ = state.update(path, &[finding("a.env", "one", 1)]);
+
+        let delta = state.update(path, &[finding("a.env", "one", 5)]);
+
+        assert!(delta.is_empty());
+    }
+
+    #[test]
+    fn removed_secret_is_resolved() {
+        let mut state = WatchState::default();
+        let path = Path::new("/repo/a.env");
+        let _ = state.update(path, &[finding("a.env", "one", 1), finding("a.env", "two", 2)]);
+
+        let delta = state.update(path, &[finding("a.env", "two", 2)]);
+
+        assert!(delta.added.is_empty());
+        assert_eq!(delta.resolved.len(), 1);
+        assert_eq!(delta.resolved[0].span.line, 1);
+    }
+
+    #[test]
+    fn duplicate_secret_counts_as_separate_finding() {
+        let mut state = WatchState::default();
+        let path = Path::new("/repo/a.env");
+        let _ = state.update(path, &[finding("a.env", "one", 1)]);
+
+        let delta = state.update(path, &[finding("a.env", "one", 1), finding("a.env", "one", 2)]);
+
+        assert_eq!(delta.added.len(), 1);
+        assert!(delta.resolved.is_empty());
+    }
+
+    #[test]
+    fn clean_rescan_forgets_file() {
+        let mut state = WatchState::default();
+        let path = Path::new("/repo/a.env");
+        let _ = state.update(path, &[finding("a.env", "one", 1)]);
+
+        let delta = state.update(path, &[]);
+
+        assert_eq!(delta.resolved.len(), 1);
+        assert_eq!(state.file_count(), 0);
+    }
+
+    #[test]
+    fn remove_resolves_all_findings() {
+        let mut state = WatchState::default();
+        let path = Path::new("/repo/a.env");
+        let _ = state.update(path, &[finding("a.env", "one", 1), finding("a.env", "two", 2)]);
+
+        let delta = state.remove(path);
+
+        assert_eq!(delta.resolved.len(), 2);
+        assert_eq!(state.finding_count(), 0);
+    }
+}
@@ -49,6 +49,67 @@ pub fn collect_files(paths: &[PathBuf], excludes: &[String], respect_gitignore:
     files
 }
 
+/// Returns the files among `targets` (absolute paths, or directories whose
+/// contents should be included) that [`collect_files`] would yield.
+///
+/// Only the directories leading to each target are walked, so gitignore and
+/// exclude rules apply exactly as in a full walk without visiting the rest
+/// of the tree.
+#[must_use]
+pub fn collect_files_among(
+    paths: &[PathBuf],
+    excludes: &[String],
+    respect_gitignore: bool,
+    targets: &[PathBuf],
+) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        let Ok(root) = std::path::absolute(path) else {
+            continue;
+        };
+        let relevant: Vec<PathBuf> = targets
+            .iter()
+            .filter(|t| t.starts_with(&root) || root.starts_with(t))
+            .cloned()
+            .collect();
+        if relevant.is_empty() {
+            continue;
+        }
+
+        if path.is_file() {
+            if !has_binary_extension(path) {
+                files.push(root);
+            }
+            continue;
+        }
+
+        let overrides = build_overrides(path, excludes);
+        let walker = build_walker_filtered(path, overrides, respect_gitignore, move |entry| {
+            std::path::absolute(entry.path())
+                .is_ok_and(|p| relevant.iter().any(|t| t.starts_with(&p) || p.starts_with(t)))
+        });
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        walker.run(|| {
+            let tx = tx.clone();
+            Box::new(move |result| {
+                if let Ok(entry) = result
+                    && is_scannable_file(&entry)
+                    && let Ok(absolute) = std::path::absolute(entry.path())
+                {
+                    let _ = tx.send(absolute);
+                }
+                ignore::WalkState::Continue
+            })
+        });
+        drop(tx);
+        files.extend(rx);
+    }
+
+    files
+}
+
 fn is_scannable_file(entry: &ignore::DirEntry) -> bool {
     entry.file_type().is_some_and(|ft| ft.is_file()) && !has_binary_extension(entry.path())
 }
@@ -108,6 +169,36 @@ fn read_large_file_mmap(file: &std::fs::File, _len: u64) -> Option<String> {
     std::str::from_utf8(&mmap).ok().map(String::from)
 }
 
+/// Returns whether a walk of `paths` would have reached `file` had it
+/// still existed: it lies under one of the paths and neither it nor a
+/// directory above it matches an exclude glob. Gitignore rules are not
+/// consulted, since a deleted file can no longer be checked against them.
+#[must_use]
+pub fn is_in_scope(paths: &[PathBuf], excludes: &[String], file: &Path) -> bool {
+    let Ok(file) = std::path::absolute(file) else {
+        return false;
+    };
+
+    paths.iter().any(|path| {
+        let Ok(root) = std::path::absolute(path) else {
+            return false;
+        };
+        if !file.starts_with(&root) {
+            return false;
+        }
+        if file == root {
+            return true;
+        }
+
+        let overrides = build_overrides(&root, excludes);
+        !file
+            .ancestors()
+            .take_while(|p| *p != root)
+            .enumerate()
+            .any(|(depth, p)| overrides.matched(p, depth > 0).is_ignore())
+    })
+}
+
 #[expect(
     clippy::expect_used,
     reason = "pattern format is validated by caller; programmer error if invalid"
@@ -123,13 +214,29 @@ fn build_overrides(path: &Path, excludes: &[String]) -> ignore::overrides::Overr
 }
 
 fn build_walker(path: &Path, overrides: ignore::overrides::Override, respect_gitignore: bool) -> ignore::WalkParallel {
-    WalkBuilder::new(path)
+    walk_builder(path, overrides, respect_gitignore).build_parallel()
+}
+
+fn build_walker_filtered(
+    path: &Path,
+    overrides: ignore::overrides::Override,
+    respect_gitignore: bool,
+    filter: impl Fn(&ignore::DirEntry) -> bool + Send + Sync + 'static,
+) -> ignore::WalkParallel {
+    walk_builder(path, overrides, respect_gitignore)
+        .filter_entry(filter)
+        .build_parallel()
+}
+
+fn walk_builder(path: &Path, overrides: ignore::overrides::Override, respect_gitignore: bool) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(path);
+    builder
         .hidden(false)
         .git_ignore(respect_gitignore)
         .git_global(respect_gitignore)
         .git_exclude(respect_gitignore)
-        .overrides(overrides)
-        .build_parallel()
+        .overrides(overrides);
+    builder
 }
 
 /// A single line of source code displayed alongside a finding.
@@ -412,4 +519,67 @@ mod tests {
         assert_eq!(second_line.content, "********");
         assert!(!second_line.is_finding);
     }
+
+    #[test]
+    fn is_in_scope_checks_paths_and_excludes() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().to_path_buf();
+        let excludes = vec!["vendor/".to_string(), "*.lock".to_string()];
+
+        assert!(is_in_scope(
+            &[root.join("src")],
+            &excludes,
+            &root.join("src/deleted.env")
+        ));
+        assert!(!is_in_scope(
+            &[root.join("src")],
+            &excludes,
+            &root.join("docs/deleted.env")
+        ));
+        assert!(!is_in_scope(
+            std::slice::from_ref(&root),
+            &excludes,
+            &root.join("vendor/lib/key.env")
+        ));
+        assert!(!is_in_scope(
+            std::slice::from_ref(&root),
+            &excludes,
+            &root.join("Cargo.lock")
+        ));
+        assert!(is_in_scope(
+            &[root.join("Cargo.lock")],
+            &excludes,
+            &root.join("Cargo.lock")
+        ));
+    }
+
+    #[test]
+    fn collect_files_among_applies_excludes_to_targets() {
+        let dir = TempDir::new().unwrap();
+        let root = std::path::absolute(dir.path()).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("vendor")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(root.join("src/other.rs"), "// untouched").unwrap();
+        std::fs::write(root.join("vendor/lib.rs"), "// vendored").unwrap();
+
+        let excludes = vec!["vendor/**".to_string()];
+        let targets = [root.join("src/main.rs"), root.join("vendor/lib.rs")];
+        let files = collect_files_among(std::slice::from_ref(&root), &excludes, true, &targets);
+
+        assert_eq!(files, [root.join("src/main.rs")]);
+    }
+
+    #[test]
+    fn collect_files_among_includes_files_in_changed_directories() {
+        let dir = TempDir::new().unwrap();
+        let root = std::path::absolute(dir.path()).unwrap();
+        std::fs::create_dir_all(root.join("a/b")).unwrap();
+        std::fs::write(root.join("a/b/one.rs"), "// one").unwrap();
+        std::fs::write(root.join("top.rs"), "// top").unwrap();
+
+        let files = collect_files_among(std::slice::from_ref(&root), &[], true, &[root.join("a")]);
+
+        assert_eq!(files, [root.join("a/b/one.rs")]);
+    }
 }
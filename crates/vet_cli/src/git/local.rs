@@ -1,16 +1,90 @@
 //! Thread-local git repository operations.
 
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
 
 use anyhow::Context as _;
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use gix::bstr::ByteSlice as _;
+use regex::Regex;
 
-use super::types::{ChangedFile, CommitInfo, ObjectId};
+use super::types::{ChangedFile, CommitInfo, ObjectId, WalkedCommit};
+use crate::HistoryInclude;
 use crate::commands::history::HistoryOptions;
 
 const DEFAULT_BINARY_THRESHOLD: usize = 512 * 1024;
 const BINARY_CHECK_LIMIT: usize = 8000;
+/// How far a commit's timestamp may precede its parent's before ref
+/// containment walks miss it.
+const CLOCK_SKEW_MARGIN_SECS: i64 = 24 * 60 * 60;
+/// Most commits walked looking for renames of moved baseline entries.
+const MAX_RENAME_COMMITS: usize = 10_000;
+
+/// Starting points for one walk, and the label for the commits it reaches.
+struct WalkSource {
+    label: Option<Arc<str>>,
+    tips: Vec<gix::ObjectId>,
+    hidden: Vec<gix::ObjectId>,
+}
+
+impl WalkSource {
+    fn new(label: Option<Arc<str>>, tips: Vec<gix::ObjectId>) -> Self {
+        Self {
+            label,
+            tips,
+            hidden: Vec::new(),
+        }
+    }
+
+    fn labelled(label: impl Into<Arc<str>>, tip: gix::ObjectId) -> Self {
+        Self::new(Some(label.into()), vec![tip])
+    }
+}
+
+/// The `--author` and `--committer` filters.
+struct PeopleFilter {
+    author: Option<Regex>,
+    committer: Option<Regex>,
+}
+
+impl PeopleFilter {
+    fn new(opts: &HistoryOptions) -> anyhow::Result<Self> {
+        let compile = |flag: &str, pattern: &Option<String>| {
+            pattern
+                .as_deref()
+                .map(|p| Regex::new(p).with_context(|| format!("invalid --{flag} pattern '{p}'")))
+                .transpose()
+        };
+
+        Ok(Self {
+            author: compile("author", &opts.author)?,
+            committer: compile("committer", &opts.committer)?,
+        })
+    }
+
+    /// Returns `true` if the commit's author and committer, as
+    /// `Name <email>`, match the patterns given.
+    fn matches(&self, repo: &gix::Repository, id: gix::ObjectId) -> bool {
+        if self.author.is_none() && self.committer.is_none() {
+            return true;
+        }
+
+        let Ok(commit) = repo.find_commit(id) else {
+            return false;
+        };
+
+        let signature_matches = |pattern: &Option<Regex>, signature: Result<gix::actor::SignatureRef<'_>, _>| {
+            pattern.as_ref().is_none_or(|pattern| {
+                signature.is_ok_and(|sig| pattern.is_match(&format!("{} <{}>", sig.name, sig.email)))
+            })
+        };
+
+        signature_matches(&self.author, commit.author()) && signature_matches(&self.committer, commit.committer())
+    }
+}
 
 /// Non-`Send` repository handle for single-threaded git operations.
 #[derive(Debug)]
@@ -19,6 +93,55 @@ pub struct LocalRepo {
 }
 
 impl LocalRepo {
+    /// Returns the configured `user.email`, if any.
+    #[must_use]
+    pub fn user_email(&self) -> Option<String> {
+        let config = self.inner.config_snapshot();
+        config.string("user.email").map(|email| email.to_string())
+    }
+
+    /// Registers a git merge driver called `name` in the repository's local
+    /// config, replacing any existing definition.
+    pub fn register_merge_driver(&self, name: &str, description: &str, command: &str) -> anyhow::Result<()> {
+        let path = self.inner.common_dir().join("config");
+        let mut config = gix::config::File::from_path_no_includes(path.clone(), gix::config::Source::Local)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let subsection = Some(name.into());
+        config.set_raw_value_by("merge", subsection, "name", description)?;
+        config.set_raw_value_by("merge", subsection, "driver", command)?;
+
+        let mut content = Vec::new();
+        config.write_to(&mut content)?;
+        std::fs::write(&path, content).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Returns `true` if the existing file at `path` lies in the working tree
+    /// and no ignore rule covers it, so `git add` would pick it up.
+    #[must_use]
+    pub fn is_unignored_worktree_path(&self, path: &Path) -> bool {
+        let Some(worktree) = self.inner.worktree() else {
+            return false;
+        };
+        let (Ok(root), Ok(path)) = (std::fs::canonicalize(worktree.base()), std::fs::canonicalize(path)) else {
+            return false;
+        };
+        let Ok(relative) = path.strip_prefix(&root) else {
+            return false;
+        };
+        if relative.starts_with(".git") {
+            return false;
+        }
+
+        // Err on the side of warning when the ignore rules cannot be read.
+        let Ok(mut excludes) = worktree.excludes(None) else {
+            return true;
+        };
+        excludes
+            .at_path(relative, Some(gix::index::entry::Mode::FILE))
+            .map_or(true, |platform| !platform.is_excluded())
+    }
+
     /// Returns paths of files in the index that differ from the HEAD tree.
     #[must_use]
     pub fn staged_files(&self) -> Vec<PathBuf> {
@@ -50,37 +173,86 @@ impl LocalRepo {
     }
 
     /// Walks the commit graph from the resolved starting points, collecting
-    /// object IDs up to the configured limit and date range.
+    /// commits up to the configured limit and date range, newest first.
+    ///
+    /// Each source in `--include` is walked after the branches, and labels
+    /// the commits no earlier source reached.
     #[expect(
         clippy::default_trait_access,
         reason = "CommitTimeOrder is a private type in gix; cannot name it explicitly"
     )]
-    pub fn collect_commits(&self, opts: &HistoryOptions) -> anyhow::Result<Vec<ObjectId>> {
-        let tips = self.resolve_walk_starting_points(opts)?;
-        if tips.is_empty() {
-            return Ok(Vec::new());
-        }
+    pub fn collect_commits(&self, opts: &HistoryOptions) -> anyhow::Result<Vec<WalkedCommit>> {
+        let sources = self.resolve_walk_sources(opts)?;
+        let people = PeopleFilter::new(opts)?;
+        let stop_at = opts.since.as_ref().and_then(|since| self.resolve_ref(since).ok());
+        let limit = opts.limit.unwrap_or(usize::MAX);
 
-        let mut walk = self
-            .inner
-            .rev_walk(tips)
-            .sorting(gix::revision::walk::Sorting::ByCommitTime(Default::default()));
+        let visited: Rc<RefCell<HashSet<gix::ObjectId>>> = Rc::default();
+        let mut walked = Vec::new();
+
+        for source in &sources {
+            let tips: Vec<_> = source
+                .tips
+                .iter()
+                .copied()
+                .filter(|tip| !visited.borrow().contains(tip))
+                .collect();
+            if tips.is_empty() {
+                continue;
+            }
+
+            let mut platform = self
+                .inner
+                .rev_walk(tips)
+                .sorting(gix::revision::walk::Sorting::ByCommitTime(Default::default()))
+                .with_hidden(source.hidden.iter().copied());
+
+            if opts.first_parent {
+                platform = platform.first_parent_only();
+            }
+
+            let seen = Rc::clone(&visited);
+            let walk = platform
+                .selected(move |id| !seen.borrow().contains(id))
+                .context("failed to start revision walk")?;
+
+            // Each walk is sorted by commit time, so no source can contribute
+            // more than `limit` commits to the newest `limit` overall.
+            let mut taken = 0;
+            for info in walk.flatten() {
+                if !visited.borrow_mut().insert(info.id) {
+                    continue;
+                }
 
-        if opts.first_parent {
-            walk = walk.first_parent_only();
+                let is_stop = stop_at.is_some_and(|id| info.id == id);
+                if !is_stop && !people.matches(&self.inner, info.id) {
+                    continue;
+                }
+
+                walked.push((info.id, info.commit_time.unwrap_or_default(), source.label.clone()));
+                taken += 1;
+
+                if is_stop || taken >= limit {
+                    break;
+                }
+            }
         }
 
-        let stop_at = opts.since.as_ref().and_then(|since| self.resolve_ref(since).ok());
+        if sources.len() > 1 {
+            walked.sort_by_key(|&(_, time, _)| std::cmp::Reverse(time));
+        }
 
-        let limit = opts.limit.unwrap_or(usize::MAX);
         let mut commits = Vec::with_capacity(limit.min(1024));
 
-        for info in walk.all().context("failed to start revision walk")?.flatten() {
-            if stop_at.is_some_and(|id| info.id == id) {
+        for (id, _, source) in walked {
+            if stop_at.is_some_and(|stop| id == stop) {
                 break;
             }
 
-            commits.push(ObjectId::from_raw(info.id));
+            commits.push(WalkedCommit {
+                id: ObjectId::from_raw(id),
+                source,
+            });
 
             if commits.len() >= limit {
                 break;
@@ -90,8 +262,165 @@ impl LocalRepo {
         Ok(commits)
     }
 
-    /// Returns the files added or modified in the given commit by diffing
-    /// against its first parent tree.
+    /// Returns the note blob attached to each annotated object, across every
+    /// ref under `refs/notes/`.
+    #[must_use]
+    pub fn notes(&self) -> HashMap<ObjectId, ObjectId> {
+        let mut notes = HashMap::new();
+
+        for (name, id) in self.all_ref_tips() {
+            if !name.starts_with("refs/notes/") {
+                continue;
+            }
+
+            let Some(tree) = self.inner.find_commit(id).ok().and_then(|commit| commit.tree_id().ok()) else {
+                continue;
+            };
+
+            // Notes trees fan out into directories named after the leading
+            // hex digits of the annotated object, like `ab/cdef…`.
+            let mut stack = vec![(tree.detach(), String::new())];
+            while let Some((id, prefix)) = stack.pop() {
+                let Ok(tree) = self.inner.find_tree(id) else {
+                    continue;
+                };
+
+                for entry in tree.iter().flatten() {
+                    let name = format!("{prefix}{}", entry.filename());
+                    if entry.mode().is_tree() {
+                        stack.push((entry.object_id(), name));
+                    } else if let Some(annotated) = ObjectId::from_hex(&name) {
+                        notes.insert(annotated, ObjectId::from_raw(entry.object_id()));
+                    }
+                }
+            }
+        }
+
+        notes
+    }
+
+    /// Returns each annotated tag as its short name, the tag object and the
+    /// commit it points to.
+    #[must_use]
+    pub fn annotated_tags(&self) -> Vec<(String, ObjectId, ObjectId)> {
+        let Ok(refs) = self.inner.references() else {
+            return Vec::new();
+        };
+
+        refs.tags()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|mut reference| {
+                let tag = reference.target().try_id()?.to_owned();
+                let header = self.inner.find_header(tag).ok()?;
+                if header.kind() != gix::object::Kind::Tag {
+                    return None;
+                }
+
+                let commit = reference.peel_to_id().ok()?.detach();
+                self.is_commit(commit).then(|| {
+                    (
+                        reference.name().shorten().to_string(),
+                        ObjectId::from_raw(tag),
+                        ObjectId::from_raw(commit),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the loose blobs that nothing references: no tree of a commit
+    /// reachable from a ref, a reflog entry or a loose commit, and not the
+    /// index. Each is paired with a stand-in commit describing it.
+    #[must_use]
+    pub fn dangling_blobs(&self) -> Vec<(ObjectId, CommitInfo)> {
+        let mut candidates: HashSet<gix::ObjectId> = self.loose_objects(gix::object::Kind::Blob).into_iter().collect();
+
+        if let Ok(index) = self.inner.index_or_empty() {
+            for entry in index.entries() {
+                candidates.remove(&entry.id);
+            }
+        }
+
+        for (_, id) in self.all_ref_tips() {
+            candidates.remove(&id);
+        }
+
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let mut tips = self.referenced_commits();
+        tips.extend(self.loose_objects(gix::object::Kind::Commit));
+
+        if let Ok(walk) = self.inner.rev_walk(tips).all() {
+            let mut seen_trees = HashSet::new();
+            for info in walk.flatten() {
+                if let Ok(commit) = self.inner.find_commit(info.id)
+                    && let Ok(tree) = commit.tree_id()
+                {
+                    self.forget_referenced_blobs(tree.detach(), &mut seen_trees, &mut candidates);
+                }
+
+                if candidates.is_empty() {
+                    break;
+                }
+            }
+        }
+
+        let mut dangling: Vec<_> = candidates
+            .into_iter()
+            .map(|id| {
+                let blob = ObjectId::from_raw(id);
+                (blob, CommitInfo::for_dangling_blob(blob, self.loose_object_written(id)))
+            })
+            .collect();
+        dangling.sort_by_key(|(blob, _)| *blob);
+        dangling
+    }
+
+    /// Returns the files renamed by first-parent commits made at or after
+    /// `since`, mapping each original path to where it ended up.
+    ///
+    /// The walk goes back from HEAD and stops as soon as every path in
+    /// `wanted` has been traced, or after [`MAX_RENAME_COMMITS`] commits.
+    #[must_use]
+    pub fn renames_since(&self, since: DateTime<Utc>, wanted: &HashSet<String>) -> HashMap<String, String> {
+        let mut renames: HashMap<String, String> = HashMap::new();
+        let mut next = self.inner.head_commit().ok();
+
+        for _ in 0..MAX_RENAME_COMMITS {
+            let Some(commit) = next else {
+                break;
+            };
+            if commit.time().map_or(true, |time| time.seconds < since.timestamp())
+                || wanted.iter().all(|path| renames.contains_key(path))
+            {
+                break;
+            }
+
+            // Newer renames are already recorded, so each path maps straight
+            // to where it ends up and the newest rename of a path wins.
+            if let (Some(parent_tree), Ok(tree)) = (self.first_parent_tree(&commit), commit.tree()) {
+                for (from, to) in Self::renamed_paths(&parent_tree, &tree) {
+                    let target = renames.get(&to).cloned().unwrap_or(to);
+                    renames.entry(from).or_insert(target);
+                }
+            }
+
+            next = commit
+                .parent_ids()
+                .next()
+                .and_then(|pid| self.inner.find_commit(pid).ok());
+        }
+
+        renames.retain(|from, to| from != to);
+        renames
+    }
+
+    /// Returns the files added, modified, deleted or renamed in the given
+    /// commit by diffing against its first parent tree.
     #[must_use]
     pub fn commit_changes(&self, commit_id: ObjectId) -> Vec<ChangedFile> {
         let Ok(commit) = self.inner.find_commit(commit_id.into_raw()) else {
@@ -110,8 +439,8 @@ impl LocalRepo {
         Self::diff_trees(&from_tree, &tree)
     }
 
-    /// Reads a blob as UTF-8 text, returning `None` if it exceeds `max_bytes`
-    /// or appears to be binary.
+    /// Reads a blob, or the message of a commit or annotated tag, as UTF-8
+    /// text, returning `None` if it exceeds `max_bytes` or appears to be binary.
     #[must_use]
     pub fn read_blob_as_text(&self, oid: ObjectId, max_bytes: Option<u64>) -> Option<String> {
         self.read_blob_as_string(oid.into_raw(), max_bytes)
@@ -126,24 +455,92 @@ impl LocalRepo {
             .map(|c| CommitInfo::from_gix_commit(&c))
     }
 
+    /// Returns the local branches and tags whose history contains each of the
+    /// given commits, as full ref names keyed by commit.
+    #[must_use]
+    pub fn refs_containing(&self, commits: &[ObjectId]) -> HashMap<ObjectId, Vec<String>> {
+        let targets: HashSet<gix::ObjectId> = commits.iter().map(|c| c.into_raw()).collect();
+        let mut containing: HashMap<ObjectId, Vec<String>> = HashMap::new();
+
+        // Nothing committed before the oldest target can lead to one, so each
+        // walk stops there, less a margin for commits with skewed clocks.
+        let Some(oldest) = targets
+            .iter()
+            .filter_map(|id| self.inner.find_commit(*id).ok()?.time().ok())
+            .map(|time| time.seconds)
+            .min()
+        else {
+            return containing;
+        };
+        let sorting = gix::revision::walk::Sorting::ByCommitTimeCutoff {
+            order: gix::traverse::commit::simple::CommitTimeOrder::NewestFirst,
+            seconds: oldest - CLOCK_SKEW_MARGIN_SECS,
+        };
+
+        for (name, tip) in self.branch_and_tag_tips() {
+            let Ok(walk) = self.inner.rev_walk([tip]).sorting(sorting).all() else {
+                continue;
+            };
+
+            let mut remaining = targets.clone();
+            for info in walk.flatten() {
+                if remaining.remove(&info.id) {
+                    containing
+                        .entry(ObjectId::from_raw(info.id))
+                        .or_default()
+                        .push(name.clone());
+
+                    if remaining.is_empty() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        containing
+    }
+
+    /// Returns the blob each of `paths` points to in the `HEAD` tree, leaving
+    /// out paths that do not exist there.
+    #[must_use]
+    pub fn blobs_at_head<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) -> HashMap<PathBuf, ObjectId> {
+        let Ok(tree) = self.inner.head_tree() else {
+            return HashMap::new();
+        };
+
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let entry = tree.lookup_entry_by_path(path).ok().flatten()?;
+                Some((path.to_path_buf(), ObjectId::from_raw(entry.object_id())))
+            })
+            .collect()
+    }
+
     fn read_blob_as_string(&self, oid: gix::ObjectId, max_bytes: Option<u64>) -> Option<String> {
-        let blob = self.inner.find_blob(oid).ok()?;
+        let object = self.inner.find_object(oid).ok()?;
+        let data = match object.kind {
+            gix::object::Kind::Blob => object.data.as_slice(),
+            gix::object::Kind::Commit => gix::objs::CommitRef::from_bytes(&object.data).ok()?.message,
+            gix::object::Kind::Tag => gix::objs::TagRef::from_bytes(&object.data).ok()?.message,
+            gix::object::Kind::Tree => return None,
+        };
 
         #[expect(
             clippy::cast_possible_truncation,
             reason = "max_bytes values are practical file sizes well within usize"
         )]
         if let Some(max) = max_bytes
-            && blob.data.len() > max as usize
+            && data.len() > max as usize
         {
             return None;
         }
 
-        if self.is_binary_blob(&blob.data) {
+        if self.is_binary_blob(data) {
             return None;
         }
 
-        String::from_utf8(blob.data.clone()).ok()
+        String::from_utf8(data.to_vec()).ok()
     }
 
     fn is_binary_blob(&self, data: &[u8]) -> bool {
@@ -161,16 +558,228 @@ impl LocalRepo {
         data[..check_len].contains(&0)
     }
 
-    fn resolve_walk_starting_points(&self, opts: &HistoryOptions) -> anyhow::Result<Vec<gix::ObjectId>> {
-        if opts.until != "HEAD" {
-            return self.resolve_single_ref(&opts.until);
+    fn resolve_walk_sources(&self, opts: &HistoryOptions) -> anyhow::Result<Vec<WalkSource>> {
+        let mut sources = vec![self.resolve_walk_starting_points(opts)?];
+
+        let include = |source| opts.include.contains(&source);
+
+        if include(HistoryInclude::Stash) {
+            let mut entries = self.reflog_entries("refs/stash");
+            if entries.is_empty()
+                && let Ok(stash) = self.inner.find_reference("refs/stash")
+            {
+                entries.push(stash.id().detach());
+            }
+            sources.extend(
+                entries
+                    .into_iter()
+                    .enumerate()
+                    .map(|(n, id)| WalkSource::labelled(format!("stash@{{{n}}}"), id)),
+            );
+        }
+
+        if include(HistoryInclude::Tags) {
+            sources.extend(self.ref_sources("refs/tags/"));
+        }
+
+        if include(HistoryInclude::Remotes) {
+            sources.extend(self.ref_sources("refs/remotes/"));
+        }
+
+        if include(HistoryInclude::Reflog) {
+            let names =
+                std::iter::once("HEAD".to_string()).chain(self.all_ref_tips().into_iter().map(|(name, _)| name));
+            for name in names {
+                sources.extend(
+                    self.reflog_entries(&name)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(n, id)| WalkSource::labelled(format!("{name}@{{{n}}}"), id)),
+                );
+            }
+        }
+
+        if include(HistoryInclude::Unreachable) {
+            sources.push(WalkSource {
+                label: Some(Arc::from("unreachable")),
+                tips: self.loose_objects(gix::object::Kind::Commit),
+                hidden: self.referenced_commits(),
+            });
+        }
+
+        for source in &mut sources {
+            source.tips.retain(|&id| self.is_commit(id));
+        }
+
+        Ok(sources)
+    }
+
+    fn resolve_walk_starting_points(&self, opts: &HistoryOptions) -> anyhow::Result<WalkSource> {
+        if let Some(range) = &opts.range {
+            return self.resolve_range(range);
+        }
+
+        let tips = if opts.until != "HEAD" {
+            self.resolve_single_ref(&opts.until)?
+        } else if let Some(branch) = &opts.branch {
+            self.resolve_branch(branch)?
+        } else {
+            self.resolve_all_local_branches()
+        };
+
+        Ok(WalkSource::new(None, tips))
+    }
+
+    /// Resolves `A..B` (in B but not A), `A...B` (in either since their merge
+    /// bases) or a single ref. A missing side defaults to `HEAD`, as in git.
+    fn resolve_range(&self, range: &str) -> anyhow::Result<WalkSource> {
+        let end = |name: &str| {
+            let name = if name.is_empty() { "HEAD" } else { name };
+            self.resolve_ref(name)
+                .map_err(|_err| anyhow::anyhow!("cannot resolve '{name}' in range '{range}'"))
+        };
+
+        if let Some((from, to)) = range.split_once("...") {
+            let (from, to) = (end(from)?, end(to)?);
+            let bases = self
+                .inner
+                .merge_bases_many(from, &[to])
+                .map(|bases| bases.into_iter().map(gix::Id::detach).collect())
+                .unwrap_or_default();
+            return Ok(WalkSource {
+                label: None,
+                tips: vec![from, to],
+                hidden: bases,
+            });
+        }
+
+        if let Some((from, to)) = range.split_once("..") {
+            return Ok(WalkSource {
+                label: None,
+                tips: vec![end(to)?],
+                hidden: vec![end(from)?],
+            });
+        }
+
+        Ok(WalkSource::new(None, vec![end(range)?]))
+    }
+
+    /// One source per ref under `prefix`, labelled with its full name.
+    fn ref_sources(&self, prefix: &str) -> Vec<WalkSource> {
+        self.all_ref_tips()
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(prefix))
+            .map(|(name, id)| WalkSource::labelled(name, id))
+            .collect()
+    }
+
+    /// Every commit a ref or reflog entry points to.
+    fn referenced_commits(&self) -> Vec<gix::ObjectId> {
+        let mut commits = self.reflog_entries("HEAD");
+        if let Ok(head) = self.inner.head_id() {
+            commits.push(head.detach());
         }
 
-        if let Some(branch) = &opts.branch {
-            return self.resolve_branch(branch);
+        for (name, id) in self.all_ref_tips() {
+            commits.push(id);
+            commits.extend(self.reflog_entries(&name));
         }
 
-        Ok(self.resolve_all_local_branches())
+        commits.retain(|&id| self.is_commit(id));
+        commits.sort();
+        commits.dedup();
+        commits
+    }
+
+    /// Every ref, by full name, peeled to the object it points to.
+    fn all_ref_tips(&self) -> Vec<(String, gix::ObjectId)> {
+        let Ok(refs) = self.inner.references() else {
+            return Vec::new();
+        };
+
+        refs.all()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|mut reference| {
+                let id = reference.peel_to_id().ok()?.detach();
+                Some((reference.name().as_bstr().to_string(), id))
+            })
+            .collect()
+    }
+
+    /// The objects a ref's reflog entries moved it to, most recent first.
+    fn reflog_entries(&self, name: &str) -> Vec<gix::ObjectId> {
+        let Ok(reference) = self.inner.find_reference(name) else {
+            return Vec::new();
+        };
+
+        let mut log = reference.log_iter();
+        let Ok(Some(lines)) = log.rev() else {
+            return Vec::new();
+        };
+
+        lines.flatten().map(|line| line.new_oid).collect()
+    }
+
+    fn loose_objects(&self, kind: gix::object::Kind) -> Vec<gix::ObjectId> {
+        let store = gix::odb::loose::Store::at(self.objects_dir(), self.inner.object_hash());
+
+        store
+            .iter()
+            .flatten()
+            .filter(|&id| self.inner.find_header(id).is_ok_and(|header| header.kind() == kind))
+            .collect()
+    }
+
+    fn loose_object_written(&self, id: gix::ObjectId) -> DateTime<Utc> {
+        let hex = id.to_string();
+        let (dir, file) = hex.split_at(2);
+
+        std::fs::metadata(self.objects_dir().join(dir).join(file))
+            .and_then(|meta| meta.modified())
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_default()
+    }
+
+    fn objects_dir(&self) -> PathBuf {
+        self.inner.common_dir().join("objects")
+    }
+
+    fn is_commit(&self, id: gix::ObjectId) -> bool {
+        self.inner
+            .find_header(id)
+            .is_ok_and(|header| header.kind() == gix::object::Kind::Commit)
+    }
+
+    /// Removes from `candidates` every blob under `tree`, skipping trees
+    /// already seen.
+    fn forget_referenced_blobs(
+        &self,
+        tree: gix::ObjectId,
+        seen: &mut HashSet<gix::ObjectId>,
+        candidates: &mut HashSet<gix::ObjectId>,
+    ) {
+        let mut stack = vec![tree];
+
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+
+            let Ok(tree) = self.inner.find_tree(id) else {
+                continue;
+            };
+
+            for entry in tree.iter().flatten() {
+                let mode = entry.mode();
+                if mode.is_tree() {
+                    stack.push(entry.object_id());
+                } else if !mode.is_commit() {
+                    candidates.remove(&entry.object_id());
+                }
+            }
+        }
     }
 
     fn resolve_single_ref(&self, reference: &str) -> anyhow::Result<Vec<gix::ObjectId>> {
@@ -207,6 +816,23 @@ impl LocalRepo {
         tips
     }
 
+    fn branch_and_tag_tips(&self) -> Vec<(String, gix::ObjectId)> {
+        let Ok(refs) = self.inner.references() else {
+            return Vec::new();
+        };
+
+        let branches = refs.local_branches().into_iter().flatten().flatten();
+        let tags = refs.tags().into_iter().flatten().flatten();
+
+        branches
+            .chain(tags)
+            .filter_map(|mut reference| {
+                let id = reference.peel_to_id().ok()?.detach();
+                Some((reference.name().as_bstr().to_string(), id))
+            })
+            .collect()
+    }
+
     fn resolve_ref(&self, reference: &str) -> anyhow::Result<gix::ObjectId> {
         if let Ok(date) = NaiveDate::parse_from_str(reference, "%Y-%m-%d") {
             return self.find_commit_before_date(date);
@@ -276,6 +902,33 @@ impl LocalRepo {
             .collect()
     }
 
+    fn renamed_paths(from: &gix::Tree<'_>, to: &gix::Tree<'_>) -> Vec<(String, String)> {
+        let Ok(mut changes) = from.changes() else {
+            return Vec::new();
+        };
+
+        let mut renamed = Vec::new();
+
+        let _ = changes.for_each_to_obtain_tree(to, |change| {
+            if let gix::object::tree::diff::Change::Rewrite {
+                source_location,
+                location,
+                copy: false,
+                ..
+            } = change
+            {
+                renamed.push((
+                    source_location.to_str_lossy().into_owned(),
+                    location.to_str_lossy().into_owned(),
+                ));
+            }
+
+            Ok::<_, std::convert::Infallible>(std::ops::ControlFlow::Continue(()))
+        });
+
+        renamed
+    }
+
     fn diff_trees(from: &gix::Tree<'_>, to: &gix::Tree<'_>) -> Vec<ChangedFile> {
         let Ok(mut changes) = from.changes() else {
             return Vec::new();
@@ -286,16 +939,51 @@ impl LocalRepo {
         let _ = changes.for_each_to_obtain_tree(to, |change| {
             use gix::object::tree::diff::Change;
 
+            let raw = |id: gix::Id<'_>| ObjectId::from_raw(id.detach());
+            let path = |location: &gix::bstr::BStr| PathBuf::from(location.to_str_lossy().into_owned());
+
             match change {
-                Change::Addition { location, id, .. }
-                | Change::Modification { location, id, .. }
-                | Change::Rewrite { location, id, .. } => {
+                Change::Addition { location, id, .. } => entries.push(ChangedFile {
+                    path: path(location),
+                    blob_id: Some(raw(id)),
+                    previous_blob_id: None,
+                }),
+                Change::Modification {
+                    location,
+                    previous_id,
+                    id,
+                    ..
+                } => entries.push(ChangedFile {
+                    path: path(location),
+                    blob_id: Some(raw(id)),
+                    previous_blob_id: Some(raw(previous_id)),
+                }),
+                Change::Deletion { location, id, .. } => entries.push(ChangedFile {
+                    path: path(location),
+                    blob_id: None,
+                    previous_blob_id: Some(raw(id)),
+                }),
+                Change::Rewrite {
+                    source_location,
+                    source_id,
+                    location,
+                    id,
+                    copy,
+                    ..
+                } => {
+                    if !copy {
+                        entries.push(ChangedFile {
+                            path: path(source_location),
+                            blob_id: None,
+                            previous_blob_id: Some(raw(source_id)),
+                        });
+                    }
                     entries.push(ChangedFile {
-                        path: PathBuf::from(location.to_str_lossy().into_owned()),
-                        blob_id: ObjectId::from_raw(id.detach()),
+                        path: path(location),
+                        blob_id: Some(raw(id)),
+                        previous_blob_id: None,
                     });
                 }
-                Change::Deletion { .. } => {}
             }
 
             Ok::<_, std::convert::Infallible>(std::ops::ControlFlow::Continue(()))
@@ -8,7 +8,7 @@ use std::path::{Path, PathBuf};
 use gix::ThreadSafeRepository;
 
 pub use self::local::LocalRepo;
-pub use self::types::{CommitInfo, ObjectId};
+pub use self::types::{CommitInfo, ObjectId, WalkedCommit};
 use crate::commands::history::HistoryOptions;
 
 /// Default object cache size for tree diffs (64 MB).
@@ -48,34 +48,110 @@ impl Repo {
         LocalRepo { inner: repo }
     }
 
+    /// Returns the repository's `.git` directory.
+    #[must_use]
+    pub fn git_dir(&self) -> &Path {
+        self.inner.git_dir()
+    }
+
+    /// Returns the repository's working tree, or `None` for a bare repository.
+    #[must_use]
+    pub fn work_dir(&self) -> Option<&Path> {
+        self.inner.work_dir()
+    }
+
     /// Returns `true` if this is a shallow clone with truncated history.
     #[must_use]
     pub fn is_shallow(&self) -> bool {
         self.inner.to_thread_local().is_shallow()
     }
 
-    /// Collects commit object IDs matching the given history options.
-    pub fn collect_commits(&self, opts: &HistoryOptions) -> anyhow::Result<Vec<ObjectId>> {
+    /// Collects the commits matching the given history options.
+    pub fn collect_commits(&self, opts: &HistoryOptions) -> anyhow::Result<Vec<WalkedCommit>> {
         self.thread_local().collect_commits(opts)
     }
 }
 
 fn compute_cache_size(repo: &gix::Repository) -> usize {
-    repo.index_or_empty()
-        .map(|idx| repo.compute_object_cache_size_for_tree_diffs(&idx))
-        .unwrap_or(DEFAULT_CACHE_SIZE)
+    repo.index_or_empty().map_or(DEFAULT_CACHE_SIZE, |idx| {
+        repo.compute_object_cache_size_for_tree_diffs(&idx)
+    })
 }
 
 fn configure_cache(repo: &mut gix::Repository, size: usize) {
     repo.object_cache_size_if_unset(size);
 }
 
+/// A git repository found beneath a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundRepo {
+    /// The working tree, or the repository directory itself when bare.
+    pub path: PathBuf,
+    /// Whether the repository has no working tree.
+    pub bare: bool,
+}
+
+/// Finds every git repository at or beneath `root`, sorted by path.
+///
+/// A directory containing `.git` is a repository with a working tree; one
+/// containing `HEAD`, `objects` and `refs` is a bare repository. Neither is
+/// searched further, and symlinks are not followed.
+pub fn find_repositories(root: &Path) -> std::io::Result<Vec<FoundRepo>> {
+    let mut found = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        if dir.join(".git").exists() {
+            found.push(FoundRepo { path: dir, bare: false });
+            continue;
+        }
+
+        if is_bare_repository(&dir) {
+            found.push(FoundRepo { path: dir, bare: true });
+            continue;
+        }
+
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if dir == root => return Err(e),
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                pending.push(entry.path());
+            }
+        }
+    }
+
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(found)
+}
+
+fn is_bare_repository(dir: &Path) -> bool {
+    dir.join("HEAD").is_file() && dir.join("objects").is_dir() && dir.join("refs").is_dir()
+}
+
 /// Returns `true` if the current working directory is inside a git repository.
 #[must_use]
 pub fn in_repo() -> bool {
     Repo::open_cwd().is_some()
 }
 
+/// Returns the `user.email` configured for the repository containing the
+/// current working directory.
+#[must_use]
+pub fn user_email() -> Option<String> {
+    Repo::open_cwd()?.thread_local().user_email()
+}
+
+/// Registers a merge driver in the local config of the repository
+/// containing the current working directory.
+pub fn register_merge_driver(name: &str, description: &str, command: &str) -> anyhow::Result<()> {
+    let repo = Repo::open_cwd().ok_or_else(|| anyhow::anyhow!("not a git repository"))?;
+    repo.thread_local().register_merge_driver(name, description, command)
+}
+
 /// Returns the list of staged file paths, or `None` if not in a repository.
 #[must_use]
 pub fn staged_files() -> Option<Vec<PathBuf>> {
@@ -1,6 +1,8 @@
 //! Git object types used throughout the CLI.
 
+use std::fmt;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use gix::bstr::ByteSlice as _;
@@ -8,10 +10,16 @@ use gix::bstr::ByteSlice as _;
 const SHORT_HASH_LENGTH: usize = 7;
 
 /// Wrapper around a raw git object ID.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ObjectId(pub(super) gix::ObjectId);
 
 impl ObjectId {
+    /// Parses a full hex object ID, returning `None` if it is malformed.
+    #[must_use]
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        gix::ObjectId::from_hex(hex.as_bytes()).ok().map(Self)
+    }
+
     pub(super) fn from_raw(oid: gix::ObjectId) -> Self {
         Self(oid)
     }
@@ -21,18 +29,41 @@ impl ObjectId {
     }
 }
 
-/// A file that was added, modified, or rewritten in a commit.
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A file that was added, modified, deleted or renamed in a commit.
 #[derive(Debug, Clone)]
 pub struct ChangedFile {
     /// Relative path within the repository.
     pub path: PathBuf,
-    /// Object ID of the file blob at this commit.
-    pub blob_id: ObjectId,
+    /// Object ID of the file blob at this commit, or `None` if the commit
+    /// deleted the file or renamed it away.
+    pub blob_id: Option<ObjectId>,
+    /// Object ID of the file blob in the first parent, or `None` if the
+    /// commit added the file.
+    pub previous_blob_id: Option<ObjectId>,
+}
+
+/// A commit reached by the history walk.
+#[derive(Debug, Clone)]
+pub struct WalkedCommit {
+    /// Object ID of the commit.
+    pub id: ObjectId,
+    /// The stash entry, tag, remote branch, reflog entry or `unreachable`
+    /// the commit was first reached through, or `None` if the branch walk
+    /// reached it.
+    pub source: Option<Arc<str>>,
 }
 
 /// Metadata extracted from a git commit for display purposes.
 #[derive(Debug, Clone)]
 pub struct CommitInfo {
+    /// Object ID of the commit.
+    pub id: ObjectId,
     /// Full hex SHA-1 hash.
     pub hash: String,
     /// Abbreviated hash (first 7 characters).
@@ -66,6 +97,7 @@ impl CommitInfo {
         let message = extract_first_line(commit);
 
         Self {
+            id: ObjectId::from_raw(commit.id),
             hash,
             short_hash,
             author_name,
@@ -74,6 +106,24 @@ impl CommitInfo {
             message,
         }
     }
+
+    /// Describes a dangling blob as if it were a commit, so that secrets in
+    /// blobs no commit references can be reported like any other. The date
+    /// is when the loose object was written.
+    pub(super) fn for_dangling_blob(blob: ObjectId, written: DateTime<Utc>) -> Self {
+        let hash = blob.to_string();
+        let short_hash = hash.get(..SHORT_HASH_LENGTH).unwrap_or(&hash).to_string();
+
+        Self {
+            id: blob,
+            hash,
+            short_hash,
+            author_name: "unknown".to_string(),
+            author_email: "unknown".to_string(),
+            date: written,
+            message: "dangling blob".to_string(),
+        }
+    }
 }
 
 fn extract_first_line(commit: &gix::Commit<'_>) -> String {
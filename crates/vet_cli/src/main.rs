@@ -5,7 +5,8 @@
 //! - `vet history` - Scan commits in git repository for secrets
 //! - `vet init` - Create configuration file
 //! - `vet hook` - Manage git pre-commit hooks
-//! - `vet patterns` - List detection patterns
+//! - `vet patterns` - List or import detection patterns
+//! - `vet watch` - Rescan files as they change
 
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
@@ -13,11 +14,13 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 mod commands;
 mod files;
 mod git;
+mod report;
 mod scanning;
 mod ui;
 
 use std::path::PathBuf;
 
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
 use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use console::style;
 pub use vet_core::CONFIG_FILENAME;
@@ -33,6 +36,26 @@ fn parse_confidence(s: &str) -> Result<Confidence, String> {
     }
 }
 
+/// Parses an expiry given as a `YYYY-MM-DD` date (midnight UTC) or as a
+/// number of days from now (`90d`).
+fn parse_expiry(s: &str) -> Result<DateTime<Utc>, String> {
+    let invalid = || format!("invalid expiry '{s}' (expected YYYY-MM-DD or a number of days like 90d)");
+
+    if let Some(days) = s.strip_suffix('d') {
+        let days: i64 = days.parse().map_err(|_err| invalid())?;
+        if days < 0 {
+            return Err(format!("invalid expiry '{s}' (number of days must not be negative)"));
+        }
+        return TimeDelta::try_days(days)
+            .and_then(|delta| Utc::now().checked_add_signed(delta))
+            .ok_or_else(|| format!("expiry '{s}' is out of range"));
+    }
+
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+        .map_err(|_err| invalid())
+}
+
 const REPO_URL: &str = "https://github.com/spikermint/vet";
 
 #[derive(Debug, Parser)]
@@ -61,7 +84,7 @@ enum Command {
     Init(InitArgs),
 
     #[command(visible_alias = "h")]
-    History(HistoryArgs),
+    History(Box<HistoryArgs>),
 
     #[command(visible_alias = "b")]
     Baseline(BaselineArgs),
@@ -70,6 +93,9 @@ enum Command {
         #[command(subcommand)]
         command: Option<HookCommand>,
     },
+
+    #[command(visible_alias = "w")]
+    Watch(WatchArgs),
 }
 
 /// Output format for scan and history results.
@@ -82,6 +108,43 @@ pub enum OutputFormat {
     Json,
     /// SARIF (Static Analysis Results Interchange Format).
     Sarif,
+    /// GitLab Secret Detection report (`gl-secret-detection-report.json`).
+    Gitlab,
+    /// GitLab Code Quality (Code Climate) report.
+    CodeQuality,
+    /// `JUnit` XML, one failed test case per finding.
+    Junit,
+    /// Comma-separated values, one row per finding.
+    Csv,
+    /// Markdown report grouped by file.
+    Markdown,
+    /// GitHub Actions annotations, plus a job summary when `$GITHUB_STEP_SUMMARY` is set.
+    Github,
+}
+
+/// Extra places `vet history` looks for commits beyond the branch walk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum HistoryInclude {
+    /// Every entry in the stash (`stash@{n}`).
+    Stash,
+    /// Every tag.
+    Tags,
+    /// Remote-tracking branches.
+    Remotes,
+    /// Every entry in the reflogs of `HEAD` and all refs.
+    Reflog,
+    /// Loose commits and blobs no ref or reflog can reach.
+    Unreachable,
+}
+
+/// What `vet history` reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum HistoryReport {
+    /// Each secret with where it was introduced.
+    #[default]
+    Findings,
+    /// Secret counts by author, top-level directory, pattern group and year.
+    Summary,
 }
 
 /// Arguments for the `vet scan` command.
@@ -147,9 +210,18 @@ pub struct ScanArgs {
     #[arg(long)]
     pub allow_new: bool,
 
+    /// Warn about baseline entries that no longer match any finding.
+    #[arg(long)]
+    pub report_stale_baseline: bool,
+
     /// Verify detected secrets against provider APIs.
     #[arg(long)]
     pub verify: bool,
+
+    /// Scan every git repository beneath this directory, each with its own
+    /// configuration and baseline. Only text and json output can be combined.
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["paths", "staged"])]
+    pub repos: Option<PathBuf>,
 }
 
 /// Arguments for the `vet fix` command.
@@ -182,11 +254,32 @@ pub struct FixArgs {
     /// Skip files larger than this size in bytes.
     #[arg(long)]
     pub max_file_size: Option<u64>,
+
+    /// Action for findings without a `[fix]` policy (redact, placeholder, extract-env, delete-line, ignore, skip).
+    #[arg(long, value_name = "ACTION", requires = "yes")]
+    pub action: Option<FixPolicy>,
+
+    /// Apply `[fix]` policies and `--action` without prompting.
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Print a JSON summary of changes instead of text output.
+    #[arg(long, requires = "yes")]
+    pub json: bool,
+
+    /// Restore the files changed by a fix session (the latest if omitted).
+    #[arg(long, value_name = "SESSION", num_args = 0..=1, conflicts_with_all = ["yes", "dry_run"])]
+    pub undo: Option<Option<String>>,
 }
 
 /// Arguments for the `vet history` command.
 #[derive(Debug, Parser)]
 pub struct HistoryArgs {
+    /// Revision range to scan: `A..B` for commits in B but not A, `A...B`
+    /// for commits on either side since their merge base, or a single ref.
+    #[arg(value_name = "RANGE", conflicts_with_all = ["since", "until", "branch"])]
+    pub range: Option<String>,
+
     /// Maximum number of commits to scan.
     #[arg(short = 'n', long, value_name = "N")]
     pub limit: Option<usize>,
@@ -207,10 +300,32 @@ pub struct HistoryArgs {
     #[arg(long)]
     pub first_parent: bool,
 
+    /// Only scan commits whose author matches this regular expression.
+    #[arg(long, value_name = "PATTERN")]
+    pub author: Option<String>,
+
+    /// Only scan commits whose committer matches this regular expression.
+    #[arg(long, value_name = "PATTERN")]
+    pub committer: Option<String>,
+
+    /// Only scan files matching this pathspec (repeatable).
+    #[arg(long = "path", value_name = "PATHSPEC")]
+    pub paths: Vec<String>,
+
+    /// Also walk commits reachable from these sources (comma-separated).
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "SOURCES")]
+    pub include: Vec<HistoryInclude>,
+
     /// Scan all branches and refs.
     #[arg(long)]
     pub all: bool,
 
+    /// Scan the history of every git repository beneath this directory,
+    /// bare or not, each with its own configuration. Only text and json
+    /// output can be combined.
+    #[arg(long, value_name = "DIR")]
+    pub repos: Option<PathBuf>,
+
     /// Output format.
     #[arg(short, long, value_enum, default_value_t)]
     pub format: OutputFormat,
@@ -219,6 +334,28 @@ pub struct HistoryArgs {
     #[arg(short, long)]
     pub output: Option<PathBuf>,
 
+    /// Report individual findings or an aggregated exposure summary.
+    #[arg(long, value_enum, default_value_t)]
+    pub report: HistoryReport,
+
+    /// Write a `git filter-repo --replace-text` file (and a BFG list beside it)
+    /// that removes the secrets found, and print a rewrite checklist.
+    #[arg(long, value_name = "FILE")]
+    pub emit_rewrite: Option<PathBuf>,
+
+    /// Verify detected secrets against provider APIs.
+    #[arg(long)]
+    pub verify: bool,
+
+    /// With `--verify`, only report secrets that are still live.
+    #[arg(long, requires = "verify")]
+    pub live_only: bool,
+
+    /// Record scanned commits and findings in this file, so later runs only
+    /// scan new commits.
+    #[arg(long, value_name = "FILE")]
+    pub state: Option<PathBuf>,
+
     /// Path to `.vet.toml` configuration file.
     #[arg(short, long)]
     pub config: Option<PathBuf>,
@@ -252,6 +389,46 @@ pub struct HistoryArgs {
     pub concurrency: Option<usize>,
 }
 
+/// Arguments for the `vet watch` command.
+#[derive(Debug, Parser)]
+pub struct WatchArgs {
+    /// Paths to watch for changes.
+    #[arg(default_value = ".")]
+    pub paths: Vec<PathBuf>,
+
+    /// Path to `.vet.toml` configuration file.
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Minimum severity level to report.
+    #[arg(short, long)]
+    pub severity: Option<Severity>,
+
+    /// Minimum confidence level to report (low or high).
+    #[arg(long, value_parser = parse_confidence)]
+    pub minimum_confidence: Option<Confidence>,
+
+    /// Glob patterns to exclude from scanning.
+    #[arg(short, long)]
+    pub exclude: Vec<String>,
+
+    /// Skip `.gitignore` rules when collecting files.
+    #[arg(long)]
+    pub skip_gitignore: bool,
+
+    /// Skip files larger than this size in bytes.
+    #[arg(long)]
+    pub max_file_size: Option<u64>,
+
+    /// Baseline file for suppressing acknowledged secrets.
+    #[arg(short = 'b', long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+
+    /// Milliseconds to wait for further changes before rescanning.
+    #[arg(long, value_name = "MS", default_value_t = 200)]
+    pub debounce: u64,
+}
+
 /// Arguments for the `vet baseline` command.
 #[derive(Debug, Parser)]
 pub struct BaselineArgs {
@@ -283,6 +460,18 @@ pub struct BaselineArgs {
     #[arg(long, value_name = "TEXT")]
     pub reason: Option<String>,
 
+    /// Reviewer to record for added findings (defaults to git `user.email`).
+    #[arg(long, value_name = "NAME")]
+    pub reviewed_by: Option<String>,
+
+    /// Ticket tracking rotation of accepted findings.
+    #[arg(long, value_name = "ID")]
+    pub ticket: Option<String>,
+
+    /// When acceptance lapses: a `YYYY-MM-DD` date or a number of days (`90d`).
+    #[arg(long, value_name = "WHEN", value_parser = parse_expiry)]
+    pub expires: Option<DateTime<Utc>>,
+
     /// Minimum confidence level to include (low or high).
     #[arg(long, value_parser = parse_confidence)]
     pub minimum_confidence: Option<Confidence>,
@@ -297,6 +486,15 @@ pub struct BaselineArgs {
 pub enum BaselineSubcommand {
     /// Show statistics about an existing baseline file.
     Stats(BaselineStatsArgs),
+
+    /// Remove baseline entries that no longer match anything.
+    Prune(BaselinePruneArgs),
+
+    /// Three-way merge baseline files, for use as a git merge driver.
+    Merge(BaselineMergeArgs),
+
+    /// Import triaged findings from another secret scanner.
+    Import(BaselineImportArgs),
 }
 
 /// Arguments for the `vet baseline stats` subcommand.
@@ -309,6 +507,94 @@ pub struct BaselineStatsArgs {
     /// Output statistics as JSON.
     #[arg(long)]
     pub json: bool,
+
+    /// List accepted findings whose expiry falls within this many days.
+    #[arg(long, value_name = "DAYS", default_value_t = 30)]
+    pub expiring_within: u32,
+}
+
+/// Arguments for the `vet baseline prune` subcommand.
+#[derive(Debug, Parser)]
+pub struct BaselinePruneArgs {
+    /// Paths to rescan; should cover everything the baseline was built from.
+    #[arg(default_value = ".")]
+    pub paths: Vec<PathBuf>,
+
+    /// Path to the baseline file (defaults to `baseline_path` from
+    /// `.vet.toml`, then `.vet-baseline.json`).
+    #[arg(short = 'b', long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+
+    /// Path to `.vet.toml` configuration file.
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Glob patterns to exclude from scanning.
+    #[arg(short, long)]
+    pub exclude: Vec<String>,
+
+    /// List stale entries without modifying the baseline.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Output the prune report as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Arguments for the `vet baseline merge` subcommand.
+///
+/// Matches git's merge driver convention (`vet baseline merge %O %A %B`):
+/// the result is written over `OURS` unless `--output` is given.
+#[derive(Debug, Parser)]
+pub struct BaselineMergeArgs {
+    /// Common ancestor version of the baseline (may be empty).
+    pub base: PathBuf,
+
+    /// Our version of the baseline.
+    pub ours: PathBuf,
+
+    /// Their version of the baseline.
+    pub theirs: PathBuf,
+
+    /// Write the merged baseline here instead of over `OURS`.
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+/// Secret scanners whose baselines and allowlists can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportSource {
+    /// A detect-secrets `.secrets.baseline` file.
+    DetectSecrets,
+    /// A gitleaks `.gitleaksignore` file or JSON report used as a baseline.
+    Gitleaks,
+    /// Trufflehog `--json` output.
+    Trufflehog,
+}
+
+/// Arguments for the `vet baseline import` subcommand.
+#[derive(Debug, Parser)]
+pub struct BaselineImportArgs {
+    /// Scanner that produced the file.
+    #[arg(long, value_name = "TOOL")]
+    pub from: ImportSource,
+
+    /// Baseline, allowlist or report to import.
+    pub file: PathBuf,
+
+    /// Path to the baseline file to add to (defaults to `baseline_path`
+    /// from `.vet.toml`, then `.vet-baseline.json`).
+    #[arg(short = 'b', long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+
+    /// Path to `.vet.toml` configuration file.
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Reviewer to record for imported findings (defaults to git `user.email`).
+    #[arg(long, value_name = "NAME")]
+    pub reviewed_by: Option<String>,
 }
 
 /// Arguments for the `vet patterns` command.
@@ -325,6 +611,37 @@ pub struct PatternsArgs {
     /// Show pattern details including regex and keywords.
     #[arg(short, long)]
     pub verbose: bool,
+
+    /// Patterns subcommand.
+    #[command(subcommand)]
+    pub command: Option<PatternsSubcommand>,
+}
+
+/// Subcommands for `vet patterns`.
+#[derive(Debug, Subcommand)]
+pub enum PatternsSubcommand {
+    /// Convert another scanner's rules into custom patterns in `.vet.toml`.
+    Import(PatternsImportArgs),
+}
+
+/// Arguments for the `vet patterns import` command.
+#[derive(Debug, Parser)]
+pub struct PatternsImportArgs {
+    /// gitleaks config whose `[[rules]]` to import.
+    #[arg(long, value_name = "FILE")]
+    pub gitleaks: PathBuf,
+
+    /// Path to `.vet.toml` configuration file to add the patterns to.
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Severity for imported patterns, as gitleaks rules have none.
+    #[arg(short, long, default_value = "high")]
+    pub severity: Severity,
+
+    /// Report what would be imported without modifying the config file.
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 /// Arguments for the `vet init` command.
@@ -347,7 +664,12 @@ pub struct InitArgs {
 #[derive(Debug, Subcommand)]
 pub enum HookCommand {
     /// Install a git pre-commit hook.
-    Install,
+    Install {
+        /// Also register `vet baseline merge` as the git merge driver for
+        /// the baseline file.
+        #[arg(long)]
+        merge_driver: bool,
+    },
     /// Uninstall the git pre-commit hook.
     Uninstall,
 }
@@ -382,23 +704,14 @@ fn parse_cli() -> Cli {
 
 fn run(command: Command) -> anyhow::Result<()> {
     match command {
-        Command::Fix(args) => commands::fix::run(
-            &args.paths,
-            args.config.as_deref(),
-            args.severity,
-            &args.exclude,
-            args.skip_gitignore,
-            args.dry_run,
-            args.max_file_size,
-        ),
+        Command::Fix(args) => commands::fix::run(&args),
         Command::History(args) => commands::history::run(&args),
         Command::Baseline(args) => commands::baseline::run(&args),
         Command::Hook { command } => commands::hook::run(command.as_ref()),
         Command::Init(args) => commands::init::run(args.yes, args.minimal, args.output),
-        Command::Patterns(args) => {
-            commands::patterns::run(args.group.as_deref(), args.severity.as_deref(), args.verbose)
-        }
+        Command::Patterns(args) => commands::patterns::run(&args),
         Command::Scan(args) => commands::scan::run(&args),
+        Command::Watch(args) => commands::watch::run(&args),
     }
 }
 
@@ -424,6 +737,7 @@ fn build_after_help() -> String {
     vet fix --dry-run              Preview fixes without changing files
     vet history                    Scan git history
     vet history -n 100             Scan last 100 commits
+    vet watch                      Rescan files as they change
     vet init                       Create config file
 
   Learn more: {}",
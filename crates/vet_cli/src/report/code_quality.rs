@@ -0,0 +1,71 @@
+//! GitLab Code Quality (Code Climate) report structure shared by `scan` and
+//! `history`.
+
+use std::io::Write;
+
+use serde::Serialize;
+use vet_core::prelude::*;
+
+/// A single entry in a Code Quality report.
+#[derive(Serialize)]
+pub struct Issue {
+    description: String,
+    check_name: String,
+    fingerprint: String,
+    severity: &'static str,
+    categories: [&'static str; 1],
+    location: CodeQualityLocation,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLocation {
+    path: String,
+    lines: CodeQualityLines,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLines {
+    begin: u32,
+}
+
+impl Issue {
+    /// Builds a security issue for a secret found at `path:line`.
+    /// `fingerprint` must be unique within the report.
+    #[must_use]
+    pub fn new(
+        description: String,
+        pattern_id: &str,
+        fingerprint: String,
+        severity: Severity,
+        path: String,
+        line: u32,
+    ) -> Self {
+        Self {
+            description,
+            check_name: pattern_id.to_string(),
+            fingerprint,
+            severity: severity_to_code_quality(severity),
+            categories: ["Security"],
+            location: CodeQualityLocation {
+                path,
+                lines: CodeQualityLines { begin: line },
+            },
+        }
+    }
+}
+
+/// Writes the issues as a Code Quality report.
+pub fn write(issues: &[Issue], writer: &mut dyn Write) -> anyhow::Result<()> {
+    serde_json::to_writer_pretty(&mut *writer, issues)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+const fn severity_to_code_quality(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "blocker",
+        Severity::High => "critical",
+        Severity::Medium => "major",
+        Severity::Low => "minor",
+    }
+}
@@ -0,0 +1,79 @@
+//! GitHub Actions workflow commands shared by `scan` and `history`.
+//!
+//! Each finding is printed as an `::error`/`::warning`/`::notice` annotation
+//! so it shows inline on the pull request diff. When `$GITHUB_STEP_SUMMARY`
+//! is set, a Markdown report is also appended to the job summary.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use anyhow::Context as _;
+use vet_core::prelude::*;
+
+use super::{escape_workflow_data, escape_workflow_property};
+
+const STEP_SUMMARY_ENV: &str = "GITHUB_STEP_SUMMARY";
+
+/// A single annotation on a file and line.
+pub struct Annotation<'a> {
+    /// Severity of the finding, which picks the workflow command.
+    pub severity: Severity,
+    /// Report path of the annotated file.
+    pub file: &'a str,
+    /// One-based line of the secret.
+    pub line: u32,
+    /// One-based column of the secret.
+    pub column: u32,
+    /// Pattern that matched, whose remediation is appended when known.
+    pub pattern: Option<&'a Pattern>,
+    /// Annotation title, normally the pattern name.
+    pub title: &'a str,
+    /// First paragraph of the annotation message.
+    pub message: String,
+}
+
+impl Annotation<'_> {
+    /// Writes the annotation as a workflow command.
+    pub fn write(&self, writer: &mut dyn Write) -> anyhow::Result<()> {
+        let mut message = self.message.clone();
+        if let Some(pattern) = self.pattern {
+            message.push_str("\n\n");
+            message.push_str(pattern.remediation());
+        }
+
+        writeln!(
+            writer,
+            "::{} file={},line={},col={},title={}::{}",
+            severity_to_command(self.severity),
+            escape_workflow_property(self.file),
+            self.line,
+            self.column,
+            escape_workflow_property(self.title),
+            escape_workflow_data(&message),
+        )?;
+        Ok(())
+    }
+}
+
+/// Appends a job summary written by `write_summary` when running inside
+/// GitHub Actions, and does nothing otherwise.
+pub fn append_step_summary(write_summary: impl FnOnce(&mut dyn Write) -> anyhow::Result<()>) -> anyhow::Result<()> {
+    let Some(path) = std::env::var_os(STEP_SUMMARY_ENV).filter(|p| !p.is_empty()) else {
+        return Ok(());
+    };
+
+    let mut summary = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open {STEP_SUMMARY_ENV}"))?;
+    write_summary(&mut summary)
+}
+
+const fn severity_to_command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "notice",
+    }
+}
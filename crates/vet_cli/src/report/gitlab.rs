@@ -0,0 +1,205 @@
+//! GitLab Secret Detection report structure shared by `scan` and `history`.
+//!
+//! Produces a `gl-secret-detection-report.json` compatible document so that
+//! findings appear in the merge request security widget. Each command only
+//! converts its findings into [`Vulnerability`] values.
+
+use std::io::Write;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+use vet_core::prelude::*;
+
+const SCHEMA_VERSION: &str = "15.2.1";
+const REPORT_TYPE: &str = "secret_detection";
+const TOOL_ID: &str = "vet";
+const TOOL_NAME: &str = "vet";
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+const IDENTIFIER_TYPE: &str = "vet_rule_id";
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// The schema requires a commit for every location. Working tree findings
+/// are not tied to one, so we use the same placeholder as GitLab's own analyzer.
+pub const UNCOMMITTED_SHA: &str = "0000000";
+
+#[derive(Serialize)]
+struct GitlabReport {
+    version: &'static str,
+    vulnerabilities: Vec<Vulnerability>,
+    scan: GitlabScan,
+}
+
+/// A single entry in the report's `vulnerabilities` array.
+#[derive(Serialize)]
+pub struct Vulnerability {
+    id: String,
+    name: String,
+    description: String,
+    severity: &'static str,
+    solution: &'static str,
+    identifiers: Vec<GitlabIdentifier>,
+    location: GitlabLocation,
+}
+
+#[derive(Serialize)]
+struct GitlabIdentifier {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+struct GitlabLocation {
+    file: String,
+    start_line: u32,
+    end_line: u32,
+    commit: Commit,
+}
+
+/// The commit a vulnerability was found in.
+#[derive(Serialize)]
+pub struct Commit {
+    sha: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+impl Commit {
+    /// The placeholder commit for findings in the working tree.
+    #[must_use]
+    pub fn uncommitted() -> Self {
+        Self {
+            sha: UNCOMMITTED_SHA.to_string(),
+            author: None,
+            date: None,
+            message: None,
+        }
+    }
+
+    /// A commit from history, with its author, date and message.
+    #[must_use]
+    pub fn new(sha: String, author: String, date: DateTime<Utc>, message: String) -> Self {
+        Self {
+            sha,
+            author: Some(author),
+            date: Some(date.format(TIMESTAMP_FORMAT).to_string()),
+            message: Some(message),
+        }
+    }
+}
+
+/// Where in a commit a secret was found.
+pub struct Location {
+    /// Report path of the file holding the secret.
+    pub file: String,
+    /// One-based line of the secret.
+    pub line: u32,
+    /// Commit the secret was found in.
+    pub commit: Commit,
+}
+
+impl Vulnerability {
+    /// Builds a vulnerability entry for a finding of `pattern_id`, naming it
+    /// after `pattern` when the pattern is known.
+    #[must_use]
+    pub fn new(
+        id: String,
+        pattern_id: &str,
+        pattern: Option<&Pattern>,
+        severity: Severity,
+        description: String,
+        location: Location,
+    ) -> Self {
+        let name = super::pattern_name(pattern_id, pattern);
+
+        Self {
+            id,
+            name: name.to_string(),
+            description,
+            severity: severity_to_gitlab(severity),
+            solution: pattern.map_or("", Pattern::remediation),
+            identifiers: vec![GitlabIdentifier {
+                kind: IDENTIFIER_TYPE,
+                name: name.to_string(),
+                value: pattern_id.to_string(),
+            }],
+            location: GitlabLocation {
+                file: location.file,
+                start_line: location.line,
+                end_line: location.line,
+                commit: location.commit,
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GitlabScan {
+    analyzer: GitlabTool,
+    scanner: GitlabTool,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    start_time: String,
+    end_time: String,
+    status: &'static str,
+}
+
+#[derive(Serialize)]
+struct GitlabTool {
+    id: &'static str,
+    name: &'static str,
+    version: &'static str,
+    vendor: GitlabVendor,
+}
+
+#[derive(Serialize)]
+struct GitlabVendor {
+    name: &'static str,
+}
+
+/// Writes a complete report for a scan that took `elapsed` and ended now.
+pub fn write(vulnerabilities: Vec<Vulnerability>, elapsed: Duration, writer: &mut dyn Write) -> anyhow::Result<()> {
+    let end = Utc::now();
+    let start = end - TimeDelta::from_std(elapsed).unwrap_or_default();
+
+    let report = GitlabReport {
+        version: SCHEMA_VERSION,
+        vulnerabilities,
+        scan: GitlabScan {
+            analyzer: tool(),
+            scanner: tool(),
+            kind: REPORT_TYPE,
+            start_time: start.format(TIMESTAMP_FORMAT).to_string(),
+            end_time: end.format(TIMESTAMP_FORMAT).to_string(),
+            status: "success",
+        },
+    };
+
+    serde_json::to_writer_pretty(&mut *writer, &report)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+const fn tool() -> GitlabTool {
+    GitlabTool {
+        id: TOOL_ID,
+        name: TOOL_NAME,
+        version: TOOL_VERSION,
+        vendor: GitlabVendor { name: TOOL_NAME },
+    }
+}
+
+const fn severity_to_gitlab(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "Critical",
+        Severity::High => "High",
+        Severity::Medium => "Medium",
+        Severity::Low => "Low",
+    }
+}
@@ -0,0 +1,105 @@
+//! Markdown report layout shared by `scan` and `history`.
+//!
+//! Findings are grouped by file, suitable for posting as a pull request
+//! comment or a GitHub Actions job summary. Each command only renders its
+//! own findings.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use vet_core::prelude::*;
+
+use super::markdown_code;
+use crate::ui::pluralise_word;
+
+const SEVERITY_ORDER: [Severity; 4] = [Severity::Critical, Severity::High, Severity::Medium, Severity::Low];
+
+/// How the report reads each command's findings.
+pub struct Layout<'a, T> {
+    /// Command name used in the heading, such as `"vet scan"`.
+    pub title: &'a str,
+    /// What was scanned, such as `"12 files scanned"`.
+    pub scanned: String,
+    /// Report path the finding is grouped under.
+    pub path: fn(&T) -> String,
+    /// Severity of the finding.
+    pub severity: fn(&T) -> Severity,
+}
+
+/// Writes the heading and summary line, then one section per file with
+/// each finding rendered by `write_finding`.
+pub fn write<T>(
+    layout: &Layout<'_, T>,
+    findings: &[T],
+    writer: &mut dyn Write,
+    mut write_finding: impl FnMut(&T, &mut dyn Write) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    writeln!(writer, "## {}", layout.title)?;
+    writeln!(writer)?;
+
+    if findings.is_empty() {
+        writeln!(writer, "No secrets found · {}", layout.scanned)?;
+        return Ok(());
+    }
+
+    let mut by_file: BTreeMap<String, Vec<&T>> = BTreeMap::new();
+    for finding in findings {
+        by_file.entry((layout.path)(finding)).or_default().push(finding);
+    }
+
+    let count = findings.len();
+    writeln!(
+        writer,
+        "**{count} {} found** in {} {} · {} · {}",
+        pluralise_word(count, "secret", "secrets"),
+        by_file.len(),
+        pluralise_word(by_file.len(), "file", "files"),
+        severity_summary(&findings.iter().map(layout.severity).collect::<Vec<_>>()),
+        layout.scanned,
+    )?;
+
+    for (path, findings) in &by_file {
+        writeln!(writer)?;
+        writeln!(writer, "### {}", markdown_code(path))?;
+
+        for finding in findings {
+            write_finding(finding, writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the pattern's remediation advice as a block quote.
+pub fn write_remediation(pattern: Option<&Pattern>, writer: &mut dyn Write) -> anyhow::Result<()> {
+    if let Some(pattern) = pattern {
+        writeln!(writer)?;
+        for line in pattern.remediation().lines() {
+            writeln!(writer, "> {line}")?;
+        }
+    }
+    Ok(())
+}
+
+fn severity_summary(severities: &[Severity]) -> String {
+    SEVERITY_ORDER
+        .into_iter()
+        .filter_map(|severity| {
+            let count = severities.iter().filter(|s| **s == severity).count();
+            (count > 0).then(|| format!("{count} {}", severity.to_string().to_lowercase()))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_summary_lists_non_zero_counts_most_severe_first() {
+        let summary = severity_summary(&[Severity::Low, Severity::Critical, Severity::Low]);
+
+        assert_eq!(summary, "1 critical, 2 low");
+    }
+}
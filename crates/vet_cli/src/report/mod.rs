@@ -0,0 +1,175 @@
+//! Report pieces shared by the `scan` and `history` output formats.
+//!
+//! The `JUnit`, CSV, Markdown and GitHub Actions writers build their output
+//! by hand, so they all need the same quoting rules. Report structures and
+//! layouts live in submodules; each command only converts its findings.
+
+pub mod code_quality;
+pub mod github;
+pub mod gitlab;
+pub mod markdown;
+pub mod sarif;
+
+use std::io::Write;
+
+use sha2::{Digest, Sha256};
+use vet_core::prelude::*;
+
+/// Returns the pattern's display name, or its ID if the pattern is unknown.
+#[must_use]
+pub fn pattern_name<'a>(pattern_id: &'a str, pattern: Option<&'a Pattern>) -> &'a str {
+    pattern.map_or(pattern_id, |p| p.name.as_ref())
+}
+
+/// Identifies one occurrence of a finding within a report.
+///
+/// The baseline fingerprint is the same for every copy of a secret in a
+/// file, so the line and column are mixed in to keep report entries that
+/// must be unique, such as GitLab vulnerability IDs, from colliding.
+#[must_use]
+pub fn occurrence_id(fingerprint: &Fingerprint, line: u32, column: u32) -> String {
+    format!(
+        "sha256:{}",
+        hex::encode(Sha256::digest(format!("{fingerprint}:{line}:{column}")))
+    )
+}
+
+/// Escapes a string for use in XML text or attribute values.
+#[must_use]
+pub fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tab and newlines are not valid in XML 1.0.
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Writes a single CSV record, quoting fields as required by RFC 4180.
+pub fn write_csv_record<S: AsRef<str>>(writer: &mut dyn Write, fields: &[S]) -> std::io::Result<()> {
+    let record: Vec<String> = fields.iter().map(|f| csv_field(f.as_ref())).collect();
+    writeln!(writer, "{}", record.join(","))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Wraps a string in a Markdown code span, using a backtick fence longer
+/// than any run of backticks inside the value.
+#[must_use]
+pub fn markdown_code(value: &str) -> String {
+    let longest_run = value.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+
+    if value.starts_with('`') || value.ends_with('`') {
+        format!("{fence} {value} {fence}")
+    } else {
+        format!("{fence}{value}{fence}")
+    }
+}
+
+/// Escapes characters that would otherwise be interpreted as Markdown
+/// table or inline syntax.
+#[must_use]
+pub fn escape_markdown(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if matches!(c, '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// Escapes the message part of a GitHub Actions workflow command.
+#[must_use]
+pub fn escape_workflow_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a property value (such as `file` or `title`) of a GitHub Actions
+/// workflow command.
+#[must_use]
+pub fn escape_workflow_property(value: &str) -> String {
+    escape_workflow_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn occurrence_id_differs_by_position() {
+        let fingerprint = Fingerprint::calculate("vcs/github-pat", Path::new("app.env"), "abc");
+
+        assert_eq!(occurrence_id(&fingerprint, 1, 7), occurrence_id(&fingerprint, 1, 7));
+        assert_ne!(occurrence_id(&fingerprint, 1, 7), occurrence_id(&fingerprint, 2, 7));
+        assert_ne!(occurrence_id(&fingerprint, 1, 7), occurrence_id(&fingerprint, 1, 30));
+    }
+
+    #[test]
+    fn escape_xml_replaces_special_characters() {
+        assert_eq!(
+            escape_xml(r#"<a href="x">&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&apos;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn escape_xml_drops_invalid_control_characters() {
+        assert_eq!(escape_xml("a\u{1b}b\tc"), "ab\tc");
+    }
+
+    #[test]
+    fn csv_record_quotes_only_when_needed() {
+        let mut out = Vec::new();
+        write_csv_record(&mut out, &["plain", "with,comma", "with \"quote\""]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "plain,\"with,comma\",\"with \"\"quote\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn markdown_code_uses_longer_fence_than_content() {
+        assert_eq!(markdown_code("key"), "`key`");
+        assert_eq!(markdown_code("a`b"), "``a`b``");
+        assert_eq!(markdown_code("`tick"), "`` `tick ``");
+    }
+
+    #[test]
+    fn escape_markdown_escapes_table_pipes() {
+        assert_eq!(escape_markdown("a|b_c"), "a\\|b\\_c");
+    }
+
+    #[test]
+    fn escape_workflow_data_encodes_newlines_and_percent() {
+        assert_eq!(escape_workflow_data("50%\nnext"), "50%25%0Anext");
+    }
+
+    #[test]
+    fn escape_workflow_property_encodes_separators() {
+        assert_eq!(escape_workflow_property("a:b,c"), "a%3Ab%2Cc");
+    }
+}
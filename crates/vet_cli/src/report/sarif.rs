@@ -0,0 +1,33 @@
+//! SARIF pieces shared by the `scan` and `history` writers.
+//!
+//! Each command builds its own runs and results; only the tool metadata and
+//! severity mappings must agree between them.
+
+use vet_core::prelude::*;
+
+pub const TOOL_NAME: &str = "vet";
+pub const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const TOOL_URI: &str = "https://github.com/spikermint/vet";
+pub const HELP_URI: &str = "https://github.com/spikermint/vet#detected-secrets";
+
+/// Maps severity onto a SARIF result level.
+#[must_use]
+pub const fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+/// Maps severity onto GitHub code scanning's CVSS-style `security-severity`
+/// bands (critical > 9.0, high 7.0-8.9, medium 4.0-6.9, low < 4.0).
+#[must_use]
+pub const fn security_severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "9.5",
+        Severity::High => "8.0",
+        Severity::Medium => "5.5",
+        Severity::Low => "3.0",
+    }
+}
@@ -5,6 +5,7 @@ use std::time::Duration;
 use console::Style;
 use indicatif::{ProgressBar, ProgressStyle};
 use vet_core::prelude::*;
+use vet_providers::VerificationStatus;
 
 /// Single-character Unicode glyphs used as status indicators.
 pub mod indicators {
@@ -82,6 +83,8 @@ pub mod colors {
 
 /// Process exit codes.
 pub mod exit {
+    /// No blocking secrets were found.
+    pub const SUCCESS: i32 = 0;
     /// Secrets were found.
     pub const FINDINGS: i32 = 1;
     /// An unrecoverable error occurred.
@@ -161,6 +164,38 @@ pub fn print_info(message: &str) {
     );
 }
 
+/// Prints one `[n/total] pattern ···· status` line of verification progress.
+/// A `None` status means the pattern has no verifier and was skipped.
+pub fn print_verification_progress(idx: usize, total: usize, pattern_id: &str, status: Option<&VerificationStatus>) {
+    let counter = format!("[{idx}/{total}]");
+    let prefix = format!("  {counter} ");
+    let prefix_len = prefix.chars().count();
+
+    let pattern_display = pattern_id;
+    let pattern_len = pattern_display.chars().count();
+
+    let result_col: usize = 50;
+    let available_for_dots = result_col.saturating_sub(prefix_len + pattern_len + 1);
+    let dots = " ".to_string() + &"·".repeat(available_for_dots);
+
+    let status_display = match status {
+        Some(VerificationStatus::Live) => format!("{}", colors::error().bold().apply_to("LIVE")),
+        Some(VerificationStatus::Inactive) => format!("{}", colors::success().apply_to("inactive")),
+        Some(VerificationStatus::Inconclusive) => {
+            format!("{}", colors::warning().apply_to("inconclusive"))
+        }
+        None => format!("{}", colors::muted().apply_to("skipped")),
+    };
+
+    println!(
+        "{}{}{}{}",
+        prefix,
+        colors::secondary().apply_to(pattern_display),
+        colors::muted().apply_to(dots),
+        status_display
+    );
+}
+
 /// Returns `singular` when `count` is 1, otherwise `plural`.
 #[must_use]
 pub const fn pluralise_word<'a>(count: usize, singular: &'a str, plural: &'a str) -> &'a str {
@@ -221,6 +256,26 @@ pub fn create_commit_progress(total: usize) -> ProgressBar {
     pb
 }
 
+/// Creates a progress bar for scanning the unique blobs found in history.
+#[must_use]
+pub fn create_blob_progress(total: usize) -> ProgressBar {
+    let pb = ProgressBar::new(total as u64);
+
+    #[expect(
+        clippy::expect_used,
+        reason = "static template string; failure is a programmer error"
+    )]
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{bar:40.cyan/243} {percent:>3}% {pos}/{len} blobs ({elapsed} elapsed)")
+            .expect("invalid progress template")
+            .progress_chars("━━╸"),
+    );
+
+    pb.enable_steady_tick(Duration::from_millis(PROGRESS_TICK_MS));
+    pb
+}
+
 #[derive(Debug, Default)]
 struct SeverityCounts {
     critical: usize,
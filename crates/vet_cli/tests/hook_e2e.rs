@@ -121,3 +121,26 @@ fn status_shows_installed_when_present() {
 
     vet().args(["hook"]).current_dir(dir.path()).assert().success();
 }
+
+#[test]
+fn install_merge_driver_registers_driver_and_attribute() {
+    let dir = TempDir::new().unwrap();
+    init_git_repo(&dir);
+    fs::write(dir.path().join(".gitattributes"), "*.png binary").unwrap();
+
+    for _ in 0..2 {
+        vet()
+            .args(["hook", "install", "--merge-driver"])
+            .current_dir(dir.path())
+            .assert()
+            .success();
+    }
+
+    let attributes = fs::read_to_string(dir.path().join(".gitattributes")).unwrap();
+    assert_eq!(attributes, "*.png binary\n.vet-baseline.json merge=vet-baseline\n");
+
+    let config = fs::read_to_string(dir.path().join(".git/config")).unwrap();
+    assert!(config.contains("[merge \"vet-baseline\"]"));
+    assert!(config.contains("driver = vet baseline merge %O %A %B"));
+    assert!(dir.path().join(".git/hooks/pre-commit").exists());
+}
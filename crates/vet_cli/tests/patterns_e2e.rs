@@ -1,7 +1,10 @@
 //! End-to-end tests for the `vet patterns` command.
 
+use std::fs;
+
 use assert_cmd::Command;
 use predicates::prelude::*;
+use tempfile::TempDir;
 
 fn vet() -> Command {
     Command::new(env!("CARGO_BIN_EXE_vet"))
@@ -67,3 +70,86 @@ fn verbose_shows_more_details() {
         "verbose should show at least as much as normal"
     );
 }
+
+const GITLEAKS_RULES: &str = r#"
+[[rules]]
+id = "acme-token"
+description = "Acme API token"
+regex = '''acme_[a-z0-9]{32}'''
+keywords = ["acme_"]
+
+[[rules]]
+id = "acme-key"
+regex = '''key\s*=\s*"(ak_[A-Z0-9]{20})"'''
+secretGroup = 1
+path = '''\.env$'''
+
+[[rules]]
+id = "pkcs12-file"
+path = '''\.p12$'''
+"#;
+
+#[test]
+fn import_gitleaks_appends_custom_patterns() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join(".vet.toml"), "# project settings\nseverity = \"low\"\n").unwrap();
+    fs::write(dir.path().join("gitleaks.toml"), GITLEAKS_RULES).unwrap();
+
+    vet()
+        .args(["patterns", "import", "--gitleaks", "gitleaks.toml"])
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("imported 2 patterns"))
+        .stdout(predicate::str::contains("capture group 1"))
+        .stdout(predicate::str::contains("1 rule skipped"));
+
+    let config = fs::read_to_string(dir.path().join(".vet.toml")).unwrap();
+    assert!(config.starts_with("# project settings"));
+    assert!(config.contains(r#"id = "custom/acme-token""#));
+    assert!(!config.contains("pkcs12-file"));
+
+    fs::write(
+        dir.path().join("app.txt"),
+        "TOKEN=acme_0123456789abcdefghijklmnopqrstuv",
+    )
+    .unwrap();
+    vet()
+        .args(["scan", "app.txt"])
+        .current_dir(dir.path())
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Acme API token"));
+}
+
+#[test]
+fn import_gitleaks_dry_run_leaves_config_untouched() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("gitleaks.toml"), GITLEAKS_RULES).unwrap();
+
+    vet()
+        .args(["patterns", "import", "--gitleaks", "gitleaks.toml", "--dry-run"])
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("would import 2 patterns"));
+
+    assert!(!dir.path().join(".vet.toml").exists());
+}
+
+#[test]
+fn import_gitleaks_skips_already_defined_patterns() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("gitleaks.toml"), GITLEAKS_RULES).unwrap();
+
+    for _ in 0..2 {
+        vet()
+            .args(["patterns", "import", "--gitleaks", "gitleaks.toml"])
+            .current_dir(dir.path())
+            .assert()
+            .success();
+    }
+
+    let config = fs::read_to_string(dir.path().join(".vet.toml")).unwrap();
+    assert_eq!(config.matches("[[patterns]]").count(), 2);
+}
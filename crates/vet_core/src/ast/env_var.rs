@@ -0,0 +1,204 @@
+//! Rewriting hardcoded string literals as environment variable lookups.
+//!
+//! Used by the "extract to environment variable" fix: the string literal that
+//! holds a secret is located with tree-sitter and replaced by the idiomatic
+//! lookup for the file's language.
+
+use std::ops::Range;
+
+use super::validator::{SourceLanguage, parse};
+
+impl SourceLanguage {
+    /// Returns the idiomatic expression that reads `name` from the environment.
+    #[must_use]
+    pub fn env_lookup(self, name: &str) -> String {
+        match self {
+            Self::Python => format!(r#"os.environ["{name}"]"#),
+            Self::JavaScript | Self::TypeScript => format!("process.env.{name}"),
+            Self::Go => format!(r#"os.Getenv("{name}")"#),
+            Self::Ruby => format!(r#"ENV["{name}"]"#),
+            Self::Java => format!(r#"System.getenv("{name}")"#),
+            Self::Rust => format!(r#"std::env::var("{name}")"#),
+        }
+    }
+
+    /// Returns the module the lookup needs when `content` does not already
+    /// import it, since inserting an import safely would mean rewriting the
+    /// file's import block.
+    #[must_use]
+    pub fn missing_env_import(self, content: &str) -> Option<&'static str> {
+        let imported = match self {
+            Self::Python => content.lines().any(|line| {
+                line.trim()
+                    .strip_prefix("import ")
+                    .is_some_and(|names| names.split(',').map(str::trim).any(binds_os))
+            }),
+            Self::Go => content.lines().any(|line| {
+                let line = line.trim();
+                line == r#""os""# || line == r#"import "os""#
+            }),
+            Self::JavaScript | Self::TypeScript | Self::Ruby | Self::Java | Self::Rust => true,
+        };
+        (!imported).then_some("os")
+    }
+
+    /// Node kinds the grammar uses for string literals.
+    const fn string_literal_kinds(self) -> &'static [&'static str] {
+        match self {
+            Self::Python | Self::Ruby => &["string"],
+            Self::JavaScript | Self::TypeScript => &["string", "template_string"],
+            Self::Go => &["interpreted_string_literal", "raw_string_literal"],
+            Self::Java => &["string_literal"],
+            Self::Rust => &["string_literal", "raw_string_literal"],
+        }
+    }
+}
+
+/// Returns `true` if a Python `import` name binds `os` (`os`, `os.path`),
+/// rather than an alias for it.
+fn binds_os(name: &str) -> bool {
+    (name == "os" || name.starts_with("os.")) && !name.contains(' ')
+}
+
+/// Finds the string literal whose entire value is the secret at
+/// `secret_start..secret_end`, returning the literal's byte range including
+/// its quotes and any prefix (`r#"`, `f"`, ...).
+///
+/// Returns `None` when the secret is not inside a string literal, or when the
+/// literal holds more than just the secret (e.g. `"Bearer sk_..."`), since
+/// replacing it would drop the surrounding text.
+#[must_use]
+pub fn find_string_literal(
+    content: &str,
+    language: SourceLanguage,
+    secret_start: usize,
+    secret_end: usize,
+) -> Option<Range<usize>> {
+    if secret_start >= secret_end || secret_end > content.len() {
+        return None;
+    }
+
+    let tree = parse(content.as_bytes(), language)?;
+    let kinds = language.string_literal_kinds();
+
+    let mut node = tree.root_node().descendant_for_byte_range(secret_start, secret_end)?;
+    while !kinds.contains(&node.kind()) {
+        node = node.parent()?;
+    }
+
+    let literal = node.byte_range();
+    let prefix = content.get(literal.start..secret_start)?;
+    let suffix = content.get(secret_end..literal.end)?;
+
+    let is_delimiter = |c: char| matches!(c, '"' | '\'' | '`' | '#');
+    let prefix_ok = prefix.contains(is_delimiter) && prefix.chars().all(|c| is_delimiter(c) || c.is_ascii_alphabetic());
+    let suffix_ok = !suffix.is_empty() && suffix.chars().all(is_delimiter);
+
+    (prefix_ok && suffix_ok).then_some(literal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "sk_live_abc123def456";
+
+    fn literal_for(content: &str, language: SourceLanguage) -> Option<&str> {
+        let start = content.find(SECRET)?;
+        let range = find_string_literal(content, language, start, start + SECRET.len())?;
+        Some(&content[range])
+    }
+
+    #[test]
+    fn env_lookup_uses_idiomatic_expression_per_language() {
+        assert_eq!(SourceLanguage::Python.env_lookup("KEY"), r#"os.environ["KEY"]"#);
+        assert_eq!(SourceLanguage::JavaScript.env_lookup("KEY"), "process.env.KEY");
+        assert_eq!(SourceLanguage::TypeScript.env_lookup("KEY"), "process.env.KEY");
+        assert_eq!(SourceLanguage::Go.env_lookup("KEY"), r#"os.Getenv("KEY")"#);
+        assert_eq!(SourceLanguage::Ruby.env_lookup("KEY"), r#"ENV["KEY"]"#);
+        assert_eq!(SourceLanguage::Java.env_lookup("KEY"), r#"System.getenv("KEY")"#);
+        assert_eq!(SourceLanguage::Rust.env_lookup("KEY"), r#"std::env::var("KEY")"#);
+    }
+
+    #[test]
+    fn missing_env_import_checks_python_and_go_imports() {
+        assert_eq!(SourceLanguage::Python.missing_env_import("import os\n"), None);
+        assert_eq!(SourceLanguage::Python.missing_env_import("import sys, os\n"), None);
+        assert_eq!(SourceLanguage::Python.missing_env_import("import os.path\n"), None);
+        assert_eq!(
+            SourceLanguage::Python.missing_env_import("import os as o\n"),
+            Some("os")
+        );
+        assert_eq!(SourceLanguage::Python.missing_env_import("key = 'x'\n"), Some("os"));
+        assert_eq!(SourceLanguage::Go.missing_env_import("import \"os\"\n"), None);
+        assert_eq!(
+            SourceLanguage::Go.missing_env_import("import (\n\t\"fmt\"\n\t\"os\"\n)\n"),
+            None
+        );
+        assert_eq!(SourceLanguage::Go.missing_env_import("import \"fmt\"\n"), Some("os"));
+        assert_eq!(SourceLanguage::JavaScript.missing_env_import(""), None);
+    }
+
+    #[test]
+    fn finds_python_string() {
+        let code = format!("api_key = '{SECRET}'\n");
+        assert_eq!(
+            literal_for(&code, SourceLanguage::Python),
+            Some(&*format!("'{SECRET}'"))
+        );
+    }
+
+    #[test]
+    fn finds_javascript_template_string() {
+        let code = format!("const key = `{SECRET}`;\n");
+        assert_eq!(
+            literal_for(&code, SourceLanguage::JavaScript),
+            Some(&*format!("`{SECRET}`"))
+        );
+    }
+
+    #[test]
+    fn finds_go_raw_string() {
+        let code = format!("package main\n\nvar key = `{SECRET}`\n");
+        assert_eq!(literal_for(&code, SourceLanguage::Go), Some(&*format!("`{SECRET}`")));
+    }
+
+    #[test]
+    fn finds_java_string() {
+        let code = format!("class A {{ String key = \"{SECRET}\"; }}\n");
+        assert_eq!(
+            literal_for(&code, SourceLanguage::Java),
+            Some(&*format!("\"{SECRET}\""))
+        );
+    }
+
+    #[test]
+    fn finds_ruby_string() {
+        let code = format!("key = \"{SECRET}\"\n");
+        assert_eq!(
+            literal_for(&code, SourceLanguage::Ruby),
+            Some(&*format!("\"{SECRET}\""))
+        );
+    }
+
+    #[test]
+    fn finds_rust_raw_string() {
+        let code = format!("let key = r#\"{SECRET}\"#;\n");
+        assert_eq!(
+            literal_for(&code, SourceLanguage::Rust),
+            Some(&*format!("r#\"{SECRET}\"#"))
+        );
+    }
+
+    #[test]
+    fn rejects_literal_with_surrounding_text() {
+        let code = format!("headers = {{'Authorization': 'Bearer {SECRET}'}}\n");
+        assert_eq!(literal_for(&code, SourceLanguage::Python), None);
+    }
+
+    #[test]
+    fn rejects_secret_outside_string_literal() {
+        let code = format!("# {SECRET}\n");
+        assert_eq!(literal_for(&code, SourceLanguage::Python), None);
+    }
+}
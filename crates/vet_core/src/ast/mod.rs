@@ -21,12 +21,14 @@
 //! ```
 
 mod dotenv;
+mod env_var;
 pub mod trigger;
 mod validator;
 
 use std::path::Path;
 use std::sync::Arc;
 
+pub use env_var::find_string_literal;
 pub use validator::SourceLanguage;
 
 /// A generic secret finding extracted by AST or `.env` analysis.
@@ -73,6 +75,30 @@ pub fn is_dotenv_file(path: &Path) -> bool {
     file_name == ".env" || file_name.starts_with(".env.")
 }
 
+/// How generic secret detection treats a file, as decided by its path.
+///
+/// The scanner only looks at a file's path to pick this, so identical content
+/// scanned under paths of the same kind always yields the same findings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    /// A `.env` file, handled by regex extraction.
+    Dotenv,
+    /// Source code in a language with AST support.
+    Source(SourceLanguage),
+    /// Anything else; only regex patterns apply.
+    Other,
+}
+
+/// Classifies a file path for generic secret detection.
+#[must_use]
+pub fn file_kind(path: &Path) -> FileKind {
+    if is_dotenv_file(path) {
+        FileKind::Dotenv
+    } else {
+        detect_language(path).map_or(FileKind::Other, FileKind::Source)
+    }
+}
+
 /// Extracts generic secret findings from a file.
 ///
 /// Routes to AST parsing (source code) or regex (`.env` files) depending on
@@ -83,18 +109,13 @@ pub fn extract_generic_findings(
     path: &Path,
     trigger_groups: &[trigger::TriggerWordGroup],
 ) -> Vec<AstFinding> {
-    if is_dotenv_file(path) {
-        let Ok(text) = std::str::from_utf8(content) else {
-            return Vec::new();
-        };
-        return dotenv::extract_dotenv_findings(text, trigger_groups);
-    }
-
-    if let Some(language) = detect_language(path) {
-        return validator::extract_ast_findings(content, language, trigger_groups);
+    match file_kind(path) {
+        FileKind::Dotenv => std::str::from_utf8(content)
+            .map(|text| dotenv::extract_dotenv_findings(text, trigger_groups))
+            .unwrap_or_default(),
+        FileKind::Source(language) => validator::extract_ast_findings(content, language, trigger_groups),
+        FileKind::Other => Vec::new(),
     }
-
-    Vec::new()
 }
 
 #[cfg(test)]
@@ -171,6 +192,16 @@ mod tests {
         assert!(!is_dotenv_file(Path::new(".envrc")));
     }
 
+    #[test]
+    fn file_kind_classifies_paths() {
+        assert_eq!(file_kind(Path::new("app/.env.local")), FileKind::Dotenv);
+        assert_eq!(
+            file_kind(Path::new("src/main.rs")),
+            FileKind::Source(SourceLanguage::Rust)
+        );
+        assert_eq!(file_kind(Path::new("README.md")), FileKind::Other);
+    }
+
     #[test]
     fn extract_generic_findings_routes_to_dotenv() {
         let groups = vec![trigger::TriggerWordGroup::from_static(
@@ -8,7 +8,7 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use tree_sitter::{Language, Parser, Query, QueryCursor, StreamingIterator as _};
+use tree_sitter::{Language, Parser, Query, QueryCursor, StreamingIterator as _, Tree};
 
 use super::AstFinding;
 use super::trigger::{TriggerWordGroup, matches_trigger};
@@ -77,20 +77,7 @@ pub fn extract_ast_findings(
 ) -> Vec<AstFinding> {
     let ts_language = language.tree_sitter_language();
 
-    let tree = PARSERS.with(|parsers| {
-        let mut parsers = parsers.borrow_mut();
-        let parser = parsers.entry(language).or_insert_with(|| {
-            let mut p = Parser::new();
-            #[expect(clippy::expect_used, reason = "grammar is compiled into the binary and always valid")]
-            p.set_language(&ts_language)
-                .expect("built-in grammar should always be loadable");
-            p
-        });
-
-        parser.parse(content, None)
-    });
-
-    let Some(tree) = tree else {
+    let Some(tree) = parse(content, language) else {
         return Vec::new();
     };
 
@@ -160,6 +147,22 @@ pub fn extract_ast_findings(
     findings
 }
 
+/// Parses source content with the thread-local parser for `language`.
+pub(super) fn parse(content: &[u8], language: SourceLanguage) -> Option<Tree> {
+    PARSERS.with(|parsers| {
+        let mut parsers = parsers.borrow_mut();
+        let parser = parsers.entry(language).or_insert_with(|| {
+            let mut p = Parser::new();
+            #[expect(clippy::expect_used, reason = "grammar is compiled into the binary and always valid")]
+            p.set_language(&language.tree_sitter_language())
+                .expect("built-in grammar should always be loadable");
+            p
+        });
+
+        parser.parse(content, None)
+    })
+}
+
 /// Strips matching surrounding quotes from a captured string value.
 ///
 /// Some grammars capture the full string node including quotes (e.g. Go's
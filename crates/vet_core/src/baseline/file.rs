@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
@@ -9,7 +10,11 @@ use super::finding::BaselineFinding;
 use super::fingerprint::Fingerprint;
 
 /// Schema version of the baseline JSON format.
-const CURRENT_VERSION: &str = "1";
+const CURRENT_VERSION: &str = "2";
+
+/// Version 1 baselines lack the review metadata and location hint added in
+/// version 2, all of which are optional, so they load unchanged.
+const LEGACY_VERSION: &str = "1";
 
 /// Persistent record of acknowledged findings, serialised as JSON.
 ///
@@ -18,7 +23,7 @@ const CURRENT_VERSION: &str = "1";
 /// `Fingerprint` derived from the pattern, file path, and secret hash.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Baseline {
-    /// Schema version string (currently `"1"`).
+    /// Schema version string (currently `"2"`).
     pub version: String,
 
     /// Timestamp when the baseline was first created.
@@ -51,7 +56,8 @@ impl Baseline {
         }
     }
 
-    /// Loads a baseline from a JSON file on disk.
+    /// Loads a baseline from a JSON file on disk, migrating version 1 files
+    /// to the current schema. The file itself is upgraded on the next save.
     ///
     /// Returns `BaselineError::NotFound` if the file does not exist, or
     /// `BaselineError::UnsupportedVersion` if the schema version is unrecognised.
@@ -72,13 +78,18 @@ impl Baseline {
             source: e,
         })?;
 
-        if baseline.version != CURRENT_VERSION {
-            return Err(BaselineError::UnsupportedVersion {
-                version: baseline.version,
-            });
-        }
+        baseline.migrate()
+    }
 
-        Ok(baseline)
+    fn migrate(mut self) -> Result<Self, BaselineError> {
+        match self.version.as_str() {
+            CURRENT_VERSION => Ok(self),
+            LEGACY_VERSION => {
+                self.version = CURRENT_VERSION.to_string();
+                Ok(self)
+            }
+            _ => Err(BaselineError::UnsupportedVersion { version: self.version }),
+        }
     }
 
     /// Atomically writes this baseline to a JSON file, updating `updated_at`.
@@ -118,6 +129,37 @@ impl Baseline {
         self.findings.iter().find(|f| &f.fingerprint == fingerprint)
     }
 
+    /// Records the line the finding with `fingerprint` was last seen on.
+    /// Returns `true` if the finding exists and its line changed.
+    pub fn record_line(&mut self, fingerprint: &Fingerprint, line: u32) -> bool {
+        match self.findings.iter_mut().find(|f| &f.fingerprint == fingerprint) {
+            Some(finding) if finding.line != Some(line) => {
+                finding.line = Some(line);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the findings whose fingerprint is not in `seen`, i.e. those
+    /// that no longer match anything in the scanned tree.
+    pub fn stale_findings<'a>(
+        &'a self,
+        seen: &'a HashSet<Fingerprint>,
+    ) -> impl Iterator<Item = &'a BaselineFinding> + 'a {
+        self.findings.iter().filter(|f| !seen.contains(&f.fingerprint))
+    }
+
+    /// Removes the findings whose fingerprint is not in `seen` and returns
+    /// them, so a fixed secret that comes back is reported again.
+    pub fn prune(&mut self, seen: &HashSet<Fingerprint>) -> Vec<BaselineFinding> {
+        let (kept, removed) = std::mem::take(&mut self.findings)
+            .into_iter()
+            .partition(|f| seen.contains(&f.fingerprint));
+        self.findings = kept;
+        removed
+    }
+
     /// Returns the number of findings in the baseline.
     #[must_use]
     pub fn len(&self) -> usize {
@@ -158,10 +200,27 @@ mod tests {
     }
 
     #[test]
-    fn new_baseline_has_version_1() {
+    fn prune_removes_unseen_findings() {
+        let mut baseline = Baseline::new();
+        baseline.add_finding(create_test_finding("sha256:kept"));
+        baseline.add_finding(create_test_finding("sha256:fixed"));
+        let seen = HashSet::from([Fingerprint::from_string("sha256:kept")]);
+
+        let stale: Vec<_> = baseline.stale_findings(&seen).map(|f| f.fingerprint.clone()).collect();
+        assert_eq!(stale, [Fingerprint::from_string("sha256:fixed")]);
+
+        let removed = baseline.prune(&seen);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].fingerprint, Fingerprint::from_string("sha256:fixed"));
+        assert_eq!(baseline.len(), 1);
+        assert!(baseline.contains_fingerprint(&Fingerprint::from_string("sha256:kept")));
+    }
+
+    #[test]
+    fn new_baseline_has_version_2() {
         let baseline = Baseline::new();
 
-        assert_eq!(baseline.version, "1");
+        assert_eq!(baseline.version, "2");
     }
 
     #[test]
@@ -196,7 +255,7 @@ mod tests {
 
         let loaded = Baseline::load(&path).unwrap();
 
-        assert_eq!(loaded.version, "1");
+        assert_eq!(loaded.version, "2");
         assert_eq!(loaded.findings.len(), 1);
         assert_eq!(loaded.findings[0].fingerprint.as_str(), "sha256:abc123");
     }
@@ -252,6 +311,27 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn load_migrates_version_1() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("v1.json");
+        fs::write(
+            &path,
+            r#"{"version": "1", "created_at": 0, "updated_at": 0, "vet_version": "0.3.0", "findings": [
+                {"fingerprint": "sha256:abc", "pattern_id": "test/pattern", "severity": "high", "file": "test.py",
+                 "secret_hash": "sha256:secret", "status": "accepted", "reason": "Test reason", "reviewed_at": 0}
+            ]}"#,
+        )
+        .unwrap();
+
+        let baseline = Baseline::load(&path).unwrap();
+
+        assert_eq!(baseline.version, "2");
+        assert_eq!(baseline.findings.len(), 1);
+        assert_eq!(baseline.findings[0].reviewed_by, None);
+        assert_eq!(baseline.findings[0].expires_at, None);
+    }
+
     #[test]
     fn add_finding_appends_new_finding() {
         let mut baseline = Baseline::new();
@@ -306,6 +386,18 @@ mod tests {
         assert!(finding.is_none());
     }
 
+    #[test]
+    fn record_line_updates_existing_finding_only() {
+        let mut baseline = Baseline::new();
+        baseline.add_finding(create_test_finding("sha256:abc"));
+        let fingerprint = Fingerprint::from_string("sha256:abc");
+
+        assert!(baseline.record_line(&fingerprint, 12));
+        assert!(!baseline.record_line(&fingerprint, 12));
+        assert!(!baseline.record_line(&Fingerprint::from_string("sha256:xyz"), 3));
+        assert_eq!(baseline.findings[0].line, Some(12));
+    }
+
     #[test]
     fn len_returns_number_of_findings() {
         let mut baseline = Baseline::new();
@@ -333,7 +425,7 @@ mod tests {
     fn default_creates_empty_baseline() {
         let baseline = Baseline::default();
 
-        assert_eq!(baseline.version, "1");
+        assert_eq!(baseline.version, "2");
         assert!(baseline.is_empty());
     }
 }
@@ -27,7 +27,7 @@ impl std::fmt::Display for BaselineStatus {
 ///
 /// Records the fingerprint, review status, and enough context to display
 /// a summary without needing to re-scan the original file.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BaselineFinding {
     /// Stable identifier derived from pattern ID, file path, and secret hash.
     pub fingerprint: Fingerprint,
@@ -53,6 +53,28 @@ pub struct BaselineFinding {
     /// Timestamp when the finding was reviewed.
     #[serde(with = "chrono::serde::ts_seconds")]
     pub reviewed_at: DateTime<Utc>,
+
+    /// Who reviewed the finding (added in version 2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_by: Option<String>,
+
+    /// Issue or ticket tracking the secret's rotation (added in version 2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+
+    /// When the acceptance lapses and the finding should be reported again
+    /// (added in version 2).
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "chrono::serde::ts_seconds_option"
+    )]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Line the secret was last seen on, as a hint for locating it
+    /// (added in version 2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
 }
 
 impl BaselineFinding {
@@ -76,8 +98,18 @@ impl BaselineFinding {
             status,
             reason,
             reviewed_at: Utc::now(),
+            reviewed_by: None,
+            ticket: None,
+            expires_at: None,
+            line: None,
         }
     }
+
+    /// Returns `true` if the finding has an expiry at or before `now`.
+    #[must_use]
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
 }
 
 #[cfg(test)]
@@ -151,4 +183,69 @@ mod tests {
         assert_eq!(finding.pattern_id, deserialized.pattern_id);
         assert_eq!(finding.status, deserialized.status);
     }
+
+    #[test]
+    fn version_2_fields_are_omitted_when_unset() {
+        let finding = BaselineFinding::new(
+            Fingerprint::from_string("sha256:abc123"),
+            "test/pattern".to_string(),
+            Severity::High,
+            "test.py".to_string(),
+            "sha256:secret".to_string(),
+            BaselineStatus::Accepted,
+            "Test reason".to_string(),
+        );
+
+        let json = serde_json::to_string(&finding).unwrap();
+
+        assert!(!json.contains("reviewed_by"));
+        assert!(!json.contains("expires_at"));
+    }
+
+    #[test]
+    fn is_expired_at_compares_expiry() {
+        let mut finding = BaselineFinding::new(
+            Fingerprint::from_string("sha256:abc123"),
+            "test/pattern".to_string(),
+            Severity::High,
+            "test.py".to_string(),
+            "sha256:secret".to_string(),
+            BaselineStatus::Accepted,
+            "Test reason".to_string(),
+        );
+        let now = Utc::now();
+
+        assert!(!finding.is_expired_at(now));
+
+        finding.expires_at = Some(now - chrono::TimeDelta::days(1));
+        assert!(finding.is_expired_at(now));
+
+        finding.expires_at = Some(now + chrono::TimeDelta::days(1));
+        assert!(!finding.is_expired_at(now));
+    }
+
+    #[test]
+    fn version_2_fields_roundtrip() {
+        let mut finding = BaselineFinding::new(
+            Fingerprint::from_string("sha256:abc123"),
+            "test/pattern".to_string(),
+            Severity::High,
+            "test.py".to_string(),
+            "sha256:secret".to_string(),
+            BaselineStatus::Accepted,
+            "Test reason".to_string(),
+        );
+        finding.reviewed_by = Some("alice@example.com".to_string());
+        finding.ticket = Some("SEC-123".to_string());
+        finding.expires_at = DateTime::from_timestamp(1_900_000_000, 0);
+        finding.line = Some(42);
+
+        let json = serde_json::to_string(&finding).unwrap();
+        let deserialized: BaselineFinding = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.reviewed_by.as_deref(), Some("alice@example.com"));
+        assert_eq!(deserialized.ticket.as_deref(), Some("SEC-123"));
+        assert_eq!(deserialized.expires_at, finding.expires_at);
+        assert_eq!(deserialized.line, Some(42));
+    }
 }
@@ -61,7 +61,7 @@ impl AsRef<str> for Fingerprint {
     }
 }
 
-fn normalise_path(path: &Path) -> String {
+pub(super) fn normalise_path(path: &Path) -> String {
     let path_str = path.to_string_lossy();
 
     path_str.replace('\\', "/").trim_start_matches("./").to_string()
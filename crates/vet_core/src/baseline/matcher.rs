@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use chrono::{DateTime, Utc};
+
 use super::file::Baseline;
 use super::fingerprint::Fingerprint;
 use crate::config::ConfigIgnore;
@@ -8,9 +10,11 @@ use crate::config::ConfigIgnore;
 ///
 /// Merges fingerprints from both a [`Baseline`] file and inline
 /// [`ConfigIgnore`] entries into a single `HashSet` for O(1) checks.
+/// Baseline entries whose expiry has passed are not suppressed.
 #[derive(Debug)]
 pub struct IgnoreMatcher {
     fingerprints: HashSet<Box<str>>,
+    expired: HashSet<Box<str>>,
 }
 
 impl IgnoreMatcher {
@@ -19,11 +23,23 @@ impl IgnoreMatcher {
     /// Duplicate fingerprints across both sources are deduplicated.
     #[must_use]
     pub fn new(baseline: Option<&Baseline>, config_ignores: &[ConfigIgnore]) -> Self {
+        Self::at(baseline, config_ignores, Utc::now())
+    }
+
+    /// Builds a matcher that treats baseline entries expiring at or before
+    /// `now` as expired.
+    #[must_use]
+    pub fn at(baseline: Option<&Baseline>, config_ignores: &[ConfigIgnore], now: DateTime<Utc>) -> Self {
         let mut fingerprints = HashSet::new();
+        let mut expired = HashSet::new();
 
         if let Some(baseline) = baseline {
             for finding in &baseline.findings {
-                fingerprints.insert(finding.fingerprint.as_str().into());
+                if finding.is_expired_at(now) {
+                    expired.insert(finding.fingerprint.as_str().into());
+                } else {
+                    fingerprints.insert(finding.fingerprint.as_str().into());
+                }
             }
         }
 
@@ -31,7 +47,9 @@ impl IgnoreMatcher {
             fingerprints.insert(ignore.fingerprint.as_str().into());
         }
 
-        Self { fingerprints }
+        expired.retain(|fingerprint| !fingerprints.contains(fingerprint));
+
+        Self { fingerprints, expired }
     }
 
     /// Returns `true` if the given fingerprint should be suppressed.
@@ -40,6 +58,13 @@ impl IgnoreMatcher {
         self.fingerprints.contains(fingerprint.as_str())
     }
 
+    /// Returns `true` if the fingerprint was accepted in the baseline but
+    /// the acceptance has expired, so it is no longer suppressed.
+    #[must_use]
+    pub fn is_expired(&self, fingerprint: &Fingerprint) -> bool {
+        self.expired.contains(fingerprint.as_str())
+    }
+
     /// Returns the total number of unique suppressed fingerprints.
     #[must_use]
     pub fn len(&self) -> usize {
@@ -140,6 +165,39 @@ mod tests {
         assert_eq!(matcher.len(), 1);
     }
 
+    #[test]
+    fn matcher_stops_suppressing_expired_entries() {
+        let mut baseline = create_baseline_with_fingerprints(&["sha256:expired", "sha256:current"]);
+        let now = Utc::now();
+        baseline.findings[0].expires_at = Some(now - chrono::TimeDelta::days(1));
+        baseline.findings[1].expires_at = Some(now + chrono::TimeDelta::days(1));
+
+        let matcher = IgnoreMatcher::at(Some(&baseline), &[], now);
+
+        assert!(!matcher.is_ignored(&Fingerprint::from_string("sha256:expired")));
+        assert!(matcher.is_expired(&Fingerprint::from_string("sha256:expired")));
+        assert!(matcher.is_ignored(&Fingerprint::from_string("sha256:current")));
+        assert!(!matcher.is_expired(&Fingerprint::from_string("sha256:current")));
+        assert_eq!(matcher.len(), 1);
+    }
+
+    #[test]
+    fn config_ignore_overrides_expired_baseline_entry() {
+        let mut baseline = create_baseline_with_fingerprints(&["sha256:same"]);
+        baseline.findings[0].expires_at = Some(Utc::now() - chrono::TimeDelta::days(1));
+        let ignores = vec![ConfigIgnore {
+            fingerprint: "sha256:same".to_string(),
+            pattern_id: "test/pattern".to_string(),
+            file: "test.py".to_string(),
+            reason: "test".to_string(),
+        }];
+
+        let matcher = IgnoreMatcher::new(Some(&baseline), &ignores);
+
+        assert!(matcher.is_ignored(&Fingerprint::from_string("sha256:same")));
+        assert!(!matcher.is_expired(&Fingerprint::from_string("sha256:same")));
+    }
+
     #[test]
     fn matcher_handles_empty_baseline() {
         let baseline = Baseline::new();
@@ -0,0 +1,184 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::file::Baseline;
+use super::finding::BaselineFinding;
+
+impl Baseline {
+    /// Three-way merges two baselines that diverged from `base`, matching
+    /// findings by fingerprint.
+    ///
+    /// A finding added or changed on one side is taken from that side, and
+    /// one removed on a side that the other left untouched is dropped. When
+    /// both sides changed the same finding differently, the most recent
+    /// review wins, with ties broken by content so the result does not
+    /// depend on which side is which. Findings are sorted by fingerprint.
+    ///
+    /// Returns the merged baseline and the number of findings both sides
+    /// changed differently.
+    #[must_use]
+    pub fn merge(base: &Self, ours: &Self, theirs: &Self) -> (Self, usize) {
+        let (base_findings, our_findings, their_findings) =
+            (by_fingerprint(base), by_fingerprint(ours), by_fingerprint(theirs));
+
+        let fingerprints: BTreeSet<&str> = our_findings.keys().chain(their_findings.keys()).copied().collect();
+
+        let mut conflicts = 0;
+        let mut findings = Vec::new();
+
+        for fingerprint in fingerprints {
+            let base = base_findings.get(fingerprint).copied();
+            let ours = our_findings.get(fingerprint).copied();
+            let theirs = their_findings.get(fingerprint).copied();
+
+            let merged = match (ours, theirs) {
+                (Some(ours), Some(theirs)) if ours == theirs || base == Some(theirs) => Some(ours),
+                (Some(ours), Some(theirs)) if base == Some(ours) => Some(theirs),
+                (Some(ours), Some(theirs)) => {
+                    conflicts += 1;
+                    Some(newest_review(ours, theirs))
+                }
+                (Some(side), None) | (None, Some(side)) => (base != Some(side)).then_some(side),
+                (None, None) => None,
+            };
+
+            findings.extend(merged.cloned());
+        }
+
+        let mut merged = Self::new();
+        merged.created_at = ours.created_at.min(theirs.created_at);
+        merged.updated_at = ours.updated_at.max(theirs.updated_at);
+        merged.findings = findings;
+
+        (merged, conflicts)
+    }
+}
+
+fn by_fingerprint(baseline: &Baseline) -> BTreeMap<&str, &BaselineFinding> {
+    baseline.findings.iter().map(|f| (f.fingerprint.as_str(), f)).collect()
+}
+
+fn newest_review<'a>(a: &'a BaselineFinding, b: &'a BaselineFinding) -> &'a BaselineFinding {
+    let content = |f: &BaselineFinding| serde_json::to_string(f).unwrap_or_default();
+
+    match a
+        .reviewed_at
+        .cmp(&b.reviewed_at)
+        .then_with(|| content(a).cmp(&content(b)))
+    {
+        Ordering::Less => b,
+        Ordering::Equal | Ordering::Greater => a,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::*;
+    use crate::Severity;
+    use crate::baseline::{BaselineStatus, Fingerprint};
+
+    fn entry(fingerprint: &str, reason: &str) -> BaselineFinding {
+        BaselineFinding::new(
+            Fingerprint::from_string(fingerprint),
+            "test/pattern".to_string(),
+            Severity::High,
+            "test.py".to_string(),
+            "sha256:secret".to_string(),
+            BaselineStatus::Accepted,
+            reason.to_string(),
+        )
+    }
+
+    fn baseline(entries: &[&BaselineFinding]) -> Baseline {
+        let mut baseline = Baseline::new();
+        baseline.findings = entries.iter().map(|&e| e.clone()).collect();
+        baseline
+    }
+
+    fn fingerprints(baseline: &Baseline) -> Vec<&str> {
+        baseline.findings.iter().map(|f| f.fingerprint.as_str()).collect()
+    }
+
+    #[test]
+    fn keeps_additions_from_both_sides_sorted() {
+        let shared = entry("sha256:b", "shared");
+        let base = baseline(&[&shared]);
+        let ours = baseline(&[&shared, &entry("sha256:c", "ours")]);
+        let theirs = baseline(&[&entry("sha256:a", "theirs"), &shared]);
+
+        let (merged, conflicts) = Baseline::merge(&base, &ours, &theirs);
+
+        assert_eq!(fingerprints(&merged), ["sha256:a", "sha256:b", "sha256:c"]);
+        assert_eq!(conflicts, 0);
+    }
+
+    #[test]
+    fn removal_wins_over_untouched_entry() {
+        let kept = entry("sha256:a", "kept");
+        let removed = entry("sha256:b", "removed");
+        let base = baseline(&[&kept, &removed]);
+        let ours = baseline(&[&kept]);
+        let theirs = baseline(&[&kept, &removed]);
+
+        let (merged, _) = Baseline::merge(&base, &ours, &theirs);
+
+        assert_eq!(fingerprints(&merged), ["sha256:a"]);
+    }
+
+    #[test]
+    fn change_wins_over_removal() {
+        let original = entry("sha256:a", "original");
+        let mut changed = original.clone();
+        changed.status = BaselineStatus::Ignored;
+
+        let (merged, _) = Baseline::merge(&baseline(&[&original]), &baseline(&[]), &baseline(&[&changed]));
+
+        assert_eq!(merged.findings, [changed]);
+    }
+
+    #[test]
+    fn one_sided_change_is_taken() {
+        let original = entry("sha256:a", "original");
+        let mut changed = original.clone();
+        changed.reason = "re-reviewed".to_string();
+
+        let (merged, conflicts) =
+            Baseline::merge(&baseline(&[&original]), &baseline(&[&original]), &baseline(&[&changed]));
+
+        assert_eq!(merged.findings[0].reason, "re-reviewed");
+        assert_eq!(conflicts, 0);
+    }
+
+    #[test]
+    fn conflicting_changes_resolve_to_newest_review_either_way_round() {
+        let original = entry("sha256:a", "original");
+        let mut older = original.clone();
+        older.reason = "older".to_string();
+        older.reviewed_at = original.reviewed_at + Duration::seconds(10);
+        let mut newer = original.clone();
+        newer.reason = "newer".to_string();
+        newer.status = BaselineStatus::Ignored;
+        newer.reviewed_at = Utc::now() + Duration::seconds(20);
+
+        let base = baseline(&[&original]);
+        let (ab, conflicts) = Baseline::merge(&base, &baseline(&[&older]), &baseline(&[&newer]));
+        let (ba, _) = Baseline::merge(&base, &baseline(&[&newer]), &baseline(&[&older]));
+
+        assert_eq!(conflicts, 1);
+        assert_eq!(ab.findings, [newer.clone()]);
+        assert_eq!(ba.findings, [newer]);
+    }
+
+    #[test]
+    fn concurrent_additions_resolve_independently_of_side() {
+        let ours = entry("sha256:a", "alpha");
+        let theirs = entry("sha256:a", "beta");
+
+        let (ab, _) = Baseline::merge(&Baseline::new(), &baseline(&[&ours]), &baseline(&[&theirs]));
+        let (ba, _) = Baseline::merge(&Baseline::new(), &baseline(&[&theirs]), &baseline(&[&ours]));
+
+        assert_eq!(ab.findings, ba.findings);
+    }
+}
@@ -5,9 +5,12 @@ mod file;
 mod finding;
 mod fingerprint;
 mod matcher;
+mod merge;
+mod relocation;
 
 pub use error::BaselineError;
 pub use file::Baseline;
 pub use finding::{BaselineFinding, BaselineStatus};
 pub use fingerprint::Fingerprint;
 pub use matcher::IgnoreMatcher;
+pub use relocation::Relocation;
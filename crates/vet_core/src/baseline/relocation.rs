@@ -0,0 +1,209 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::file::Baseline;
+use super::fingerprint::{Fingerprint, normalise_path};
+use crate::finding::Finding;
+
+/// A baseline entry carried to the path its secret is now found at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relocation {
+    /// Pattern that matched the secret.
+    pub pattern_id: String,
+    /// Path the entry was recorded under.
+    pub from: String,
+    /// Path the secret is now found at.
+    pub to: String,
+}
+
+impl Baseline {
+    /// Moves entries for renamed files onto findings with the same pattern
+    /// and secret hash, updating their fingerprint, file and line hint.
+    ///
+    /// Fingerprints include the file path, so without this a moved file
+    /// turns every baselined secret in it into a new finding. An entry may
+    /// move if its recorded file no longer exists under `root`, or if
+    /// `renames` (original path to new path, as detected by git) says the
+    /// file was renamed. When several entries hold the same secret, the one
+    /// `renames` maps to the finding's path is preferred.
+    pub fn relocate(
+        &mut self,
+        findings: &[Finding],
+        renames: &HashMap<String, String>,
+        root: &Path,
+    ) -> Vec<Relocation> {
+        self.relocate_where(findings, renames, |file| root.join(file).exists())
+    }
+
+    /// Returns the normalised paths of entries not among `seen`, which are
+    /// the paths [`Baseline::relocate`] looks up in its renames.
+    #[must_use]
+    pub fn stale_paths(&self, seen: &HashSet<Fingerprint>) -> HashSet<String> {
+        self.stale_findings(seen)
+            .map(|entry| normalise_path(Path::new(&entry.file)))
+            .collect()
+    }
+
+    fn relocate_where(
+        &mut self,
+        findings: &[Finding],
+        renames: &HashMap<String, String>,
+        exists: impl Fn(&str) -> bool,
+    ) -> Vec<Relocation> {
+        let mut known: HashSet<Fingerprint> = self.findings.iter().map(|f| f.fingerprint.clone()).collect();
+        let present: HashSet<Fingerprint> = findings.iter().map(Finding::baseline_fingerprint).collect();
+
+        let mut movable: HashMap<(&str, &str), Vec<usize>> = HashMap::new();
+        for (index, entry) in self.findings.iter().enumerate() {
+            if present.contains(&entry.fingerprint) {
+                continue;
+            }
+            if exists(&entry.file) && !renames.contains_key(&normalise_path(Path::new(&entry.file))) {
+                continue;
+            }
+            movable
+                .entry((&entry.pattern_id, &entry.secret_hash))
+                .or_default()
+                .push(index);
+        }
+
+        if movable.is_empty() {
+            return Vec::new();
+        }
+
+        let mut moves = Vec::new();
+        for finding in findings {
+            let fingerprint = finding.baseline_fingerprint();
+            if known.contains(&fingerprint) {
+                continue;
+            }
+
+            let key = (finding.pattern_id.as_ref(), finding.secret.hash_hex());
+            let Some(candidates) = movable.get_mut(&key).filter(|c| !c.is_empty()) else {
+                continue;
+            };
+
+            let to = normalise_path(&finding.path);
+            let position = candidates
+                .iter()
+                .position(|&i| renames.get(&normalise_path(Path::new(&self.findings[i].file))) == Some(&to))
+                .unwrap_or(0);
+
+            known.insert(fingerprint.clone());
+            moves.push((candidates.remove(position), finding, fingerprint));
+        }
+
+        moves
+            .into_iter()
+            .map(|(index, finding, fingerprint)| {
+                let entry = &mut self.findings[index];
+                let to = finding.path.to_string_lossy().into_owned();
+                let from = std::mem::replace(&mut entry.file, to.clone());
+                entry.fingerprint = fingerprint;
+                entry.line = Some(finding.line());
+
+                Relocation {
+                    pattern_id: entry.pattern_id.clone(),
+                    from,
+                    to,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+    use crate::baseline::{BaselineFinding, BaselineStatus};
+    use crate::test_utils::make_finding;
+
+    fn finding_at(path: &str, secret: &str) -> Finding {
+        let mut finding = make_finding("test/pattern", secret);
+        finding.path = Path::new(path).into();
+        finding
+    }
+
+    fn baseline_with(findings: &[Finding]) -> Baseline {
+        let mut baseline = Baseline::new();
+        for finding in findings {
+            baseline.add_finding(BaselineFinding::new(
+                finding.baseline_fingerprint(),
+                finding.pattern_id.to_string(),
+                Severity::High,
+                finding.path.to_string_lossy().into_owned(),
+                finding.secret.hash_hex().to_string(),
+                BaselineStatus::Accepted,
+                "test".to_string(),
+            ));
+        }
+        baseline
+    }
+
+    #[test]
+    fn moves_entry_whose_file_is_gone() {
+        let mut baseline = baseline_with(&[finding_at("config/app.py", "secret-one")]);
+        let moved = finding_at("src/config/app.py", "secret-one");
+
+        let relocations = baseline.relocate_where(std::slice::from_ref(&moved), &HashMap::new(), |_| false);
+
+        assert_eq!(
+            relocations,
+            [Relocation {
+                pattern_id: "test/pattern".to_string(),
+                from: "config/app.py".to_string(),
+                to: "src/config/app.py".to_string(),
+            }]
+        );
+        assert!(baseline.contains_fingerprint(&moved.baseline_fingerprint()));
+        assert_eq!(baseline.findings[0].file, "src/config/app.py");
+        assert_eq!(baseline.len(), 1);
+    }
+
+    #[test]
+    fn keeps_entry_whose_file_still_exists() {
+        let mut baseline = baseline_with(&[finding_at("config/app.py", "secret-one")]);
+        let copied = finding_at("src/config/app.py", "secret-one");
+
+        let relocations = baseline.relocate_where(&[copied], &HashMap::new(), |_| true);
+
+        assert!(relocations.is_empty());
+        assert_eq!(baseline.findings[0].file, "config/app.py");
+    }
+
+    #[test]
+    fn git_renames_move_entries_whose_path_was_reused() {
+        let mut baseline = baseline_with(&[finding_at("config/app.py", "secret-one")]);
+        let renames = HashMap::from([("config/app.py".to_string(), "src/app.py".to_string())]);
+
+        let relocations = baseline.relocate_where(&[finding_at("./src/app.py", "secret-one")], &renames, |_| true);
+
+        assert_eq!(relocations.len(), 1);
+        assert_eq!(baseline.findings[0].file, "./src/app.py");
+    }
+
+    #[test]
+    fn git_renames_pick_between_entries_with_the_same_secret() {
+        let mut baseline = baseline_with(&[finding_at("a.env", "shared"), finding_at("b.env", "shared")]);
+        let renames = HashMap::from([("b.env".to_string(), "moved/b.env".to_string())]);
+
+        baseline.relocate_where(&[finding_at("moved/b.env", "shared")], &renames, |_| false);
+
+        assert_eq!(baseline.findings[0].file, "a.env");
+        assert_eq!(baseline.findings[1].file, "moved/b.env");
+    }
+
+    #[test]
+    fn different_secret_is_not_relocated() {
+        let mut baseline = baseline_with(&[finding_at("config/app.py", "secret-one")]);
+
+        let relocations = baseline.relocate_where(
+            &[finding_at("src/config/app.py", "secret-two")],
+            &HashMap::new(),
+            |_| false,
+        );
+
+        assert!(relocations.is_empty());
+    }
+}
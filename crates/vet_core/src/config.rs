@@ -1,4 +1,7 @@
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -61,9 +64,86 @@ pub struct Config {
     #[serde(default)]
     pub baseline_path: Option<String>,
 
+    /// Match baseline entries for moved files by pattern and secret hash
+    /// when their recorded path no longer exists or git saw it renamed.
+    #[serde(default)]
+    pub baseline_follow_renames: bool,
+
     /// Findings that have been explicitly acknowledged and suppressed.
     #[serde(default, rename = "ignore")]
     pub ignores: Vec<ConfigIgnore>,
+
+    /// Remediation policy used by `vet fix --yes`, keyed by pattern ID,
+    /// group wildcard (e.g. `"payments/*"`) or `"*"` for everything.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fix: BTreeMap<String, FixPolicy>,
+}
+
+/// Remediation applied to a finding when `vet fix` runs non-interactively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FixPolicy {
+    /// Replace the secret with `<REDACTED>`.
+    Redact,
+    /// Replace the secret with an environment variable placeholder.
+    Placeholder,
+    /// Replace the string literal with the language's environment variable
+    /// lookup and move the value into `.env`.
+    ExtractEnv,
+    /// Delete the line containing the secret.
+    DeleteLine,
+    /// Append a `vet:ignore` comment to the line.
+    Ignore,
+    /// Leave the finding untouched.
+    Skip,
+}
+
+impl FixPolicy {
+    /// Returns the policy name as written in `.vet.toml`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Redact => "redact",
+            Self::Placeholder => "placeholder",
+            Self::ExtractEnv => "extract-env",
+            Self::DeleteLine => "delete-line",
+            Self::Ignore => "ignore",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+impl fmt::Display for FixPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FixPolicy {
+    type Err = ParseFixPolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "redact" => Ok(Self::Redact),
+            "placeholder" => Ok(Self::Placeholder),
+            "extract-env" => Ok(Self::ExtractEnv),
+            "delete-line" => Ok(Self::DeleteLine),
+            "ignore" => Ok(Self::Ignore),
+            "skip" => Ok(Self::Skip),
+            _ => Err(ParseFixPolicyError {
+                invalid_value: s.into(),
+            }),
+        }
+    }
+}
+
+/// Error returned when a string is not a recognised [`FixPolicy`].
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "invalid fix action '{invalid_value}': expected one of 'redact', 'placeholder', 'extract-env', 'delete-line', 'ignore', 'skip'"
+)]
+pub struct ParseFixPolicyError {
+    invalid_value: Box<str>,
 }
 
 /// A user-defined secret detection pattern declared in `.vet.toml`.
@@ -161,6 +241,26 @@ impl Config {
     pub fn compile_custom_patterns(&self) -> Result<Vec<Pattern>, PatternError> {
         self.patterns.iter().map(CustomPattern::compile).collect()
     }
+
+    /// Resolves the `[fix]` policy for a pattern.
+    ///
+    /// An exact pattern ID wins over a group wildcard such as `"payments/*"`,
+    /// which in turn wins over the catch-all `"*"`.
+    #[must_use]
+    pub fn fix_policy(&self, pattern_id: &str) -> Option<FixPolicy> {
+        if let Some(policy) = self.fix.get(pattern_id) {
+            return Some(*policy);
+        }
+
+        self.fix
+            .iter()
+            .filter_map(|(key, policy)| {
+                let prefix = key.strip_suffix('*')?;
+                pattern_id.starts_with(prefix).then_some((prefix.len(), *policy))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, policy)| policy)
+    }
 }
 
 fn read_file(path: &Path) -> Result<String, ConfigError> {
@@ -573,12 +673,14 @@ mod tests {
             patterns: vec![],
             disabled_patterns: vec!["x".into()],
             baseline_path: Some(".vet-baseline.json".into()),
+            baseline_follow_renames: true,
             ignores: vec![ConfigIgnore {
                 fingerprint: "sha256:abc123".into(),
                 pattern_id: "aws/access-key".into(),
                 file: "src/test.py".into(),
                 reason: "test".into(),
             }],
+            fix: BTreeMap::from([("payments/*".into(), FixPolicy::Placeholder)]),
         };
 
         let toml = original.to_toml().unwrap();
@@ -590,8 +692,10 @@ mod tests {
         assert_eq!(restored.minimum_confidence, original.minimum_confidence);
         assert_eq!(restored.disabled_patterns, original.disabled_patterns);
         assert_eq!(restored.baseline_path, original.baseline_path);
+        assert!(restored.baseline_follow_renames);
         assert_eq!(restored.ignores.len(), 1);
         assert_eq!(restored.ignores[0].fingerprint, "sha256:abc123");
+        assert_eq!(restored.fix, original.fix);
     }
 
     #[test]
@@ -620,4 +724,63 @@ mod tests {
         };
         assert!(error.path().is_none());
     }
+
+    #[test]
+    fn from_toml_parses_fix_policy_table() {
+        let config = Config::from_toml(
+            r#"
+            [fix]
+            "payments/*" = "placeholder"
+            "vcs/github-pat" = "delete-line"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.fix.get("payments/*"), Some(&FixPolicy::Placeholder));
+        assert_eq!(config.fix.get("vcs/github-pat"), Some(&FixPolicy::DeleteLine));
+    }
+
+    #[test]
+    fn from_toml_rejects_unknown_fix_policy() {
+        let result = Config::from_toml("[fix]\n\"*\" = \"shred\"\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn fix_policy_prefers_exact_id_over_group_wildcard() {
+        let config = Config::from_toml(
+            r#"
+            [fix]
+            "*" = "redact"
+            "payments/*" = "placeholder"
+            "payments/stripe-secret" = "skip"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.fix_policy("payments/stripe-secret"), Some(FixPolicy::Skip));
+        assert_eq!(
+            config.fix_policy("payments/paypal-secret"),
+            Some(FixPolicy::Placeholder)
+        );
+        assert_eq!(config.fix_policy("vcs/github-pat"), Some(FixPolicy::Redact));
+    }
+
+    #[test]
+    fn fix_policy_returns_none_without_matching_entry() {
+        let config = Config::from_toml("[fix]\n\"payments/*\" = \"redact\"\n").unwrap();
+        assert_eq!(config.fix_policy("vcs/github-pat"), None);
+    }
+
+    #[test]
+    fn fix_policy_parses_from_cli_string() {
+        assert_eq!("delete-line".parse::<FixPolicy>().unwrap(), FixPolicy::DeleteLine);
+        assert!("shred".parse::<FixPolicy>().is_err());
+    }
+
+    #[test]
+    fn to_toml_omits_empty_fix_table() {
+        let toml = Config::default().to_toml().unwrap();
+        assert!(!toml.contains("[fix]"));
+    }
 }
@@ -0,0 +1,212 @@
+use std::io;
+use std::path::Path;
+
+use crate::fs_util::atomic_write;
+
+/// File name of the local environment file secrets are moved into.
+pub const DOTENV_FILENAME: &str = ".env";
+
+/// File name of the git ignore file `.env` is added to.
+pub const GITIGNORE_FILENAME: &str = ".gitignore";
+
+/// Derives an environment variable name from a pattern ID (e.g. `"payments/stripe-secret"` becomes `"STRIPE_SECRET"`).
+#[must_use]
+pub fn derive_env_var_name(pattern_id: &str) -> String {
+    pattern_id
+        .split('/')
+        .next_back()
+        .unwrap_or(pattern_id)
+        .replace('-', "_")
+        .to_uppercase()
+}
+
+/// Chooses the variable name to store `value` under in a `.env` file.
+///
+/// Reuses the existing entry when the value is already present under any
+/// name, and otherwise picks `base`, `base_2`, `base_3`... until one is free.
+/// Returns the name and whether an entry for it already exists.
+#[must_use]
+pub fn choose_name(dotenv: &str, base: &str, value: &str) -> (String, bool) {
+    let entries: Vec<(&str, String)> = dotenv.lines().filter_map(parse_entry).collect();
+
+    if let Some((name, _)) = entries.iter().find(|(_, v)| v == value) {
+        return ((*name).to_string(), true);
+    }
+
+    // One more candidate than there are entries, so at least one is free.
+    let taken = |name: &str| entries.iter().any(|(n, _)| *n == name);
+    let name = std::iter::once(base.to_string())
+        .chain((2..=entries.len() + 1).map(|n| format!("{base}_{n}")))
+        .find(|name| !taken(name))
+        .unwrap_or_else(|| base.to_string());
+
+    (name, false)
+}
+
+/// Formats a `NAME=value` line, quoting the value when it contains
+/// characters a `.env` parser would otherwise treat specially.
+#[must_use]
+pub fn format_entry(name: &str, value: &str) -> String {
+    let is_plain = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '+' | '=' | ':' | '@' | '~'));
+
+    if is_plain {
+        format!("{name}={value}\n")
+    } else {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("{name}=\"{escaped}\"\n")
+    }
+}
+
+/// Returns `true` if a `.gitignore` already ignores the root `.env` file.
+#[must_use]
+pub fn gitignore_covers_dotenv(gitignore: &str) -> bool {
+    gitignore
+        .lines()
+        .map(str::trim)
+        .any(|line| matches!(line, ".env" | "/.env" | ".env*" | "/.env*" | "*.env"))
+}
+
+/// Returns the text to append to `existing` so that `addition` starts on a
+/// line of its own.
+#[must_use]
+pub fn appendix(existing: &str, addition: &str) -> String {
+    if existing.is_empty() || existing.ends_with('\n') {
+        addition.to_string()
+    } else {
+        format!("\n{addition}")
+    }
+}
+
+/// Reads the `.env` file in `root` and chooses a name for `value`, without
+/// modifying anything on disk.
+pub fn resolve_name(root: &Path, base: &str, value: &str) -> io::Result<String> {
+    let dotenv = read_or_empty(&root.join(DOTENV_FILENAME))?;
+    Ok(choose_name(&dotenv, base, value).0)
+}
+
+/// Stores `value` as `name` in the `.env` file in `root`, creating the file
+/// if needed, and makes sure `.gitignore` lists it so the secret is never
+/// committed.
+pub fn store(root: &Path, name: &str, value: &str) -> io::Result<()> {
+    let dotenv_path = root.join(DOTENV_FILENAME);
+    let dotenv = read_or_empty(&dotenv_path)?;
+    if !dotenv
+        .lines()
+        .filter_map(parse_entry)
+        .any(|(n, v)| n == name && v == value)
+    {
+        let updated = dotenv.clone() + &appendix(&dotenv, &format_entry(name, value));
+        atomic_write(&dotenv_path, &updated)?;
+    }
+
+    let gitignore_path = root.join(GITIGNORE_FILENAME);
+    let gitignore = read_or_empty(&gitignore_path)?;
+    if !gitignore_covers_dotenv(&gitignore) {
+        let updated = gitignore.clone() + &appendix(&gitignore, &format!("{DOTENV_FILENAME}\n"));
+        atomic_write(&gitignore_path, &updated)?;
+    }
+
+    Ok(())
+}
+
+fn read_or_empty(path: &Path) -> io::Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e),
+    }
+}
+
+fn parse_entry(line: &str) -> Option<(&str, String)> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return None;
+    }
+
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (name, value) = line.split_once('=')?;
+    let value = value.trim();
+
+    let value = if let Some(quoted) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        quoted.replace("\\\"", "\"").replace("\\\\", "\\")
+    } else if let Some(quoted) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        quoted.to_string()
+    } else {
+        value.to_string()
+    };
+
+    Some((name.trim(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn derive_env_var_name_extracts_after_slash() {
+        assert_eq!(derive_env_var_name("payments/stripe-secret"), "STRIPE_SECRET");
+    }
+
+    #[test]
+    fn derive_env_var_name_converts_hyphens() {
+        assert_eq!(derive_env_var_name("ai/openai-api-key"), "OPENAI_API_KEY");
+    }
+
+    #[test]
+    fn derive_env_var_name_handles_no_slash() {
+        assert_eq!(derive_env_var_name("simple-pattern"), "SIMPLE_PATTERN");
+    }
+
+    #[test]
+    fn choose_name_uses_base_when_free() {
+        assert_eq!(choose_name("OTHER=1\n", "KEY", "secret"), ("KEY".into(), false));
+    }
+
+    #[test]
+    fn choose_name_reuses_entry_with_same_value() {
+        assert_eq!(
+            choose_name("export OLD_KEY=\"secret\"\n", "KEY", "secret"),
+            ("OLD_KEY".into(), true)
+        );
+    }
+
+    #[test]
+    fn choose_name_suffixes_when_taken_by_other_value() {
+        assert_eq!(choose_name("KEY=a\nKEY_2=b\n", "KEY", "c"), ("KEY_3".into(), false));
+    }
+
+    #[test]
+    fn format_entry_quotes_special_characters() {
+        assert_eq!(format_entry("KEY", "abc/123+="), "KEY=abc/123+=\n");
+        assert_eq!(format_entry("KEY", "a b\"c"), "KEY=\"a b\\\"c\"\n");
+    }
+
+    #[test]
+    fn gitignore_covers_dotenv_recognises_common_entries() {
+        assert!(gitignore_covers_dotenv("target/\n/.env\n"));
+        assert!(gitignore_covers_dotenv(".env*\n"));
+        assert!(!gitignore_covers_dotenv(".env.local\n"));
+    }
+
+    #[test]
+    fn store_creates_dotenv_and_gitignore_entry() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "target/").unwrap();
+
+        store(dir.path(), "KEY", "secret").unwrap();
+        store(dir.path(), "KEY", "secret").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(".env")).unwrap(),
+            "KEY=secret\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(".gitignore")).unwrap(),
+            "target/\n.env\n"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::fs::{self, File};
+use std::fs::{self, File, OpenOptions};
 use std::io::{self, Write};
 use std::path::Path;
 
@@ -6,8 +6,31 @@ use std::path::Path;
 /// first, syncing to disk, then renaming into place.
 pub fn atomic_write(path: &Path, content: &str) -> io::Result<()> {
     let temp_path = path.with_extension("tmp");
+    let file = File::create(&temp_path)?;
+    write_and_rename(file, &temp_path, path, content)
+}
+
+/// Like [`atomic_write`], but the file is created readable and writable by
+/// its owner only, for content such as raw secrets.
+pub fn atomic_write_private(path: &Path, content: &str) -> io::Result<()> {
+    let temp_path = path.with_extension("tmp");
+
+    // A leftover temp file would keep its own permissions when reopened.
+    match fs::remove_file(&temp_path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
 
-    let mut file = File::create(&temp_path)?;
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let file = options.open(&temp_path)?;
+    write_and_rename(file, &temp_path, path, content)
+}
+
+fn write_and_rename(mut file: File, temp_path: &Path, path: &Path, content: &str) -> io::Result<()> {
     file.write_all(content.as_bytes())?;
 
     // Ensure data is persisted to disk before rename
@@ -16,7 +39,7 @@ pub fn atomic_write(path: &Path, content: &str) -> io::Result<()> {
     // Drop file handle before rename (Windows compatibility)
     drop(file);
 
-    fs::rename(&temp_path, path)?;
+    fs::rename(temp_path, path)?;
 
     Ok(())
 }
@@ -76,4 +99,25 @@ mod tests {
 
         fs::remove_file(&test_file).unwrap();
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn atomic_write_private_restricts_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = std::env::temp_dir();
+        let test_file = temp_dir.join("vet_atomic_write_test_private.txt");
+
+        // A world-readable file being replaced must not stay world-readable.
+        fs::write(&test_file, "old content").unwrap();
+        fs::set_permissions(&test_file, fs::Permissions::from_mode(0o644)).unwrap();
+
+        atomic_write_private(&test_file, "secret").unwrap();
+
+        let mode = fs::metadata(&test_file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(fs::read_to_string(&test_file).unwrap(), "secret");
+
+        fs::remove_file(&test_file).unwrap();
+    }
 }
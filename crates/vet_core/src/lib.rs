@@ -32,6 +32,8 @@ pub mod comment_syntax;
 /// User configuration loaded from `.vet.toml`.
 pub mod config;
 pub(crate) mod entropy;
+/// Moving secrets into a gitignored `.env` file.
+pub mod env_file;
 /// Error types for pattern compilation, configuration, and baseline operations.
 pub mod error;
 /// Types representing detected secrets and their locations.
@@ -51,8 +53,8 @@ pub(crate) mod test_utils;
 /// Text utilities for line boundary detection.
 pub mod text;
 
-pub use baseline::{Baseline, BaselineError, BaselineFinding, BaselineStatus, Fingerprint, IgnoreMatcher};
-pub use config::{Config, ConfigError, ConfigIgnore, CustomPattern};
+pub use baseline::{Baseline, BaselineError, BaselineFinding, BaselineStatus, Fingerprint, IgnoreMatcher, Relocation};
+pub use config::{Config, ConfigError, ConfigIgnore, CustomPattern, FixPolicy, ParseFixPolicyError};
 pub use error::{PatternError, VetError};
 pub use finding::{Confidence, Finding, FindingId, Secret, Span};
 pub use pattern::{DetectionStrategy, Group, Pattern, PatternRegistry, Severity};
@@ -1,7 +1,9 @@
 //! Convenience re-exports of the most commonly used types.
 
-pub use crate::baseline::{Baseline, BaselineError, BaselineFinding, BaselineStatus, Fingerprint, IgnoreMatcher};
-pub use crate::config::{Config, ConfigError};
+pub use crate::baseline::{
+    Baseline, BaselineError, BaselineFinding, BaselineStatus, Fingerprint, IgnoreMatcher, Relocation,
+};
+pub use crate::config::{Config, ConfigError, FixPolicy};
 pub use crate::error::{PatternError, VetError};
 pub use crate::finding::{Confidence, Finding, FindingId, Secret, Span};
 pub use crate::pattern::{DetectionStrategy, Group, Pattern, PatternRegistry, Severity};
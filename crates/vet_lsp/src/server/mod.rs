@@ -194,7 +194,7 @@ impl LanguageServer for VetLanguageServer {
 
         let state = self.state.read().await;
         let document = state.get_document(uri);
-        let actions = actions_for_diagnostics(uri, range, diagnostics, document);
+        let actions = actions_for_diagnostics(uri, range, diagnostics, document, &state.workspace_roots);
 
         Ok(Some(actions).filter(|a| !a.is_empty()))
     }